@@ -65,9 +65,9 @@ bb_write_sizes_and_constraints() {
   jq . "$OUT_JSON_PATH" || true
 
   if command -v noir-profiler >/dev/null 2>&1; then
-    # Derive input size label from the output filename: <target>_<SIZE>_sizes.json
+    # Derive input size label from the output filename: <target>_<SIZE>_<system>[_<feature>]_sizes.json
     local SIZE_LABEL
-    SIZE_LABEL=$(basename "$OUT_JSON_PATH" | sed -E "s/^${TARGET_NAME}_([^_]+)_sizes\\.json$/\\1/")
+    SIZE_LABEL=$(basename "$OUT_JSON_PATH" | sed -E "s/^${TARGET_NAME}_([0-9]+)_.*sizes\\.json$/\\1/")
 
     local CIRCUIT_SIZE
     CIRCUIT_SIZE=$(
@@ -3,7 +3,7 @@ set -euo pipefail
 
 # Required env vars:
 # - STATE_JSON: path to JSON arguments for proving
-# - SIZES_JSON: output JSON path (defaults to <dir>/${TARGET}_<INPUT_SIZE>_sizes.json when run via benchmark.sh)
+# - SIZES_JSON: output JSON path (defaults to <dir>/${TARGET}_<INPUT_SIZE>_<system>[_<feature>]_sizes.json when run via benchmark.sh)
 
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 OUT_JSON="${SIZES_JSON:-}"
@@ -18,6 +18,7 @@ OUT_JSON="${SIZES_JSON:-}"
 bb_clear_crs
 
 # Run one proving cycle to generate artifacts for measurement
+"$SCRIPT_DIR/ecdsa_witness.sh" >/dev/null 2>&1 || true
 "$SCRIPT_DIR/ecdsa_prove.sh" >/dev/null 2>&1 || true
 
 # Measure CRS size after proving
@@ -11,15 +11,16 @@ SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 WORKSPACE_ROOT_PATH=$(jq -r '."workspace-root-path"' "$STATE_JSON")
 CIRCUIT_PATH=$(jq -r '."circuit-path"' "$STATE_JSON")
 TOML_PATH=$(jq -r '."toml-path"' "$STATE_JSON")
+PACKAGE=$(jq -r '.package' "$STATE_JSON")
 
 # Change to workspace root
 cd "$WORKSPACE_ROOT_PATH"
 
 #### Step 1: Witness generation ####
 WITNESS_FILE="ecdsa.gz"
-nargo execute --prover-name $TOML_PATH --package "ecdsa" $WITNESS_FILE
+nargo execute --prover-name $TOML_PATH --package "$PACKAGE" $WITNESS_FILE
 
 #### Step 2: bb prove ####
-bb prove -b "$CIRCUIT_PATH" -w "$WORKSPACE_ROOT_PATH/target/$WITNESS_FILE" --write_vk -o "$WORKSPACE_ROOT_PATH/target/"
+bb prove -b "$CIRCUIT_PATH" -w "$WORKSPACE_ROOT_PATH/target/$WITNESS_FILE" --write_vk -o "$WORKSPACE_ROOT_PATH/target/" ${FEATURE:+--oracle_hash "$FEATURE"}
 
 cd ../..
\ No newline at end of file
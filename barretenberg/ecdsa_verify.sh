@@ -16,6 +16,6 @@ cd "$WORKSPACE_ROOT_PATH"
 #### bb verify ####
 PROOF_PATH="${WORKSPACE_ROOT_PATH}/target/proof"
 VK_PATH="${WORKSPACE_ROOT_PATH}/target/vk"
-bb verify -p "$PROOF_PATH" -vk "$VK_PATH"
+bb verify -p "$PROOF_PATH" -vk "$VK_PATH" ${FEATURE:+--oracle_hash "$FEATURE"}
 
 cd ../..
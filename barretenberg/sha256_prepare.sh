@@ -5,6 +5,9 @@ set -euo pipefail
 # - UTILS_BIN: path to utils binary
 # - INPUT_SIZE: input size in bytes
 # - STATE_JSON: output JSON file path
+# Optional:
+# - NEGATIVE_CASE, NEGATIVE_CASE_OUT: soundness phase of benchmark.sh, prepare
+#   this invalid instance and confirm it in NEGATIVE_CASE_OUT
 
 : "${UTILS_BIN:?UTILS_BIN is required}"
 : "${INPUT_SIZE:?INPUT_SIZE is required}"
@@ -14,8 +17,12 @@ set -euo pipefail
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 WORKSPACE_ROOT_PATH="${SCRIPT_DIR}/circuits"
 
+# `sha256` returns the digest instead of checking it, so invalid instances
+# are proven with `sha256_check`, which takes the digest as a public input.
+PACKAGE="sha256${NEGATIVE_CASE:+_check}"
+
 # Update circuit to use INPUT_SIZE for the input parameter only
-CIRCUIT_SOURCE="${WORKSPACE_ROOT_PATH}/hash/sha256/src/main.nr"
+CIRCUIT_SOURCE="${WORKSPACE_ROOT_PATH}/hash/${PACKAGE}/src/main.nr"
 if [[ -f "$CIRCUIT_SOURCE" ]]; then
   # Replace the input array length in `fn main(input: [u8; N], ...)`
   sed -E -i.bak "s/(fn[[:space:]]+main\([[:space:]]*input:[[:space:]]*\[u8;)[[:space:]]*[0-9]+/\1 ${INPUT_SIZE}/" "$CIRCUIT_SOURCE"
@@ -30,90 +37,26 @@ cd "$WORKSPACE_ROOT_PATH"
 nargo compile --workspace --silence-warnings --skip-brillig-constraints-check
 cd ../..
 
-CIRCUIT_PATH="${WORKSPACE_ROOT_PATH}/target/sha256.json"
+CIRCUIT_PATH="${WORKSPACE_ROOT_PATH}/target/${PACKAGE}.json"
 
 ####   Generate input(Prover.toml)   ####
-GEN="$("$UTILS_BIN" sha256 -n ${INPUT_SIZE})"
-MSG="$(printf "%s\n" "$GEN" | sed -n '1p')"
-HEX_NO_PREFIX="$(printf "%s\n" "$GEN" | sed -n '2p')"
-
-if [[ -z "$MSG" || -z "$HEX_NO_PREFIX" ]]; then
-  echo "prepare.sh: generator output malformed" >&2
-  exit 2
-fi
-
-# Clean it: remove whitespace/newlines if any
-hex_clean="${MSG//$'\n'/}"
-hex_clean="${hex_clean//$'\r'/}"
-hex_clean="${hex_clean//[[:space:]]/}"
-
-# Check even length
-if (( ${#hex_clean} % 2 != 0 )); then
-  echo "Error: cleaned hex string has odd length (${#hex_clean})" >&2
-  exit 1
-fi
-
-expected_bytes=$(( ${#hex_clean} / 2 ))
+CIRCUIT_MEMBER_DIR="${WORKSPACE_ROOT_PATH}/hash/${PACKAGE}"
+TOML_PATH="${CIRCUIT_MEMBER_DIR}/Prover_${INPUT_SIZE}.toml"
 
-# We pipe hex → binary → decimal values without capturing raw binary
-# Use xxd if available
-if command -v xxd >/dev/null 2>&1; then
-  # echo hex → xxd reverse to binary → od to decimal bytes
-  mapfile -t byte_vals < <(
-    echo -n "$hex_clean" \
-      | xxd -r -p \
-      | od -An -vt u1 \
-      | tr -s ' ' '\n' \
-      | sed '/^\s*$/d'
-  )
+if [[ -n "${NEGATIVE_CASE:-}" ]]; then
+  "$UTILS_BIN" negative --target sha256 --case "$NEGATIVE_CASE" -n "$INPUT_SIZE" --format toml --fields input,input_len,digest > "$TOML_PATH"
+  echo "$NEGATIVE_CASE" > "${NEGATIVE_CASE_OUT:?NEGATIVE_CASE_OUT is required}"
 else
-  # fallback: convert hex to \x escapes then printf, then od
-  mapfile -t byte_vals < <(
-    echo -n "$hex_clean" \
-      | sed 's/../\\x&/g' \
-      | xargs printf \
-      | od -An -vt u1 \
-      | tr -s ' ' '\n' \
-      | sed '/^\s*$/d'
-  )
+  "$UTILS_BIN" sha256 -n "$INPUT_SIZE" --format toml --fields input,input_len > "$TOML_PATH"
 fi
 
-parsed_len=${#byte_vals[@]}
-
-# Optionally check lengths match
-if (( parsed_len != expected_bytes )); then
-  echo "Warning: parsed length ($parsed_len) != expected ($expected_bytes)" >&2
-fi
-
-# Build TOML: input array and input_len
-
-# Determine path
-CIRCUIT_MEMBER_DIR="${WORKSPACE_ROOT_PATH}/hash/sha256"
-TOML_PATH="${CIRCUIT_MEMBER_DIR}/Prover_${INPUT_SIZE}.toml"
-
-{
-  # Print array
-  printf "input = ["
-  for i in "${!byte_vals[@]}"; do
-    v=${byte_vals[i]}
-    if (( i == 0 )); then
-      printf "%d" "$v"
-    else
-      printf ", %d" "$v"
-    fi
-  done
-  printf "]\n"
-
-  # Print length
-  printf "input_len = %d\n" "$INPUT_SIZE"
-} > "$TOML_PATH"
-
 ####    Create STATE JSON    ####
-JQ_PROG='{"workspace-root-path":$workspace, "circuit-path":$circuit, "toml-path":$toml, "input-size":$len}'
+JQ_PROG='{"workspace-root-path":$workspace, "circuit-path":$circuit, "toml-path":$toml, "input-size":$len, "package":$package}'
 
 jq -nc \
   --arg workspace "$WORKSPACE_ROOT_PATH" \
   --arg circuit "$CIRCUIT_PATH" \
   --arg toml "$TOML_PATH" \
   --argjson len "$INPUT_SIZE" \
+  --arg package "$PACKAGE" \
   "$JQ_PROG" > "$STATE_JSON"
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+# Witness generation, timed separately from proving. Writes the witness
+# that the prove script reads.
+#
+# Required env vars:
+# - STATE_JSON: path to JSON arguments
+
+: "${STATE_JSON:?STATE_JSON is required}"
+
+SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
+
+WORKSPACE_ROOT_PATH=$(jq -r '."workspace-root-path"' "$STATE_JSON")
+TOML_PATH=$(jq -r '."toml-path"' "$STATE_JSON")
+PACKAGE=$(jq -r '.package' "$STATE_JSON")
+INPUT_SIZE=$(jq -r '."input-size"' "$STATE_JSON")
+
+# Change to workspace root
+cd "$WORKSPACE_ROOT_PATH"
+
+#### Witness generation ####
+WITNESS_FILE="sha256_${INPUT_SIZE}.gz"
+nargo execute --prover-name $TOML_PATH --package "$PACKAGE" $WITNESS_FILE
+
+cd ../..
\ No newline at end of file
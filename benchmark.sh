@@ -2,10 +2,18 @@
 set -euo pipefail
 
 # Generic benchmark orchestrator for non-Rust systems.
-# Usage: benchmark.sh --system-dir <path> [--targets "sha256,poseidon,..."]
+# Usage: benchmark.sh --system-dir <path> [--targets "sha256,poseidon,..."] [--feature <name>]
+#
+# --feature names a variant of the system (e.g. a barretenberg oracle hash). It
+# is exported to the system scripts as FEATURE and becomes part of every result
+# file name, so several variants can be benchmarked in the same directory.
+#
+# BENCH_SOUNDNESS=1 runs the soundness phase of every target before its
+# benchmarks, BENCH_SOUNDNESS=only instead of them (see run_soundness).
 
 SYSTEM_DIR=""
 TARGETS=("sha256" "ecdsa")
+FEATURE=""
 
 while [[ $# -gt 0 ]]; do
   case "$1" in
@@ -13,6 +21,8 @@ while [[ $# -gt 0 ]]; do
       SYSTEM_DIR="$2"; shift 2 ;;
     --targets)
       IFS=',' read -r -a TARGETS <<< "$2"; shift 2 ;;
+    --feature)
+      FEATURE="$2"; shift 2 ;;
     --logging)
       LOGGING_RUN=true; shift ;;
     --quick)
@@ -24,6 +34,12 @@ while [[ $# -gt 0 ]]; do
   esac
 done
 
+case "${BENCH_SOUNDNESS:-}" in
+  ""|0) SOUNDNESS_MODE=off ;;
+  only) SOUNDNESS_MODE=only ;;
+  *) SOUNDNESS_MODE=before ;;
+esac
+
 if [[ -z "${QUICK_RUN:-}" ]]; then
   RUNS=10
 else
@@ -45,10 +61,15 @@ UTILS_BIN="${SCRIPT_DIR}/target/release/utils"
 MEASURE_RAM_SCRIPT="${SCRIPT_DIR}/measure_mem_avg.sh"
 BENCH_PROPS_JSON="${SYSTEM_DIR}/bench_props.json"
 NUM_CONSTRAINTS="${SYSTEM_DIR}/circuit_sizes.json"
+SYSTEM_NAME="$(basename "$(cd "$SYSTEM_DIR" && pwd)")"
+FEAT_SUFFIX="${FEATURE:+_${FEATURE}}"
+export FEATURE
 
-if [[ ! -f "$BENCH_PROPS_JSON" ]]; then
-  echo "bench_props.json not found: $BENCH_PROPS_JSON" >&2
-  exit 1
+# Registered systems publish the properties of the ProvingSystem registry;
+# others must provide bench_props.json (format_hyperfine enforces both).
+PROPS_ARGS=()
+if [[ -f "$BENCH_PROPS_JSON" ]]; then
+  PROPS_ARGS=(--properties "$BENCH_PROPS_JSON")
 fi
 
 step() { printf "\n\033[1;34m==> %s\033[0m\n" "$*"; }
@@ -60,6 +81,55 @@ if [[ ! -x "$UTILS_BIN" ]]; then
   exit 1
 fi
 
+# Soundness phase of $TARGET: runs every negative instance (`utils negative`)
+# at the smallest size through the prepare, witness, prove and verify scripts.
+# A prepare script supports it by preparing NEGATIVE_CASE and writing the case
+# name to NEGATIVE_CASE_OUT; otherwise the case is recorded as skipped. Writes
+# <stem>_soundness.json and fails if an instance was accepted.
+run_soundness() {
+  local size stem state case_out case outcome results="[]" accepted=""
+  size="$($UTILS_BIN sizes get --target "$TARGET" --index 0)"
+  stem="${TARGET}_${size}_${SYSTEM_NAME}${FEAT_SUFFIX}"
+  state="$STATE_DIR/soundness_${stem}.json"
+  case_out="$STATE_DIR/soundness_${stem}.case"
+
+  # Like the verifier benchmark: a prove_for_verify script also writes what verify needs
+  local prove_sh="$PROVE_SH"
+  if [[ -x "$PROVE_FOR_VERIY_SH" ]]; then
+    prove_sh="$PROVE_FOR_VERIY_SH"
+  fi
+
+  step "[$TARGET] Soundness (size ${size})"
+  while read -r case; do
+    rm -f "$case_out"
+    if ! NEGATIVE_CASE="$case" NEGATIVE_CASE_OUT="$case_out" UTILS_BIN="$UTILS_BIN" \
+        INPUT_SIZE="$size" STATE_JSON="$state" bash "$PREPARE_SH" > /dev/null 2>&1; then
+      outcome=prove_refused
+    elif [[ "$(cat "$case_out" 2>/dev/null)" != "$case" ]]; then
+      outcome=skipped
+    elif [[ -x "$WITNESS_SH" ]] && ! STATE_JSON="$state" bash "$WITNESS_SH" > /dev/null 2>&1; then
+      outcome=prove_refused
+    elif ! STATE_JSON="$state" bash "$prove_sh" > /dev/null 2>&1; then
+      outcome=prove_refused
+    elif ! STATE_JSON="$state" bash "$VERIFY_SH" > /dev/null 2>&1; then
+      outcome=verify_rejected
+    else
+      outcome=accepted
+      accepted="$accepted $case"
+    fi
+    echo "soundness $TARGET $SYSTEM_NAME $case: $outcome"
+    results="$(jq -c --arg case "$case" --arg outcome "$outcome" \
+      '. + [{case: $case, outcome: $outcome}]' <<< "$results")"
+  done < <("$UTILS_BIN" negative --target "$TARGET")
+
+  jq . <<< "$results" > "$SYSTEM_DIR/${stem}_soundness.json"
+  ok "Soundness report: $SYSTEM_DIR/${stem}_soundness.json"
+  if [[ -n "$accepted" ]]; then
+    echo "$SYSTEM_NAME accepted invalid $TARGET instances:$accepted" >&2
+    exit 1
+  fi
+}
+
 step "Running benchmarks for system: $SYSTEM_DIR"
 
 STATE_DIR="$SYSTEM_DIR/.bench_state"
@@ -76,58 +146,95 @@ for target in "${TARGETS[@]}"; do
   VERIFY_SH="${SYSTEM_DIR}/${TARGET}_verify.sh"
   MEASURE_SH="${SYSTEM_DIR}/${TARGET}_measure.sh"
   PROVE_FOR_VERIY_SH="${SYSTEM_DIR}/${TARGET}_prove_for_verify.sh"
+  # Optional: witness generation, timed separately and run before every prove.
+  WITNESS_SH="${SYSTEM_DIR}/${TARGET}_witness.sh"
 
   if [[ ! -x "$PREPARE_SH" ]]; then
     warn "Skipping target $TARGET: prepare script not found/executable"
     continue
   fi
 
+  if [[ "$SOUNDNESS_MODE" != off ]]; then
+    run_soundness
+  fi
+  if [[ "$SOUNDNESS_MODE" == only ]]; then
+    continue
+  fi
+
   for (( i=0; i<sizes_len; i++ )); do
     INPUT_SIZE="$($UTILS_BIN sizes get --target "$TARGET" --index "$i")"
+    # Stem shared by all result files of this run: <target>_<size>_<system>[_<feature>]
+    RESULT_STEM="${TARGET}_${INPUT_SIZE}_${SYSTEM_NAME}${FEAT_SUFFIX}"
+
+    PROVER_JSON_FILE="$STATE_DIR/prover_${RESULT_STEM}.json"
+    VERIFIER_JSON_FILE="$STATE_DIR/verifier_${RESULT_STEM}.json"
+    # Scripts may report gate/cycle counts here, e.g. {"cycles": N}.
+    export COUNTS_JSON="$SYSTEM_DIR/${RESULT_STEM}_counts.json"
+    rm -f "$COUNTS_JSON"
 
-    PROVER_JSON_FILE="$STATE_DIR/prover_${TARGET}_${INPUT_SIZE}.json"
-    VERIFIER_JSON_FILE="$STATE_DIR/verifier_${TARGET}_${INPUT_SIZE}.json"
+    PREPARE_CMD="UTILS_BIN=$UTILS_BIN INPUT_SIZE=$INPUT_SIZE STATE_JSON=$PROVER_JSON_FILE bash $PREPARE_SH"
+    VERIFIER_PREPARE_CMD="UTILS_BIN=$UTILS_BIN INPUT_SIZE=$INPUT_SIZE STATE_JSON=$VERIFIER_JSON_FILE bash $PREPARE_SH"
+    if [[ -x "$WITNESS_SH" ]]; then
+      PROVER_PREPARE_CMD="$PREPARE_CMD && STATE_JSON=$PROVER_JSON_FILE bash $WITNESS_SH > /dev/null 2>&1"
+      VERIFIER_PREPARE_CMD="$VERIFIER_PREPARE_CMD && STATE_JSON=$VERIFIER_JSON_FILE bash $WITNESS_SH > /dev/null 2>&1"
+    else
+      PROVER_PREPARE_CMD="$PREPARE_CMD"
+    fi
 
     step "[$TARGET] Size measurement (size ${INPUT_SIZE})"
-    SIZES_JSON="$SYSTEM_DIR/${TARGET}_${INPUT_SIZE}_sizes.json"
+    SIZES_JSON="$SYSTEM_DIR/${RESULT_STEM}_sizes.json"
     SIZES_JSON="$SIZES_JSON" UTILS_BIN="$UTILS_BIN" INPUT_SIZE="$INPUT_SIZE" STATE_JSON="$PROVER_JSON_FILE" bash "$PREPARE_SH"
     SIZES_JSON="$SIZES_JSON" STATE_JSON="$PROVER_JSON_FILE" bash "$MEASURE_SH" || warn "Size measurement failed"
     ok "Sizes report: $SIZES_JSON"
 
-    step "[$TARGET] Prover (size ${INPUT_SIZE}):"
     if [[ -z "${LOGGING_RUN:-}" ]]; then
     SHOW_OUTPUT=""
     else
     SHOW_OUTPUT="--show-output"
     fi
 
+    if [[ -x "$WITNESS_SH" ]]; then
+      step "[$TARGET] Witness generation (size ${INPUT_SIZE}):"
+      hyperfine $SHOW_OUTPUT --runs "$RUNS" \
+        --prepare "$PREPARE_CMD" \
+        "STATE_JSON=$PROVER_JSON_FILE bash $WITNESS_SH" \
+        --export-json "$SYSTEM_DIR/hyperfine_${RESULT_STEM}_witness_metrics.json"
+    fi
+
+    step "[$TARGET] Prover (size ${INPUT_SIZE}):"
     hyperfine $SHOW_OUTPUT --runs "$RUNS" \
-      --prepare "UTILS_BIN=$UTILS_BIN INPUT_SIZE=$INPUT_SIZE STATE_JSON=$PROVER_JSON_FILE bash $PREPARE_SH" \
+      --prepare "$PROVER_PREPARE_CMD" \
       "STATE_JSON=$PROVER_JSON_FILE bash $PROVE_SH" \
-      --export-json "$SYSTEM_DIR/hyperfine_${TARGET}_${INPUT_SIZE}_prover_metrics.json"
+      --export-json "$SYSTEM_DIR/hyperfine_${RESULT_STEM}_prover_metrics.json"
 
     step "[$TARGET] Verifier (size ${INPUT_SIZE}):"
     if [[ -x "$PROVE_FOR_VERIY_SH" ]]; then
       hyperfine --runs "$RUNS" \
-        --prepare "UTILS_BIN=$UTILS_BIN INPUT_SIZE=$INPUT_SIZE STATE_JSON=$VERIFIER_JSON_FILE bash $PREPARE_SH && STATE_JSON=$VERIFIER_JSON_FILE bash $PROVE_FOR_VERIY_SH > /dev/null 2>&1" \
+        --prepare "$VERIFIER_PREPARE_CMD && STATE_JSON=$VERIFIER_JSON_FILE bash $PROVE_FOR_VERIY_SH > /dev/null 2>&1" \
         "STATE_JSON=$VERIFIER_JSON_FILE bash $VERIFY_SH" \
-        --export-json "$SYSTEM_DIR/hyperfine_${TARGET}_${INPUT_SIZE}_verifier_metrics.json"
+        --export-json "$SYSTEM_DIR/hyperfine_${RESULT_STEM}_verifier_metrics.json"
     else
       hyperfine --runs "$RUNS" \
-        --prepare "UTILS_BIN=$UTILS_BIN INPUT_SIZE=$INPUT_SIZE STATE_JSON=$VERIFIER_JSON_FILE bash $PREPARE_SH && STATE_JSON=$VERIFIER_JSON_FILE bash $PROVE_SH > /dev/null 2>&1" \
+        --prepare "$VERIFIER_PREPARE_CMD && STATE_JSON=$VERIFIER_JSON_FILE bash $PROVE_SH > /dev/null 2>&1" \
         "STATE_JSON=$VERIFIER_JSON_FILE bash $VERIFY_SH" \
-        --export-json "$SYSTEM_DIR/hyperfine_${TARGET}_${INPUT_SIZE}_verifier_metrics.json"
+        --export-json "$SYSTEM_DIR/hyperfine_${RESULT_STEM}_verifier_metrics.json"
     fi
 
     if [[ -z "${NO_RAM:-}" ]]; then
       step "[$TARGET] RAM measurement (size ${INPUT_SIZE})"
-      MEM_JSON="$SYSTEM_DIR/${TARGET}_${INPUT_SIZE}_mem_report.json"
+      MEM_JSON="$SYSTEM_DIR/${RESULT_STEM}_mem_report.json"
+      bash -c "$PROVER_PREPARE_CMD"
       bash "$MEASURE_RAM_SCRIPT" -o "$MEM_JSON" -- bash -lc "STATE_JSON=\"$PROVER_JSON_FILE\" bash \"$PROVE_SH\"" || warn "Memory measurement failed"
       ok "Memory report: $MEM_JSON"
     fi
   done
 done
 
+if [[ "$SOUNDNESS_MODE" == only ]]; then
+  ok "Soundness phase complete"
+  exit 0
+fi
+
 step "Benchmark complete"
 step "Post-processing hyperfine outputs into Metrics JSONs"
 
@@ -144,7 +251,7 @@ fi
 
 if [[ -x "$FORMATTER_BIN" ]]; then
   step "Formatting hyperfine outputs into Metrics JSON"
-  "$FORMATTER_BIN" --system-dir "$SYSTEM_DIR" --properties "$BENCH_PROPS_JSON" --num-constraints-file "$NUM_CONSTRAINTS" || warn "format_hyperfine failed"
+  "$FORMATTER_BIN" --system-dir "$SYSTEM_DIR" --feature "$FEATURE" "${PROPS_ARGS[@]}" --num-constraints-file "$NUM_CONSTRAINTS" || warn "format_hyperfine failed"
 else
   warn "format_hyperfine binary not found; skipping formatting"
 fi
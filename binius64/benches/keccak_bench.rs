@@ -2,38 +2,31 @@ use binius_prover::hash::parallel_compression::ParallelCompressionAdaptor;
 use binius_utils::serialization::SerializeBytes;
 use binius_verifier::hash::{StdCompression, StdDigest};
 use binius64::{
-    BINIUS64_BENCH_PROPERTIES,
+    Prepared,
     circuits::{KeccakCircuit, keccak::KeccakParams},
     prepare,
+    utils::HashInstance,
 };
 use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 utils::define_benchmark_harness!(
     BenchTarget::Keccak,
     ProvingSystem::Binius64,
     None,
     "keccak_mem_binius64",
-    BINIUS64_BENCH_PROPERTIES,
-    |input_size| {
-        prepare::<KeccakCircuit>(
-            input_size,
-            KeccakParams {
-                max_len_bytes: Some(input_size),
-            },
-        )
-        .expect("Failed to prepare keccak circuit for prove/verify")
-    },
+    |input_size| { prepare_keccak(input_size, HashInstance::keccak(input_size)) },
     |(_, _, cs, _, _, _)| { cs.n_and_constraints() + cs.n_mul_constraints() },
-    |(_verifier, prover, _cs, keccak_circuit, compiled_circuit, input_size)| {
+    |(_verifier, prover, _cs, keccak_circuit, compiled_circuit, instance)| {
         binius64::prove::<
             StdDigest,
             StdCompression,
             ParallelCompressionAdaptor<StdCompression>,
             KeccakCircuit,
-        >(prover, compiled_circuit, keccak_circuit, *input_size)
+        >(prover, compiled_circuit, keccak_circuit, instance.clone())
         .expect("Failed to prove keccak circuit")
     },
-    |(verifier, _prover, _cs, _keccak_circuit, _compiled_circuit, _input_size),
+    |(verifier, _prover, _cs, _keccak_circuit, _compiled_circuit, _instance),
      (proof, pub_witness)| {
         binius64::verify::<StdDigest, StdCompression, ParallelCompressionAdaptor<StdCompression>>(
             verifier,
@@ -42,11 +35,24 @@ utils::define_benchmark_harness!(
         )
         .expect("Failed to verify keccak circuit")
     },
-    |(_verifier, _prover, cs, _keccak_circuit, _compiled_circuit, _input_size)| {
+    |(_verifier, _prover, cs, _keccak_circuit, _compiled_circuit, _instance)| {
         let mut buf: Vec<u8> = Vec::new();
         cs.serialize(&mut buf)
             .expect("Failed to serialize constraint system into byte array");
         buf.len()
     },
-    |(proof, _pub_witness)| proof.len()
+    |(proof, _pub_witness)| proof.len();
+    soundness = |input_size, input: &GeneratedInput| {
+        Some(prepare_keccak(input_size, HashInstance::from_generated(input)?))
+    }
 );
+
+fn prepare_keccak(input_size: usize, instance: HashInstance) -> Prepared<KeccakCircuit> {
+    prepare::<KeccakCircuit>(
+        instance,
+        KeccakParams {
+            max_len_bytes: Some(input_size),
+        },
+    )
+    .expect("Failed to prepare keccak circuit for prove/verify")
+}
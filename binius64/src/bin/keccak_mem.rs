@@ -4,6 +4,7 @@ use binius_verifier::hash::{StdCompression, StdDigest};
 use binius64::{
     circuits::{KeccakCircuit, keccak::KeccakParams},
     prepare, prove,
+    utils::HashInstance,
 };
 use clap::Parser;
 
@@ -21,9 +22,9 @@ fn main() {
 }
 
 fn keccak_mem(input_size: usize) -> Result<()> {
-    let (_verifier, prover, _cs, keccak_circuit, compiled_circuit, input_size) =
+    let (_verifier, prover, _cs, keccak_circuit, compiled_circuit, instance) =
         prepare::<KeccakCircuit>(
-            input_size,
+            HashInstance::keccak(input_size),
             KeccakParams {
                 max_len_bytes: Some(input_size),
             },
@@ -33,6 +34,6 @@ fn keccak_mem(input_size: usize) -> Result<()> {
         StdCompression,
         ParallelCompressionAdaptor<StdCompression>,
         KeccakCircuit,
-    >(&prover, &compiled_circuit, &keccak_circuit, input_size)?;
+    >(&prover, &compiled_circuit, &keccak_circuit, instance)?;
     Ok(())
 }
@@ -2,7 +2,7 @@ use anyhow::Result;
 use binius_prover::hash::parallel_compression::ParallelCompressionAdaptor;
 use binius_verifier::hash::{StdCompression, StdDigest};
 use binius64::circuits::sha256::Sha256Params;
-use binius64::{circuits::Sha256Circuit, prepare, prove};
+use binius64::{circuits::Sha256Circuit, prepare, prove, utils::HashInstance};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
@@ -19,9 +19,9 @@ fn main() {
 }
 
 fn sha256_mem(input_size: usize) -> Result<()> {
-    let (_verifier, prover, _cs, sha256_circuit, compiled_circuit, input_size) =
+    let (_verifier, prover, _cs, sha256_circuit, compiled_circuit, instance) =
         prepare::<Sha256Circuit>(
-            input_size,
+            HashInstance::sha256(input_size),
             Sha256Params {
                 max_len_bytes: Some(input_size),
                 exact_len: true,
@@ -32,6 +32,6 @@ fn sha256_mem(input_size: usize) -> Result<()> {
         StdCompression,
         ParallelCompressionAdaptor<StdCompression>,
         Sha256Circuit,
-    >(&prover, &compiled_circuit, &sha256_circuit, input_size)?;
+    >(&prover, &compiled_circuit, &sha256_circuit, instance)?;
     Ok(())
 }
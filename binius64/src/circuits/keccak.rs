@@ -6,9 +6,9 @@ use binius_frontend::{CircuitBuilder, Wire, WitnessFiller};
 use clap::Args;
 
 use crate::utils::{
-    CircuitTrait, DEFAULT_HASH_MESSAGE_BYTES, determine_hash_max_bytes_from_args, zero_pad_message,
+    CircuitTrait, DEFAULT_HASH_MESSAGE_BYTES, HashInstance, determine_hash_max_bytes_from_args,
+    zero_pad_message,
 };
-use utils::generate_keccak_input;
 
 pub type KeccakParams = <KeccakCircuit as CircuitTrait>::Params;
 pub type KeccakInstance = <KeccakCircuit as CircuitTrait>::Instance;
@@ -28,7 +28,7 @@ pub struct Params {
 
 impl CircuitTrait for KeccakCircuit {
     type Params = Params;
-    type Instance = usize;
+    type Instance = HashInstance;
 
     fn build(params: Params, builder: &mut CircuitBuilder) -> Result<Self> {
         let max_len_bytes = determine_hash_max_bytes_from_args(params.max_len_bytes)?;
@@ -47,23 +47,14 @@ impl CircuitTrait for KeccakCircuit {
         })
     }
 
-    fn populate_witness(&self, message_len_bytes: usize, w: &mut WitnessFiller) -> Result<()> {
-        // Step 1: Generate a deterministic message and its Keccak-256 digest using shared utils
-        let (message_bytes, digest_bytes) = generate_keccak_input(message_len_bytes);
+    fn populate_witness(&self, instance: HashInstance, w: &mut WitnessFiller) -> Result<()> {
+        // Step 1: Zero-pad to maximum length
+        let padded_message = zero_pad_message(instance.message, self.max_len_bytes)?;
 
-        // Step 2: Zero-pad to maximum length
-        let padded_message = zero_pad_message(message_bytes, self.max_len_bytes)?;
-
-        // Step 3: Convert digest bytes to fixed-size array
-        let digest: [u8; 32] = digest_bytes
-            .as_slice()
-            .try_into()
-            .expect("Keccak-256 digest must be 32 bytes");
-
-        // Step 4: Populate witness values
-        self.keccak_hash.populate_len_bytes(w, padded_message.len());
+        // Step 2: Populate witness values
+        self.keccak_hash.populate_len_bytes(w, instance.len_bytes);
         self.keccak_hash.populate_message(w, &padded_message);
-        self.keccak_hash.populate_digest(w, digest);
+        self.keccak_hash.populate_digest(w, instance.digest);
 
         Ok(())
     }
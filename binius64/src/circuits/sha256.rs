@@ -10,9 +10,9 @@ use clap::Args;
 use std::array;
 
 use crate::utils::{
-    CircuitTrait, DEFAULT_HASH_MESSAGE_BYTES, determine_hash_max_bytes_from_args, zero_pad_message,
+    CircuitTrait, DEFAULT_HASH_MESSAGE_BYTES, HashInstance, determine_hash_max_bytes_from_args,
+    zero_pad_message,
 };
-use utils::generate_sha256_input;
 
 pub type Sha256Params = <Sha256Circuit as CircuitTrait>::Params;
 pub type Sha256Instance = <Sha256Circuit as CircuitTrait>::Instance;
@@ -23,7 +23,7 @@ pub struct Sha256Circuit {
 
 impl CircuitTrait for Sha256Circuit {
     type Params = Params;
-    type Instance = usize;
+    type Instance = HashInstance;
 
     fn build(params: Params, builder: &mut CircuitBuilder) -> Result<Self> {
         let max_len_bytes = determine_hash_max_bytes_from_args(params.max_len_bytes)?;
@@ -38,24 +38,15 @@ impl CircuitTrait for Sha256Circuit {
         Ok(Self { sha256_gadget })
     }
 
-    fn populate_witness(&self, message_len_bytes: usize, w: &mut WitnessFiller) -> Result<()> {
-        // Step 1: Generate a deterministic message and its SHA-256 digest using shared utils
-        let (message_bytes, digest_bytes) = generate_sha256_input(message_len_bytes);
+    fn populate_witness(&self, instance: HashInstance, w: &mut WitnessFiller) -> Result<()> {
+        // Step 1: Zero-pad to maximum length
+        let padded_message =
+            zero_pad_message(instance.message, self.sha256_gadget.max_len_bytes())?;
 
-        // Step 2: Zero-pad to maximum length
-        let padded_message = zero_pad_message(message_bytes, self.sha256_gadget.max_len_bytes())?;
-
-        // Step 3: Convert digest bytes to fixed-size array
-        let digest: [u8; 32] = digest_bytes
-            .as_slice()
-            .try_into()
-            .expect("SHA-256 digest must be 32 bytes");
-
-        // Step 4: Populate witness values
-        self.sha256_gadget
-            .populate_len_bytes(w, padded_message.len());
+        // Step 2: Populate witness values
+        self.sha256_gadget.populate_len_bytes(w, instance.len_bytes);
         self.sha256_gadget.populate_message(w, &padded_message);
-        self.sha256_gadget.populate_digest(w, digest);
+        self.sha256_gadget.populate_digest(w, instance.digest);
 
         Ok(())
     }
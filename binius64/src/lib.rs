@@ -1,6 +1,3 @@
-use std::borrow::Cow;
-
-use ::utils::harness::{AuditStatus, BenchProperties};
 use anyhow::Result;
 use binius_core::{Word, constraint_system::ConstraintSystem};
 use binius_frontend::{Circuit, CircuitBuilder};
@@ -24,20 +21,6 @@ use crate::utils::{CircuitTrait, StdProver, StdVerifier};
 pub mod circuits;
 pub mod utils;
 
-pub const BINIUS64_BENCH_PROPERTIES: BenchProperties = BenchProperties {
-    proving_system: Cow::Borrowed("Binius64"),
-    field_curve: Cow::Borrowed("GHASH binary field"), // https://www.binius.xyz/basics/binius64-vs-v0
-    iop: Cow::Borrowed("Binius64"),
-    pcs: Some(Cow::Borrowed("Binius64")),
-    arithm: Cow::Borrowed("Binius64"),
-    is_zk: false,      // https://www.irreducible.com/posts/announcing-binius64
-    security_bits: 96, // https://github.com/IrreducibleOSS/binius64/blob/main/verifier/verifier/src/verify.rs#L40
-    is_pq: true,       // hash-based PCS
-    is_maintained: true,
-    is_audited: AuditStatus::NotAudited,
-    isa: None,
-};
-
 /// Setup the prover and verifier and use SHA256 for Merkle tree compression.
 /// Providing the `key_collection` skips expensive key collection building.
 fn setup(
@@ -56,11 +39,22 @@ fn setup(
     Ok((verifier, prover))
 }
 
+/// The verifier, prover, constraint system, circuit and instance `prepare`
+/// returns.
+pub type Prepared<CT> = (
+    StdVerifier,
+    StdProver,
+    ConstraintSystem,
+    CT,
+    Circuit,
+    <CT as CircuitTrait>::Instance,
+);
+
 // Use the default configs/params
 pub fn prepare<CT: CircuitTrait>(
-    input_size: usize,
+    instance: CT::Instance,
     params: CT::Params,
-) -> Result<(StdVerifier, StdProver, ConstraintSystem, CT, Circuit, usize)> {
+) -> Result<Prepared<CT>> {
     // Extract common arguments
     let log_inv_rate = 1;
 
@@ -75,7 +69,7 @@ pub fn prepare<CT: CircuitTrait>(
 
     // Using SHA256 compression for Merkle tree
     let (verifier, prover) = setup(cs.clone(), log_inv_rate as usize, None)?;
-    Ok((verifier, prover, cs, circuit, compiled_circuit, input_size))
+    Ok((verifier, prover, cs, circuit, compiled_circuit, instance))
 }
 
 pub fn prove<D, C, PC, CT>(
@@ -10,6 +10,7 @@ use binius_verifier::{
     hash::{StdCompression, StdDigest},
 };
 
+use ::utils::inputs::GeneratedInput;
 use clap::Args;
 
 // Reference: https://github.com/IrreducibleOSS/binius64/blob/main/prover/examples/src/circuits/sha256.rs
@@ -38,6 +39,36 @@ pub struct Instance {
     pub message_string: Option<String>,
 }
 
+/// Message, length and expected digest a hash circuit is populated with.
+#[derive(Debug, Clone)]
+pub struct HashInstance {
+    pub message: Vec<u8>,
+    pub len_bytes: usize,
+    pub digest: [u8; 32],
+}
+
+impl HashInstance {
+    /// The SHA-256 benchmark input of `size` bytes.
+    pub fn sha256(size: usize) -> Self {
+        Self::from_generated(&GeneratedInput::sha256(size)).expect("sha256 input fields")
+    }
+
+    /// The Keccak-256 benchmark input of `size` bytes.
+    pub fn keccak(size: usize) -> Self {
+        Self::from_generated(&GeneratedInput::keccak(size)).expect("keccak input fields")
+    }
+
+    /// The `input`, `input_len` and `digest` fields of a generated input, valid
+    /// or not; `None` if it has no such fields.
+    pub fn from_generated(input: &GeneratedInput) -> Option<Self> {
+        Some(HashInstance {
+            message: input.bytes("input")?.to_vec(),
+            len_bytes: input.int("input_len")?,
+            digest: input.bytes("digest")?.try_into().ok()?,
+        })
+    }
+}
+
 // Reference: https://github.com/IrreducibleOSS/binius64/blob/main/prover/examples/src/lib.rs
 pub type StdVerifier = Verifier<StdDigest, StdCompression>;
 
@@ -2,26 +2,13 @@ use cairo_m::{compile_program, prepare, prove, verify};
 use cairo_m_common::{InputValue, Program};
 use cairo_m_prover::{adapter::import_from_runner_output, public_data::PublicData};
 use cairo_m_runner::run_cairo_program;
-use utils::harness::{AuditStatus, BenchProperties, ProvingSystem};
+use utils::harness::ProvingSystem;
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::CairoM,
     None,
     "sha256_mem_cairo_m",
-    BenchProperties::new(
-        "Circle STARK", // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#welcome-to-cairo-m
-        "M31", // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#welcome-to-cairo-m
-        "Circle FRI", // https://eprint.iacr.org/2024/278.pdf
-        Some("Circle FRI"), // https://eprint.iacr.org/2024/278.pdf
-        "AIR",
-        false,
-        96, // https://github.com/kkrt-labs/cairo-m/blob/main/crates/prover/src/prover_config.rs#L13-L20
-        true, // hash-based PCS
-        true, // https://github.com/kkrt-labs
-        AuditStatus::NotAudited, // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#about
-        Some("Cairo ISA"), // https://github.com/kkrt-labs/cairo-m/blob/main/docs/design.md
-    ),
     { compile_program() },
     |input_size, program: &Program| { prepare(input_size, program) },
     |_, _| 0,
@@ -1,28 +1,16 @@
 use std::{fs::File, io::BufReader};
 
 use ark_bn254::Bn254;
-use circom::prepare;
+use circom::{prepare, prepare_input};
 use circom_prover::prover::{CircomProof, ark_circom};
-use utils::harness::{AuditStatus, ProvingSystem};
+use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::Circom,
     None,
     "sha256_mem_circom",
-    utils::harness::BenchProperties::new(
-        "Groth16",
-        "Bn254",
-        "Groth16",
-        None,
-        "R1CS",
-        true,
-        128, // Bn254 curve
-        false,
-        true,
-        AuditStatus::PartiallyAudited, // e.g., https://veridise.com/wp-content/uploads/2023/02/VAR-circom-bigint.pdf
-        None,
-    ),
     |input_size| { prepare(input_size) },
     |(_witness_fn, _input_str, zkey_path)| {
         let mut buffer = BufReader::new(File::open(zkey_path).expect("Unable to open zkey"));
@@ -46,7 +34,8 @@ utils::define_benchmark_harness!(
         serde_json::to_vec(proof)
             .expect("Failed to serialize proof")
             .len()
-    }
+    };
+    soundness = |size, input: &GeneratedInput| prepare_input(size, input)
 );
 
 fn sum_file_sizes_in_the_dir(file_path: &str) -> std::io::Result<usize> {
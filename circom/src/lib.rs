@@ -3,8 +3,7 @@ use circom_prover::{
     prover::{CircomProof, ProofLib},
     witness::WitnessFn,
 };
-use std::collections::HashMap;
-use utils::generate_sha256_input;
+use utils::inputs::{GeneratedInput, InputFormat};
 
 // Prepare witness generator
 witnesscalc_adapter::witness!(sha256_128);
@@ -14,6 +13,15 @@ witnesscalc_adapter::witness!(sha256_1024);
 witnesscalc_adapter::witness!(sha256_2048);
 
 pub fn prepare(input_size: usize) -> (WitnessFn, String, String) {
+    prepare_input(input_size, &GeneratedInput::sha256(input_size)).expect("sha256 input")
+}
+
+/// `prepare` for a given input, e.g. a negative instance. `None` if its
+/// `input_len` is not `input_size`: the circuits hash a fixed length message.
+pub fn prepare_input(
+    input_size: usize,
+    input: &GeneratedInput,
+) -> Option<(WitnessFn, String, String)> {
     // prepare witness_fn
     let witness_fn = match input_size {
         128 => WitnessFn::WitnessCalc(sha256_128_witness),
@@ -25,24 +33,12 @@ pub fn prepare(input_size: usize) -> (WitnessFn, String, String) {
     };
 
     // Prepare inputs
-    let (input, digest) = generate_sha256_input(input_size);
-    let inputs = HashMap::from([
-        (
-            "in".to_string(),
-            input
-                .into_iter()
-                .map(|n| n.to_string())
-                .collect::<Vec<String>>(),
-        ),
-        (
-            "hash".to_string(),
-            digest
-                .into_iter()
-                .map(|n| n.to_string())
-                .collect::<Vec<String>>(),
-        ),
-    ]);
-    let input_str = serde_json::to_string(&inputs).unwrap();
+    input.fixed_length_hash()?;
+    let input_str = input
+        .select(&["input:in", "digest:hash"])
+        .expect("sha256 input fields")
+        .render(InputFormat::Circom);
+    let input_str = String::from_utf8(input_str).unwrap();
 
     // Prepare zkey path
     let current_dir = std::env::current_dir().expect("Failed to get current directory");
@@ -51,7 +47,7 @@ pub fn prepare(input_size: usize) -> (WitnessFn, String, String) {
         current_dir.as_path().to_str().unwrap()
     );
 
-    (witness_fn, input_str, zkey_path)
+    Some((witness_fn, input_str, zkey_path))
 }
 
 pub fn prove(witness_fn: WitnessFn, input_str: String, zkey_path: String) -> CircomProof {
@@ -0,0 +1,149 @@
+//! Benchmark workloads shared by the zkVM guests.
+//!
+//! Each zkVM crate has a tiny `guest/<bench>` entry shim that reads the input
+//! bytes with its zkVM's I/O, calls one of the functions below and commits
+//! the result. Keeping the workload here guarantees every zkVM hashes and
+//! verifies exactly the same thing and decodes the same input encoding, the
+//! one written by `utils::zkvm`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// SHA-256 digest of `input`.
+#[cfg(feature = "sha256")]
+pub fn sha256(input: &[u8]) -> [u8; 32] {
+    #[cfg(feature = "openvm")]
+    {
+        openvm_sha2::sha256(input)
+    }
+    #[cfg(not(feature = "openvm"))]
+    {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(input).into()
+    }
+}
+
+/// Keccak-256 digest of `input`.
+#[cfg(feature = "keccak")]
+pub fn keccak256(input: &[u8]) -> [u8; 32] {
+    use sha3::{Digest, Keccak256};
+    Keccak256::digest(input).into()
+}
+
+/// Verifies a secp256k1 ECDSA signature over a prehashed message.
+///
+/// `input` is the bincode encoded `(encoded_verifying_key, digest, signature)`
+/// written by `utils::zkvm::build_ecdsa_input`. Panics if the input is
+/// malformed or the signature does not verify; otherwise returns the public
+/// values, the bincode encoded `(encoded_verifying_key, digest)`.
+#[cfg(feature = "ecdsa")]
+pub fn verify_ecdsa(input: &[u8]) -> Vec<u8> {
+    use k256::ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+
+    let [encoded_verifying_key, digest, signature] =
+        decode_byte_vecs(input).expect("Failed to deserialize input");
+
+    let verifying_key =
+        VerifyingKey::from_sec1_bytes(encoded_verifying_key).expect("Invalid verifying key");
+    let signature = Signature::from_slice(signature).expect("Invalid signature");
+
+    verifying_key
+        .verify_prehash(digest, &signature)
+        .expect("ECDSA signature verification failed");
+
+    encode_byte_vecs(&[encoded_verifying_key, digest])
+}
+
+/// Decodes a tuple of `N` byte vectors encoded with `bincode::options()`
+/// (varint lengths), borrowing from `input`.
+pub fn decode_byte_vecs<const N: usize>(mut input: &[u8]) -> Option<[&[u8]; N]> {
+    let mut fields = [&[][..]; N];
+    for field in &mut fields {
+        let len = usize::try_from(read_varint(&mut input)?).ok()?;
+        if input.len() < len {
+            return None;
+        }
+        let (bytes, rest) = input.split_at(len);
+        *field = bytes;
+        input = rest;
+    }
+    input.is_empty().then_some(fields)
+}
+
+/// Encodes a tuple of byte vectors the way `bincode::options()` does.
+pub fn encode_byte_vecs(fields: &[&[u8]]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for field in fields {
+        write_varint(&mut out, field.len() as u64);
+        out.extend_from_slice(field);
+    }
+    out
+}
+
+// bincode varint: values below 251 are one byte, larger ones are a marker
+// byte followed by a little-endian u16, u32 or u64.
+const U16_MARKER: u8 = 251;
+const U32_MARKER: u8 = 252;
+const U64_MARKER: u8 = 253;
+
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let (&marker, rest) = input.split_first()?;
+    let width = match marker {
+        U16_MARKER => 2,
+        U32_MARKER => 4,
+        U64_MARKER => 8,
+        byte if byte < U16_MARKER => {
+            *input = rest;
+            return Some(byte as u64);
+        }
+        _ => return None,
+    };
+    if rest.len() < width {
+        return None;
+    }
+    let (bytes, rest) = rest.split_at(width);
+    let mut le = [0u8; 8];
+    le[..width].copy_from_slice(bytes);
+    *input = rest;
+    Some(u64::from_le_bytes(le))
+}
+
+fn write_varint(out: &mut Vec<u8>, value: u64) {
+    if value < U16_MARKER as u64 {
+        out.push(value as u8);
+    } else if let Ok(value) = u16::try_from(value) {
+        out.push(U16_MARKER);
+        out.extend_from_slice(&value.to_le_bytes());
+    } else if let Ok(value) = u32::try_from(value) {
+        out.push(U32_MARKER);
+        out.extend_from_slice(&value.to_le_bytes());
+    } else {
+        out.push(U64_MARKER);
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_byte_vecs_round_trip() {
+        let short = vec![1u8; 65];
+        let long = vec![2u8; 300];
+        let encoded = encode_byte_vecs(&[&short, &[], &long]);
+
+        // 65 fits in one byte; 300 needs the u16 marker.
+        assert_eq!(encoded[0], 65);
+        assert_eq!(&encoded[67..70], &[U16_MARKER, 0x2c, 0x01]);
+
+        let [a, b, c] = decode_byte_vecs(&encoded).unwrap();
+        assert_eq!((a, b, c), (&short[..], &[][..], &long[..]));
+
+        assert!(decode_byte_vecs::<3>(&encoded[..encoded.len() - 1]).is_none());
+        assert!(decode_byte_vecs::<2>(&encoded).is_none());
+    }
+}
@@ -1,37 +1,26 @@
 use ere_jolt::compiler::RustRv32imaCustomized;
 use jolt::{
-    execution_cycles, prepare_sha256, preprocessing_size, proof_size, prove_sha256, verify_sha256,
-};
-use std::collections::HashMap;
-use utils::{
-    harness::ProvingSystem,
-    zkvm::{SHA256_BENCH, helpers::load_or_compile_program},
+    execution_cycles, prepare_sha256, prepare_sha256_input, preprocessing_size, proof_size,
+    prove_sha256, proving_time, verify_sha256,
 };
+use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
+use utils::zkvm::helpers::load_or_compile_program;
+use utils::zkvm::{SHA256_BENCH, bench_proof_feature};
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::Jolt,
-    None,
+    bench_proof_feature(),
     "sha256_mem_jolt",
-    utils::harness::BenchProperties::default(),
-    {
-        let mut programs = HashMap::new();
-        for input_size in utils::metadata::selected_sha2_inputs() {
-            programs.insert(
-                input_size,
-                load_or_compile_program(
-                    &RustRv32imaCustomized,
-                    &format!("{}_{}", SHA256_BENCH, input_size),
-                ),
-            );
-        }
-        programs
-    },
+    { load_or_compile_program(&RustRv32imaCustomized, SHA256_BENCH) },
     prepare_sha256,
     |_, _| 0,
     prove_sha256,
     verify_sha256,
     preprocessing_size,
     proof_size,
-    execution_cycles
+    execution_cycles,
+    proving_time;
+    soundness = |_, input: &GeneratedInput, program| prepare_sha256_input(input, program)
 );
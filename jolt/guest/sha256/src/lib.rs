@@ -0,0 +1,6 @@
+#![cfg_attr(feature = "guest", no_std)]
+
+#[jolt::provable]
+fn sha2(input: &[u8]) -> [u8; 32] {
+    guest_lib::sha256(input)
+}
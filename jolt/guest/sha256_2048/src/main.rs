@@ -1,5 +0,0 @@
-#![cfg_attr(feature = "guest", no_std)]
-#![no_main]
-
-#[allow(unused_imports)]
-use jolt_sha256::*;
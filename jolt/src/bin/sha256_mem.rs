@@ -1,8 +1,8 @@
-use std::collections::HashMap;
-
 use clap::Parser;
+use ere_jolt::compiler::RustRv32imaCustomized;
 use jolt::{prepare_sha256, prove_sha256};
-use utils::zkvm::{SHA256_BENCH, helpers::load_compiled_program};
+use utils::zkvm::SHA256_BENCH;
+use utils::zkvm::helpers::load_compiled_program;
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -13,12 +13,8 @@ struct Args {
 
 fn main() {
     let args = Args::parse();
-    let mut programs = HashMap::new();
-    programs.insert(
-        args.input_size,
-        load_compiled_program(&format!("{}_{}", SHA256_BENCH, args.input_size)),
-    );
+    let program = load_compiled_program::<RustRv32imaCustomized>(SHA256_BENCH);
 
-    let prepared = prepare_sha256(args.input_size, &programs);
+    let prepared = prepare_sha256(args.input_size, &program);
     prove_sha256(&prepared, &());
 }
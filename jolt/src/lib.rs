@@ -1,25 +1,88 @@
-use std::collections::HashMap;
-
 use ere_jolt::{EreJolt, compiler::RustRv32imaCustomized};
-use ere_zkvm_interface::ProverResourceType;
-use utils::zkvm::{CompiledProgram, PreparedSha256, build_input};
+use ere_zkvm_interface::{Input, ProverResourceType};
+use utils::inputs::GeneratedInput;
+use utils::zkvm::{
+    CompiledProgram, PreparedProgram, SHA256_BENCH, ZkTarget, build_input, hash_data,
+};
 
 pub use utils::zkvm::{
-    execution_cycles, preprocessing_size, proof_size, prove_sha256, verify_sha256,
+    execution_cycles, preprocessing_size, proof_size, prove as prove_sha256, proving_time,
+    verify as verify_sha256,
 };
 
+/// The jolt SHA-256 guest reads the raw message and returns the digest, which
+/// jolt commits as the postcard encoding of the `[u8; 32]` (its 32 bytes),
+/// followed by the zero padding of the output region.
+pub struct JoltSha256;
+
+impl ZkTarget for JoltSha256 {
+    const NAME: &'static str = SHA256_BENCH;
+    /// (message, digest)
+    type Data = (Vec<u8>, Vec<u8>);
+
+    fn generate(input_size: usize) -> Self::Data {
+        utils::generate_sha256_input(input_size)
+    }
+
+    fn encode_input((message, _): &Self::Data) -> Input {
+        build_input(message.clone())
+    }
+
+    fn check_public_values((_, digest): &Self::Data, public_values: &[u8]) -> Result<(), String> {
+        match decode_public_values(public_values) {
+            Some(committed) if committed == digest.as_slice() => Ok(()),
+            Some(_) => Err("digest mismatch".to_string()),
+            None => Err("malformed jolt output".to_string()),
+        }
+    }
+
+    fn from_generated(input: &GeneratedInput) -> Option<Self::Data> {
+        hash_data(input)
+    }
+}
+
+pub type PreparedSha256 = PreparedProgram<EreJolt, JoltSha256>;
+
+fn vm(program: &CompiledProgram<RustRv32imaCustomized>) -> EreJolt {
+    EreJolt::new(program.program.clone(), ProverResourceType::Cpu)
+        .expect("jolt prover build failed")
+}
+
 pub fn prepare_sha256(
     input_size: usize,
-    programs: &HashMap<usize, CompiledProgram<RustRv32imaCustomized>>,
-) -> PreparedSha256<EreJolt> {
-    let program = &programs
-        .get(&input_size)
-        .expect("program not found in cache");
-    let vm = EreJolt::new(program.program.clone(), ProverResourceType::Cpu)
-        .expect("jolt prover build failed");
-
-    let (message_bytes, _digest) = utils::generate_sha256_input(input_size);
-    let input = build_input(message_bytes);
-
-    PreparedSha256::new(vm, input, program.byte_size)
+    program: &CompiledProgram<RustRv32imaCustomized>,
+) -> PreparedSha256 {
+    PreparedSha256::prepare(vm(program), program.byte_size, input_size)
+}
+
+/// `prepare_sha256` for a given input, e.g. a negative instance.
+pub fn prepare_sha256_input(
+    input: &GeneratedInput,
+    program: &CompiledProgram<RustRv32imaCustomized>,
+) -> Option<PreparedSha256> {
+    PreparedSha256::from_generated(vm(program), program.byte_size, input)
+}
+
+fn decode_public_values(raw: &[u8]) -> Option<&[u8]> {
+    let (digest, padding) = raw.split_at_checked(32)?;
+    padding.iter().all(|&b| b == 0).then_some(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jolt_output_decodes_to_the_digest() {
+        let data = utils::generate_sha256_input(128);
+        let mut output = data.1.clone();
+        assert!(JoltSha256::check_public_values(&data, &output).is_ok());
+
+        output.resize(4096, 0);
+        assert!(JoltSha256::check_public_values(&data, &output).is_ok());
+
+        output[0] ^= 1;
+        assert!(JoltSha256::check_public_values(&data, &output).is_err());
+        assert!(JoltSha256::check_public_values(&data, &data.1[..31]).is_err());
+    }
 }
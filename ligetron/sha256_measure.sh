@@ -3,7 +3,7 @@ set -euo pipefail
 
 # Required env vars:
 # - STATE_JSON: path to JSON arguments for proving
-# - SIZES_JSON: output JSON path (defaults to <dir>/${TARGET}_<INPUT_SIZE>_sizes.json when run via benchmark.sh)
+# - SIZES_JSON: output JSON path (defaults to <dir>/${TARGET}_<INPUT_SIZE>_<system>[_<feature>]_sizes.json when run via benchmark.sh)
 
 SCRIPT_DIR="$(cd "$(dirname "$0")" && pwd)"
 OUT_JSON="${SIZES_JSON:-}"
@@ -54,8 +54,8 @@ if [[ -x "$PROVER_BIN" ]]; then
   ')
 
   if [[ -n "$CONSTRAINTS_SUM" ]]; then
-    # Derive input size label from SIZES_JSON filename: sha256_<SIZE>_sizes.json
-    SIZE_LABEL=$(basename "$OUT_JSON" | sed -E 's/^sha256_([^_]+)_sizes\.json$/\1/')
+    # Derive input size label from SIZES_JSON filename: sha256_<SIZE>_<system>[_<feature>]_sizes.json
+    SIZE_LABEL=$(basename "$OUT_JSON" | sed -E 's/^sha256_([0-9]+)_.*sizes\.json$/\1/')
     if [[ -n "$SIZE_LABEL" ]]; then
       SYSTEM_DIR="$(cd "$(dirname "$0")" && pwd)"
       CONSTRAINTS_JSON_PATH="${SYSTEM_DIR}/circuit_sizes.json"
@@ -1,38 +1,60 @@
 use ere_miden::{EreMiden, compiler::MidenAsm};
 use ere_zkvm_interface::{Input, ProverResourceType};
 use std::convert::TryInto;
-use utils::zkvm::{CompiledProgram, PreparedSha256, ProofArtifacts};
+use utils::inputs::GeneratedInput;
+use utils::zkvm::{CompiledProgram, PreparedProgram, SHA256_BENCH, ZkTarget, hash_data};
 
-pub use utils::zkvm::{execution_cycles, preprocessing_size, proof_size, prove_sha256};
+pub use utils::zkvm::{
+    execution_cycles, preprocessing_size, proof_size, prove as prove_sha256, proving_time,
+    verify as verify_sha256,
+};
 
-pub fn prepare_sha256(
-    input_size: usize,
-    program: &CompiledProgram<MidenAsm>,
-) -> PreparedSha256<EreMiden> {
-    let vm = EreMiden::new(program.program.clone(), ProverResourceType::Cpu)
-        .expect("failed to build miden prover instance");
+/// The miden SHA-256 guest reads the message as u32 words in field elements
+/// and commits the digest words the same way.
+pub struct MidenSha256;
+
+impl ZkTarget for MidenSha256 {
+    const NAME: &'static str = SHA256_BENCH;
+    /// (message, digest)
+    type Data = (Vec<u8>, Vec<u8>);
+
+    fn generate(input_size: usize) -> Self::Data {
+        utils::generate_sha256_input(input_size)
+    }
 
-    let (message_bytes, digest) = utils::generate_sha256_input(input_size);
-    let input = build_input(message_bytes);
+    fn encode_input((message, _): &Self::Data) -> Input {
+        build_input(message.clone())
+    }
+
+    fn check_public_values((_, digest): &Self::Data, public_values: &[u8]) -> Result<(), String> {
+        if decode_public_values(public_values) != *digest {
+            return Err("digest mismatch".to_string());
+        }
+        Ok(())
+    }
 
-    PreparedSha256::with_expected_digest(vm, input, program.byte_size, digest)
+    fn from_generated(input: &GeneratedInput) -> Option<Self::Data> {
+        hash_data(input)
+    }
+}
+
+pub type PreparedSha256 = PreparedProgram<EreMiden, MidenSha256>;
+
+fn vm(program: &CompiledProgram<MidenAsm>) -> EreMiden {
+    EreMiden::new(program.program.clone(), ProverResourceType::Cpu)
+        .expect("failed to build miden prover instance")
 }
 
-// Miden has custom verification logic due to special public value decoding
-pub fn verify_sha256(
-    prepared: &PreparedSha256<EreMiden>,
-    proof: &ProofArtifacts,
-    _: &&CompiledProgram<MidenAsm>,
-) {
-    let public_values = prepared.verify(&proof.proof).expect("miden verify failed");
-
-    assert_eq!(public_values, proof.public_values, "public values mismatch");
-
-    let digest_bytes = decode_public_values(&proof.public_values);
-    let expected_digest = prepared
-        .expected_digest()
-        .expect("expected digest not recorded");
-    assert_eq!(digest_bytes, expected_digest, "digest mismatch");
+pub fn prepare_sha256(input_size: usize, program: &CompiledProgram<MidenAsm>) -> PreparedSha256 {
+    PreparedSha256::prepare(vm(program), program.byte_size, input_size)
+}
+
+/// `prepare_sha256` for a given input, e.g. a negative instance.
+pub fn prepare_sha256_input(
+    input: &GeneratedInput,
+    program: &CompiledProgram<MidenAsm>,
+) -> Option<PreparedSha256> {
+    PreparedSha256::from_generated(vm(program), program.byte_size, input)
 }
 
 fn build_input(data: Vec<u8>) -> Input {
@@ -94,10 +116,7 @@ mod tests {
             .execute(prepared.input())
             .expect("guest execution must succeed");
         let digest_bytes = decode_public_values(&public_values);
-        let expected_digest = prepared
-            .expected_digest()
-            .expect("expected digest not recorded");
-        assert_eq!(digest_bytes, expected_digest);
+        assert_eq!(digest_bytes, prepared.data().1);
 
         // Ensure prove/verify plumbing also succeeds
         let proof = prove_sha256(&prepared, &program);
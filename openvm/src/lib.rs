@@ -1,20 +1,28 @@
 use ere_openvm::{EreOpenVM, compiler::RustRv32imaCustomized};
 use ere_zkvm_interface::ProverResourceType;
-use utils::zkvm::{CompiledProgram, PreparedSha256, build_input};
+use utils::inputs::GeneratedInput;
+use utils::zkvm::{CompiledProgram, PreparedSha256};
 
 pub use utils::zkvm::{
-    execution_cycles, preprocessing_size, proof_size, prove_sha256, verify_sha256,
+    execution_cycles, preprocessing_size, proof_size, prove_sha256, proving_time, verify_sha256,
 };
 
+fn vm(program: &CompiledProgram<RustRv32imaCustomized>) -> EreOpenVM {
+    EreOpenVM::new(program.program.clone(), ProverResourceType::Cpu)
+        .expect("failed to build OpenVM prover instance")
+}
+
 pub fn prepare_sha256(
     input_size: usize,
     program: &CompiledProgram<RustRv32imaCustomized>,
 ) -> PreparedSha256<EreOpenVM> {
-    let vm = EreOpenVM::new(program.program.clone(), ProverResourceType::Cpu)
-        .expect("failed to build OpenVM prover instance");
-
-    let (message_bytes, digest) = utils::generate_sha256_input(input_size);
-    let input = build_input(message_bytes);
+    PreparedSha256::prepare(vm(program), program.byte_size, input_size)
+}
 
-    PreparedSha256::with_expected_digest(vm, input, program.byte_size, digest)
+/// `prepare_sha256` for a given input, e.g. a negative instance.
+pub fn prepare_sha256_input(
+    input: &GeneratedInput,
+    program: &CompiledProgram<RustRv32imaCustomized>,
+) -> Option<PreparedSha256<EreOpenVM>> {
+    PreparedSha256::from_generated(vm(program), program.byte_size, input)
 }
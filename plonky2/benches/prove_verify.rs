@@ -1,8 +1,9 @@
-use plonky2_sha256::bench::{prove, sha256_prepare, verify};
+use plonky2_sha256::bench::{prove, sha256_prepare, sha256_prepare_input, verify};
 
 use plonky2::{plonk::config::PoseidonGoldilocksConfig, util::serialization::Write};
 use plonky2_u32::gates::arithmetic_u32::{U32GateSerializer, U32GeneratorSerializer};
-use utils::harness::{AuditStatus, BenchProperties, ProvingSystem};
+use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 const D: usize = 2;
 type C = PoseidonGoldilocksConfig;
@@ -12,19 +13,6 @@ utils::define_benchmark_harness!(
     ProvingSystem::Plonky2,
     None,
     "sha256_no_lookup_mem",
-    BenchProperties::new(
-        "Plonky2",    // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        "Goldilocks", // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        "FRI",        // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        Some("FRI"),  // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        "Plonkish",   // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        true,         // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
-        100,          // https://github.com/0xPolygonZero/plonky2?tab=readme-ov-file#security
-        true,         // hash-based PCS
-        false, // deprecated: https://github.com/0xPolygonZero/plonky2?tab=readme-ov-file#%EF%B8%8F-plonky2-deprecation-notice
-        AuditStatus::Audited, // https://github.com/0xPolygonZero/plonky2/tree/main/audits
-        None,
-    ),
     sha256_prepare,
     |(_, _, n_gates)| *n_gates,
     |(circuit_data, pw, _)| { prove(circuit_data, pw.clone()) },
@@ -51,5 +39,6 @@ utils::define_benchmark_harness!(
         let mut buffer = Vec::new();
         buffer.write_proof(&proof.proof).unwrap();
         buffer.len()
-    }
+    };
+    soundness = |_, input: &GeneratedInput| sha256_prepare_input(input)
 );
@@ -9,6 +9,8 @@ use plonky2::{
     },
 };
 
+use utils::inputs::GeneratedInput;
+
 use crate::circuit::{array_to_bits, make_circuits};
 
 const D: usize = 2;
@@ -27,9 +29,18 @@ pub fn prove(
 }
 
 pub fn sha256_prepare(input_size: usize) -> (CircuitData<F, C, D>, PartialWitness<F>, usize) {
-    let (msg, hash) = utils::generate_sha256_input(input_size);
+    sha256_prepare_input(&GeneratedInput::sha256(input_size)).expect("sha256 input")
+}
+
+/// `sha256_prepare` for a given input, e.g. a negative instance. `None` if
+/// its `input_len` is not the message length: the circuit hashes the whole
+/// message.
+pub fn sha256_prepare_input(
+    input: &GeneratedInput,
+) -> Option<(CircuitData<F, C, D>, PartialWitness<F>, usize)> {
+    let (msg, hash) = input.fixed_length_hash()?;
 
-    let msg_bits = array_to_bits(&msg);
+    let msg_bits = array_to_bits(msg);
     let len = msg.len() * 8;
     println!("block count: {}", (len + 65).div_ceil(512));
     const D: usize = 2;
@@ -43,7 +54,7 @@ pub fn sha256_prepare(input_size: usize) -> (CircuitData<F, C, D>, PartialWitnes
         pw.set_bool_target(targets.message[i], *msg_bit).unwrap();
     }
 
-    let expected_res = array_to_bits(hash.as_slice());
+    let expected_res = array_to_bits(hash);
     for (i, expected_res_bit) in expected_res.iter().enumerate() {
         if *expected_res_bit {
             builder.assert_one(targets.digest[i].target);
@@ -53,5 +64,5 @@ pub fn sha256_prepare(input_size: usize) -> (CircuitData<F, C, D>, PartialWitnes
     }
 
     let n_gates = builder.num_gates();
-    (builder.build::<C>(), pw, n_gates)
+    Some((builder.build::<C>(), pw, n_gates))
 }
@@ -1,28 +1,17 @@
 use gkr_engine::MPIConfig;
 use sha256_expander_benchmark::bench::get_constraints;
 use sha256_expander_benchmark::bench::prepare;
+use sha256_expander_benchmark::bench::prepare_input;
 use sha256_expander_benchmark::bench::prove;
 use sha256_expander_benchmark::bench::verify;
-use utils::harness::{AuditStatus, ProvingSystem};
+use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::Expander,
     None,
     "sha256_mem_expander",
-    utils::harness::BenchProperties::new(
-        "Libra",
-        "M31",         // See ./polyhedra-expander/src/bench.rs
-        "GKR",         // https://eprint.iacr.org/2019/317
-        Some("Orion"), // See ./polyhedra-expander/src/bench.rs
-        "GKR",         // https://eprint.iacr.org/2019/317
-        false,
-        128,  // https://github.com/PolyhedraZK/Expander/blob/main/poly_commit/src/lib.rs#L6
-        true, // Hash-based PCS (https://eprint.iacr.org/2022/1010.pdf)
-        true,
-        AuditStatus::NotAudited,
-        None,
-    ),
     {
         let universe = MPIConfig::init().expect("Failed to initialize MPI");
         let world = universe.world();
@@ -57,5 +46,6 @@ utils::define_benchmark_harness!(
         );
     },
     |(circuit_bytes, _), _| { circuit_bytes.len() },
-    |proof, _shared| proof.bytes.len()
+    |proof, _shared| proof.bytes.len();
+    soundness = |_, input: &GeneratedInput, _| prepare_input(input)
 );
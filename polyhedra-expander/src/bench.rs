@@ -18,6 +18,7 @@ use poly_commit::OrionPCSForGKR;
 use serdes::ExpSerde;
 use std::io::Cursor;
 use transcript::BytesHashTranscript;
+use utils::inputs::GeneratedInput;
 
 // Constants and circuit definition
 const OUTPUT_LEN: usize = 32; // SHA-256 digest length
@@ -146,14 +147,15 @@ pub fn verify(
     ));
 }
 
-// Macro generating a match arm that prepares the circuit and witness for the given input size
+// Macro generating a match arm that prepares the circuit and witness for the given message
+// and expected digest
 macro_rules! prepare_arm {
-    ($Circuit:ident, $LEN:expr) => {{
+    ($Circuit:ident, $LEN:expr, $message_bytes:expr, $output:expr) => {{
         let compile_result =
             compile(&$Circuit::<Variable>::default(), CompileOptions::default()).unwrap();
 
         const LEN: usize = $LEN;
-        let (message_bytes, output) = utils::generate_sha256_input(LEN);
+        let (message_bytes, output): (&[u8], &[u8]) = ($message_bytes, $output);
 
         let mut assignment = $Circuit::<M31>::default();
         for (i, input_byte) in message_bytes.iter().enumerate().take(LEN) {
@@ -191,5 +193,17 @@ fn serialize_outputs(
 /// Prepare the circuit and witness for the given input size
 /// (invokes `match` for all input sizes that was generated by `../build.rs`)
 pub fn prepare(input_len: usize) -> (Vec<u8>, Vec<u8>) {
-    match_sha2_sizes!(input_len, prepare_arm)
+    prepare_input(&GeneratedInput::sha256(input_len)).expect("sha256 input")
+}
+
+/// `prepare` for a given input, e.g. a negative instance. `None` if its
+/// `input_len` is not the message length: the circuits hash a fixed length message.
+pub fn prepare_input(input: &GeneratedInput) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (message_bytes, output) = input.fixed_length_hash()?;
+    Some(match_sha2_sizes!(
+        message_bytes.len(),
+        prepare_arm,
+        message_bytes,
+        output
+    ))
 }
@@ -14,14 +14,14 @@ impl Define<M31SingleConfig> for SHA256Circuit{{LEN}}<Variable> {
 {{CIRCUIT_DECLS}}
 
 macro_rules! match_sha2_sizes {
-    ($input_len:expr, $arm:ident) => { match $input_len {
+    ($input_len:expr, $arm:ident $(, $arg:expr)*) => { match $input_len {
 {{MATCH_ARMS}}
         _ => panic!("unsupported input length: {}", $input_len),
     }};
 }
 
 // BEGIN_MATCH_ARM
-        {{LEN}} => $arm!(SHA256Circuit{{LEN}}, {{LEN}}),
+        {{LEN}} => $arm!(SHA256Circuit{{LEN}}, {{LEN}} $(, $arg)*),
 // END_MATCH_ARM
 
 
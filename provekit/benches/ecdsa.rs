@@ -1,12 +1,12 @@
-use provekit::{PROVEKIT_PROPS, prepare_ecdsa, preprocessing_size, prove, verify};
+use provekit::{prepare_ecdsa, prepare_ecdsa_input, preprocessing_size, prove, verify};
 use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 utils::define_benchmark_harness!(
     BenchTarget::Ecdsa,
     ProvingSystem::Provekit,
     None,
     "ecdsa_mem_provekit",
-    PROVEKIT_PROPS,
     prepare_ecdsa,
     |(proof_scheme, _, _)| { proof_scheme.r1cs.num_constraints() },
     |(proof_scheme, toml_path, _)| { prove(proof_scheme, toml_path) },
@@ -14,5 +14,6 @@ utils::define_benchmark_harness!(
         verify(proof, proof_scheme).unwrap();
     },
     |(_, _, circuit_path)| { preprocessing_size(circuit_path) },
-    |proof| { proof.whir_r1cs_proof.transcript.len() }
+    |proof| { proof.whir_r1cs_proof.transcript.len() };
+    soundness = |_, input: &GeneratedInput| Some(prepare_ecdsa_input(input))
 );
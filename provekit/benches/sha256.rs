@@ -1,12 +1,12 @@
-use provekit::{PROVEKIT_PROPS, prepare_sha256, preprocessing_size, prove, verify};
+use provekit::{prepare_sha256, prepare_sha256_input, preprocessing_size, prove, verify};
 use utils::harness::ProvingSystem;
+use utils::inputs::GeneratedInput;
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::Provekit,
     None,
     "sha256_mem_provekit",
-    PROVEKIT_PROPS,
     prepare_sha256,
     |(proof_scheme, _, _)| { proof_scheme.r1cs.num_constraints() },
     |(proof_scheme, toml_path, _)| { prove(proof_scheme, toml_path) },
@@ -14,5 +14,6 @@ utils::define_benchmark_harness!(
         verify(proof, proof_scheme).unwrap();
     },
     |(_, _, circuit_path)| { preprocessing_size(circuit_path) },
-    |proof| { proof.whir_r1cs_proof.transcript.len() }
+    |proof| { proof.whir_r1cs_proof.transcript.len() };
+    soundness = |size, input: &GeneratedInput| Some(prepare_sha256_input(size, input))
 );
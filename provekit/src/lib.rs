@@ -2,61 +2,66 @@ use provekit_common::{NoirProof, NoirProofScheme};
 use provekit_prover::NoirProofSchemeProver;
 use provekit_r1cs_compiler::NoirProofSchemeBuilder;
 use provekit_verifier::NoirProofSchemeVerifier;
-use std::borrow::Cow;
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-use utils::generate_ecdsa_input;
-use utils::harness::{AuditStatus, BenchProperties};
+use utils::cache::{ArtifactCache, ArtifactKey, tool_version};
+use utils::inputs::{GeneratedInput, InputFormat};
 
 const WORKSPACE_ROOT: &str = "circuits";
 const SHA256_CIRCUIT_SUB_PATH: &str = "hash/sha256-provekit";
 const ECDSA_CIRCUIT_SUB_PATH: &str = "ecdsa";
 
-pub const PROVEKIT_PROPS: BenchProperties = BenchProperties {
-    proving_system: Cow::Borrowed("Spartan+WHIR"), // https://github.com/worldfnd/provekit
-    field_curve: Cow::Borrowed("Bn254"),           // https://github.com/worldfnd/provekit
-    iop: Cow::Borrowed("Spartan"),                 // https://github.com/worldfnd/provekit
-    pcs: Some(Cow::Borrowed("WHIR")),              // https://github.com/worldfnd/provekit
-    arithm: Cow::Borrowed("R1CS"),                 // https://github.com/worldfnd/provekit
-    is_zk: true,                                   // https://github.com/worldfnd/provekit/pull/138
-    security_bits: 128, // https://github.com/worldfnd/provekit/blob/d7deea66c41d56c1d411dd799d0d6066272323e4/provekit/r1cs-compiler/src/whir_r1cs.rs#L43
-    is_pq: true,        // hash-based PCS
-    is_maintained: true, // https://github.com/worldfnd/provekit
-    is_audited: AuditStatus::NotAudited,
-    isa: None,
-};
-
-fn compile_workspace() -> PathBuf {
+const NARGO_COMPILE_ARGS: [&str; 4] = [
+    "compile",
+    "--workspace",
+    "--silence-warnings",
+    "--skip-brillig-constraints-check",
+];
+
+/// Compiles the Noir workspace, or reuses the artifacts cached for the same
+/// sources and nargo version. Returns the workspace root and the directory
+/// holding the compiled `<package>.json` files.
+fn compile_workspace() -> (PathBuf, PathBuf) {
     let current_dir = std::env::current_dir().expect("Failed to get current directory");
     let workspace_root = current_dir.join(WORKSPACE_ROOT);
-    let output = Command::new("nargo")
-        .args([
-            "compile",
-            "--workspace",
-            "--silence-warnings",
-            "--skip-brillig-constraints-check",
-        ])
-        .current_dir(&workspace_root)
-        .output()
-        .expect("Failed to run nargo compile");
-    if !output.status.success() {
-        panic!(
-            "Workspace compilation failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
-    workspace_root
+    let key = ArtifactKey {
+        kind: "noir-circuit".to_string(),
+        name: "provekit".to_string(),
+        sources: vec![workspace_root.clone()],
+        exclude: vec!["Prover.toml".to_string()],
+        toolchain: vec![tool_version("nargo")],
+        options: NARGO_COMPILE_ARGS.iter().map(|a| a.to_string()).collect(),
+    };
+    let artifacts = ArtifactCache::open_default()
+        .get_or_build(&key, |out| {
+            let output = Command::new("nargo")
+                .args(NARGO_COMPILE_ARGS)
+                .current_dir(&workspace_root)
+                .output()?;
+            if !output.status.success() {
+                return Err(io::Error::other(format!(
+                    "Workspace compilation failed: {}",
+                    String::from_utf8_lossy(&output.stderr)
+                )));
+            }
+            for entry in fs::read_dir(workspace_root.join("target"))? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|e| e == "json") {
+                    fs::copy(&path, out.join(path.file_name().unwrap()))?;
+                }
+            }
+            Ok(())
+        })
+        .unwrap_or_else(|e| panic!("{e}"));
+    (workspace_root, artifacts)
 }
 
-pub fn prepare_sha256(input_size: usize) -> (NoirProofScheme, PathBuf, PathBuf) {
-    // 1) Rewrite circuit input length to match input_size before compiling
-    let current_dir = std::env::current_dir().expect("Failed to get current directory");
-    let workspace_root_pre = current_dir.join(WORKSPACE_ROOT);
-    let circuit_source =
-        workspace_root_pre.join("hash/sha256-provekit/sha256_var_input/src/main.nr");
-
-    if let Ok(mut content) = fs::read_to_string(&circuit_source) {
+/// Rewrites the length of `input` in `fn main(input: [u8; N], ...)` of a
+/// circuit to `input_size`.
+fn set_input_size(circuit_source: &Path, input_size: usize) {
+    if let Ok(mut content) = fs::read_to_string(circuit_source) {
         // Replace only the input param length in `fn main(input: [u8; N], ...)`
         if let Some(fn_pos) = content.find("fn main(")
             && let Some(input_pos_rel) = content[fn_pos..].find("input: [u8;")
@@ -74,50 +79,75 @@ pub fn prepare_sha256(input_size: usize) -> (NoirProofScheme, PathBuf, PathBuf)
             }
             if start != end {
                 content.replace_range(start..end, &input_size.to_string());
-                fs::write(&circuit_source, content).expect("Failed to update circuit input length");
+                fs::write(circuit_source, content).expect("Failed to update circuit input length");
             }
         }
     }
+}
+
+/// Sizes, compiles and loads the SHA-256 circuit `package_name` and writes
+/// the fields of `input` to its Prover.toml.
+fn prepare_sha256_circuit(
+    package_name: &str,
+    input_size: usize,
+    input: &GeneratedInput,
+) -> (NoirProofScheme, PathBuf, PathBuf) {
+    // 1) Rewrite circuit input length to match input_size before compiling
+    let current_dir = std::env::current_dir().expect("Failed to get current directory");
+    let circuit_member_dir = current_dir
+        .join(WORKSPACE_ROOT)
+        .join(SHA256_CIRCUIT_SUB_PATH)
+        .join(package_name);
+    set_input_size(&circuit_member_dir.join("src/main.nr"), input_size);
 
     // 2) Compile workspace
-    let workspace_root = compile_workspace();
+    let (_, artifacts) = compile_workspace();
 
     // 3) Load scheme and prepare TOML matching the chosen size
-    let package_name = "sha256_var_input";
-    let circuit_path = workspace_root
-        .join("target")
-        .join(format!("{package_name}.json"));
+    let circuit_path = artifacts.join(format!("{package_name}.json"));
 
     let proof_scheme = NoirProofScheme::from_file(circuit_path.to_str().unwrap())
         .unwrap_or_else(|e| panic!("Failed to load proof scheme: {e}"));
 
-    let dir_name = "sha256_var_input";
-    let circuit_member_dir = workspace_root.join(SHA256_CIRCUIT_SUB_PATH).join(dir_name);
-    fs::create_dir_all(&circuit_member_dir).expect("Failed to create circuit dir");
-
-    // Generate exactly `input_size` bytes of input; circuit expects fixed array with `input_size` elements
-    let (data, _digest) = utils::generate_sha256_input(input_size);
-    let toml_content = format!(
-        "input = [{}]\ninput_len = {input_size}",
-        data.iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(", "),
-    );
-
+    let toml_content = input.render(InputFormat::Toml);
     let toml_path = circuit_member_dir.join("Prover.toml");
     fs::write(&toml_path, toml_content).expect("Failed to write Prover.toml");
 
     (proof_scheme, toml_path, circuit_path)
 }
 
+pub fn prepare_sha256(input_size: usize) -> (NoirProofScheme, PathBuf, PathBuf) {
+    // Generate exactly `input_size` bytes of input; circuit expects fixed array with `input_size` elements
+    let input = GeneratedInput::sha256(input_size)
+        .select(&["input", "input_len"])
+        .expect("sha256 input fields");
+    prepare_sha256_circuit("sha256_var_input", input_size, &input)
+}
+
+/// `prepare_sha256` for a given input, e.g. a negative instance or a test
+/// vector. `sha256_var_input` returns the digest instead of checking it, so
+/// this proves `sha256_check`, the same hash with the expected digest as a
+/// public input.
+pub fn prepare_sha256_input(
+    input_size: usize,
+    input: &GeneratedInput,
+) -> (NoirProofScheme, PathBuf, PathBuf) {
+    let input = input
+        .select(&["input", "input_len", "digest"])
+        .expect("sha256 input fields");
+    prepare_sha256_circuit("sha256_check", input_size, &input)
+}
+
 pub fn prepare_ecdsa(_: usize) -> (NoirProofScheme, PathBuf, PathBuf) {
-    let workspace_root = compile_workspace();
+    prepare_ecdsa_input(&GeneratedInput::ecdsa_p256())
+}
+
+/// `prepare_ecdsa` for a given input, e.g. a negative instance.
+pub fn prepare_ecdsa_input(input: &GeneratedInput) -> (NoirProofScheme, PathBuf, PathBuf) {
+    let (workspace_root, artifacts) = compile_workspace();
 
     let package_name = "p256_bigcurve";
-    let circuit_path = workspace_root
-        .join("target")
-        .join(format!("{package_name}.json"));
+    let circuit_path = artifacts.join(format!("{package_name}.json"));
 
     let proof_scheme = NoirProofScheme::from_file(circuit_path.to_str().unwrap())
         .unwrap_or_else(|e| panic!("Failed to load proof scheme: {e}"));
@@ -126,30 +156,15 @@ pub fn prepare_ecdsa(_: usize) -> (NoirProofScheme, PathBuf, PathBuf) {
     let circuit_member_dir = workspace_root.join(ECDSA_CIRCUIT_SUB_PATH).join(dir_name);
     fs::create_dir_all(&circuit_member_dir).expect("Failed to create circuit dir");
 
-    let (digest, (pub_key_x, pub_key_y), signature) = generate_ecdsa_input();
-    let toml_content = format!(
-        "hashed_message = [{}]\npub_key_x = [{}]\npub_key_y = [{}]\nsignature = [{}]",
-        digest
-            .iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(", "),
-        pub_key_x
-            .iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(", "),
-        pub_key_y
-            .iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(", "),
-        signature
-            .iter()
-            .map(u8::to_string)
-            .collect::<Vec<_>>()
-            .join(", "),
-    );
+    let toml_content = input
+        .select(&[
+            "message_hash:hashed_message",
+            "public_key_x:pub_key_x",
+            "public_key_y:pub_key_y",
+            "signature",
+        ])
+        .expect("ecdsa input fields")
+        .render(InputFormat::Toml);
 
     let toml_path = circuit_member_dir.join("Prover.toml");
     fs::write(&toml_path, toml_content).expect("Failed to write Prover.toml");
@@ -176,3 +191,55 @@ pub fn preprocessing_size(circuit_path: &Path) -> usize {
         .map(|m| m.len())
         .unwrap_or(0) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use utils::harness::{BenchHarnessConfig, BenchTarget, ProvingSystem, run_soundness_fn};
+    use utils::inputs::FieldValue;
+    use utils::vectors::{TestVector, check_hash_vectors, sha256_vectors};
+
+    #[test]
+    fn sha256_matches_vectors() {
+        // `sha256_check` asserts the digest instead of returning it, so the
+        // circuit agrees on a vector when its reference digest proves.
+        let vectors = sha256_vectors();
+        let input_size = vectors.iter().map(|v| v.message.len()).max().unwrap();
+        check_hash_vectors(&vectors, |message| {
+            let vector = vectors.iter().find(|v| v.message == message).unwrap();
+            let mut input = TestVector::Hash(vector.clone()).input();
+            if let Some(FieldValue::Bytes(bytes)) = input.field_mut("input") {
+                bytes.resize(input_size, 0);
+            }
+            let (proof_scheme, toml_path, _) = prepare_sha256_input(input_size, &input);
+            let accepted = catch_unwind(AssertUnwindSafe(|| {
+                verify(&prove(&proof_scheme, &toml_path), &proof_scheme).is_ok()
+            }))
+            .unwrap_or(false);
+            if accepted {
+                vector.digest.clone()
+            } else {
+                Vec::new()
+            }
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn ecdsa_rejects_invalid_instances() {
+        let cfg = BenchHarnessConfig {
+            target: BenchTarget::Ecdsa,
+            system: ProvingSystem::Provekit,
+            feature: None,
+            mem_binary_name: "ecdsa_mem_provekit",
+            output_dir: &std::env::temp_dir(),
+        };
+        run_soundness_fn(
+            cfg,
+            |_, input| Some(prepare_ecdsa_input(input)),
+            |(proof_scheme, toml_path, _)| prove(proof_scheme, toml_path),
+            |(proof_scheme, _, _), proof| verify(proof, proof_scheme).unwrap(),
+        );
+    }
+}
@@ -1,18 +1,18 @@
 use ere_risc0::compiler::RustRv32imaCustomized;
 use risc0::{
-    execution_cycles, prepare_ecdsa, preprocessing_size, proof_size, prove_ecdsa,
-    risc0_bench_properties, verify_ecdsa,
+    execution_cycles, prepare_ecdsa, prepare_ecdsa_input, preprocessing_size, proof_size,
+    prove_ecdsa, proving_time, verify_ecdsa,
 };
 use utils::harness::ProvingSystem;
-use utils::zkvm::ECDSA_BENCH;
+use utils::inputs::GeneratedInput;
 use utils::zkvm::helpers::load_or_compile_program;
+use utils::zkvm::{ECDSA_BENCH, bench_proof_feature};
 
 utils::define_benchmark_harness!(
     BenchTarget::Ecdsa,
     ProvingSystem::Risc0,
-    None,
+    bench_proof_feature(),
     "ecdsa_mem_risc0",
-    risc0_bench_properties(),
     { load_or_compile_program(&RustRv32imaCustomized, ECDSA_BENCH) },
     prepare_ecdsa,
     |_, _| 0,
@@ -20,5 +20,7 @@ utils::define_benchmark_harness!(
     verify_ecdsa,
     preprocessing_size,
     proof_size,
-    execution_cycles
+    execution_cycles,
+    proving_time;
+    soundness = |_, input: &GeneratedInput, program| prepare_ecdsa_input(input, program)
 );
@@ -1,7 +1,7 @@
-use risc0_zkvm::{guest::env, sha, sha::Sha256};
+use risc0_zkvm::guest::env;
 
 fn main() {
     let data = env::read_frame();
-    let hash = sha::Impl::hash_bytes(&data);
-    env::commit_slice(hash.as_bytes());
+    let hash = guest_lib::sha256(&data);
+    env::commit_slice(&hash);
 }
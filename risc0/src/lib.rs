@@ -1,66 +1,94 @@
 use ere_risc0::{EreRisc0, compiler::RustRv32imaCustomized};
 use ere_zkvm_interface::ProverResourceType;
-use utils::harness::{AuditStatus, BenchProperties};
-use utils::zkvm::{
-    CompiledProgram, PreparedEcdsa, PreparedSha256, build_ecdsa_input, build_input,
-    encode_public_key,
-};
+use utils::inputs::GeneratedInput;
+use utils::zkvm::{CompiledProgram, PreparedEcdsa, PreparedSha256};
 
 pub use utils::zkvm::{
-    execution_cycles, preprocessing_size, proof_size, prove_ecdsa, prove_sha256, verify_ecdsa,
-    verify_sha256,
+    execution_cycles, preprocessing_size, proof_size, prove_ecdsa, prove_sha256, proving_time,
+    verify_ecdsa, verify_sha256,
 };
 
-pub fn risc0_bench_properties() -> BenchProperties {
-    BenchProperties::new(
-        "STARK",
-        "BabyBear",  // 15 × 2^27 + 1; https://dev.risczero.com/proof-system-in-detail.pdf
-        "STARK",     // https://dev.risczero.com/proof-system/stark-by-hand
-        Some("FRI"), // https://dev.risczero.com/proof-system/stark-by-hand
-        "AIR",       // https://dev.risczero.com/proof-system/proof-system-sequence-diagram
-        true,        // https://dev.risczero.com/api/security-model
-        96,   // 96-bit base STARK, 99-bit recursion; https://dev.risczero.com/api/security-model
-        true, // STARK is PQ-safe (Groth16 compression is not); https://dev.risczero.com/api/security-model
-        true, // https://github.com/risc0/risc0/releases
-        AuditStatus::Audited, // https://github.com/risc0/rz-security/tree/main/audits
-        Some("RISC-V RV32IM"), // base + multiplication; https://dev.risczero.com/reference-docs/about-risc-v
-    )
+fn vm(program: &CompiledProgram<RustRv32imaCustomized>) -> EreRisc0 {
+    EreRisc0::new(program.program.clone(), ProverResourceType::Cpu)
+        .expect("failed to build risc0 prover instance")
 }
 
 pub fn prepare_sha256(
     input_size: usize,
     program: &CompiledProgram<RustRv32imaCustomized>,
 ) -> PreparedSha256<EreRisc0> {
-    let vm = EreRisc0::new(program.program.clone(), ProverResourceType::Cpu)
-        .expect("failed to build risc0 prover instance");
-
-    let (message_bytes, digest) = utils::generate_sha256_input(input_size);
-    let input = build_input(message_bytes);
+    PreparedSha256::prepare(vm(program), program.byte_size, input_size)
+}
 
-    PreparedSha256::with_expected_digest(vm, input, program.byte_size, digest)
+/// `prepare_sha256` for a given input, e.g. a negative instance.
+pub fn prepare_sha256_input(
+    input: &GeneratedInput,
+    program: &CompiledProgram<RustRv32imaCustomized>,
+) -> Option<PreparedSha256<EreRisc0>> {
+    PreparedSha256::from_generated(vm(program), program.byte_size, input)
 }
 
 /// Prepares an ECDSA signature verification benchmark (single secp256k1 signature).
 pub fn prepare_ecdsa(
-    _input_size: usize,
+    input_size: usize,
     program: &CompiledProgram<RustRv32imaCustomized>,
 ) -> PreparedEcdsa<EreRisc0> {
-    let vm = EreRisc0::new(program.program.clone(), ProverResourceType::Cpu)
-        .expect("failed to build risc0 prover instance");
+    PreparedEcdsa::prepare(vm(program), program.byte_size, input_size)
+}
 
-    let (digest, (pub_key_x, pub_key_y), signature) = utils::generate_ecdsa_k256_input();
+/// `prepare_ecdsa` for a given input. The guest verifies secp256k1, so the
+/// P-256 negative instances of `utils::negative` are not expressible.
+pub fn prepare_ecdsa_input(
+    input: &GeneratedInput,
+    program: &CompiledProgram<RustRv32imaCustomized>,
+) -> Option<PreparedEcdsa<EreRisc0>> {
+    PreparedEcdsa::from_generated(vm(program), program.byte_size, input)
+}
 
-    let encoded_verifying_key = encode_public_key(&pub_key_x, &pub_key_y)
-        .expect("generated public key should have valid size");
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ere_zkvm_interface::zkVM;
+    use utils::vectors::{
+        check_ecdsa_vectors, check_hash_vectors, ecdsa_k256_vectors, sha256_vectors,
+    };
+    use utils::zkvm::helpers::load_or_compile_program;
+    use utils::zkvm::{ECDSA_BENCH, EcdsaTarget, SHA256_BENCH, ZkTarget};
 
-    let input = build_ecdsa_input(encoded_verifying_key.clone(), digest.clone(), signature)
-        .expect("generated ECDSA input should have valid sizes");
+    #[test]
+    fn sha256_guest_matches_vectors() {
+        let program = load_or_compile_program(&RustRv32imaCustomized, SHA256_BENCH);
+        check_hash_vectors(&sha256_vectors(), |message| {
+            // The digest is only used by verify, which is not run here.
+            let prepared =
+                PreparedSha256::new(vm(&program), program.byte_size, (message.to_vec(), vec![]));
+            let (public_values, _) = prepared
+                .vm()
+                .execute(prepared.input())
+                .expect("guest execution must succeed");
+            public_values.to_vec()
+        })
+        .unwrap();
+    }
 
-    PreparedEcdsa::with_expected_values(
-        vm,
-        input,
-        program.byte_size,
-        (pub_key_x, pub_key_y),
-        digest,
-    )
+    #[test]
+    fn ecdsa_guest_matches_vectors() {
+        let program = load_or_compile_program(&RustRv32imaCustomized, ECDSA_BENCH);
+        // The guest panics on an invalid signature, so execution fails.
+        check_ecdsa_vectors(&ecdsa_k256_vectors(), |v| {
+            let data = (
+                v.message_hash.clone(),
+                (v.public_key_x.clone(), v.public_key_y.clone()),
+                v.signature.clone(),
+            );
+            let prepared = PreparedEcdsa::new(vm(&program), program.byte_size, data);
+            prepared
+                .vm()
+                .execute(prepared.input())
+                .is_ok_and(|(public_values, _)| {
+                    EcdsaTarget::check_public_values(prepared.data(), &public_values).is_ok()
+                })
+        })
+        .unwrap();
+    }
 }
@@ -1,17 +1,18 @@
 use ere_sp1::compiler::RustRv32imaCustomized;
 use sp1::{
-    execution_cycles, prepare_sha256, preprocessing_size, proof_size, prove_sha256, verify_sha256,
+    execution_cycles, prepare_sha256, prepare_sha256_input, preprocessing_size, proof_size,
+    prove_sha256, proving_time, verify_sha256,
 };
 use utils::harness::ProvingSystem;
-use utils::zkvm::SHA256_BENCH;
+use utils::inputs::GeneratedInput;
 use utils::zkvm::helpers::load_or_compile_program;
+use utils::zkvm::{SHA256_BENCH, bench_proof_feature};
 
 utils::define_benchmark_harness!(
     BenchTarget::Sha256,
     ProvingSystem::Sp1,
-    None,
+    bench_proof_feature(),
     "sha256_mem_sp1",
-    utils::harness::BenchProperties::default(),
     { load_or_compile_program(&RustRv32imaCustomized, SHA256_BENCH) },
     prepare_sha256,
     |_, _| 0,
@@ -19,5 +20,7 @@ utils::define_benchmark_harness!(
     verify_sha256,
     preprocessing_size,
     proof_size,
-    execution_cycles
+    execution_cycles,
+    proving_time;
+    soundness = |_, input: &GeneratedInput, program| prepare_sha256_input(input, program)
 );
@@ -0,0 +1,330 @@
+//! Scaling analysis of collected results: fits how prove time, memory and
+//! constraint (or cycle) counts grow with input size, to estimate the cost of
+//! sizes that are not benchmarked.
+//!
+//! Three models are fitted per system and quantity by least squares:
+//! linear (`a + b·n`), n log n (`a + b·n·log2 n`) and power law (`c·n^k`,
+//! fitted in log space). The model with the highest R² is reported.
+
+use crate::bench::{Metrics, display_bytes, display_duration};
+use human_repr::HumanCount;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+/// Bytes per SHA-256 (and Keccak-f lane-sized) message block used for the
+/// marginal cost.
+pub const BLOCK_SIZE: usize = 64;
+
+/// A system is flagged as overhead-dominated when the fixed (size-independent)
+/// part of its linear fit exceeds this share of the cost at the largest
+/// measured size. Extrapolations of such systems are unreliable.
+pub const FIXED_OVERHEAD_SHARE: f64 = 0.5;
+
+/// A quantity whose scaling is analysed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    ProveTime,
+    PeakMemory,
+    /// Constraints for circuit systems, executed cycles for zkVMs.
+    Size,
+}
+
+impl Quantity {
+    pub const ALL: [Quantity; 3] = [Quantity::ProveTime, Quantity::PeakMemory, Quantity::Size];
+
+    /// Value of the quantity, `None` if not measured.
+    fn value(&self, m: &Metrics) -> Option<f64> {
+        let v = match self {
+            Quantity::ProveTime => m.proof_duration.as_secs_f64(),
+            Quantity::PeakMemory => m.peak_memory as f64,
+            Quantity::Size if m.num_constraints > 0 => m.num_constraints as f64,
+            Quantity::Size => m.cycles.unwrap_or(0) as f64,
+        };
+        (v > 0.0).then_some(v)
+    }
+
+    /// Formats a value of this quantity in human-readable units.
+    pub fn display(&self, v: f64) -> String {
+        match self {
+            Quantity::ProveTime => display_duration(&Duration::from_secs_f64(v.max(0.0))),
+            Quantity::PeakMemory => display_bytes(&(v.max(0.0) as usize)),
+            Quantity::Size => (v.max(0.0) as u64).human_count_bare().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Quantity::ProveTime => "prove time",
+            Quantity::PeakMemory => "peak memory",
+            Quantity::Size => "constraints/cycles",
+        })
+    }
+}
+
+/// A fitted scaling model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Model {
+    /// `a + b·n`
+    Linear { a: f64, b: f64 },
+    /// `a + b·n·log2(n)`
+    NLogN { a: f64, b: f64 },
+    /// `c·n^k`
+    PowerLaw { c: f64, k: f64 },
+}
+
+impl Model {
+    pub fn predict(&self, n: f64) -> f64 {
+        match *self {
+            Model::Linear { a, b } => a + b * n,
+            Model::NLogN { a, b } => a + b * n * n.log2(),
+            Model::PowerLaw { c, k } => c * n.powf(k),
+        }
+    }
+}
+
+impl fmt::Display for Model {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Model::Linear { .. } => f.write_str("linear"),
+            Model::NLogN { .. } => f.write_str("n log n"),
+            Model::PowerLaw { k, .. } => write!(f, "power law (n^{k:.2})"),
+        }
+    }
+}
+
+/// A model together with its goodness of fit.
+#[derive(Debug, Clone, Copy)]
+pub struct Fit {
+    pub model: Model,
+    /// Coefficient of determination, computed on the original (not log) scale.
+    pub r_squared: f64,
+}
+
+/// Least-squares fit of `y = a + b·x`.
+fn fit_affine(xs: &[f64], ys: &[f64]) -> Option<(f64, f64)> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let sxx: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    if sxx == 0.0 {
+        return None;
+    }
+    let sxy: f64 = xs
+        .iter()
+        .zip(ys)
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let b = sxy / sxx;
+    Some((mean_y - b * mean_x, b))
+}
+
+fn r_squared(model: &Model, points: &[(f64, f64)]) -> f64 {
+    let mean = points.iter().map(|p| p.1).sum::<f64>() / points.len() as f64;
+    let ss_tot: f64 = points.iter().map(|p| (p.1 - mean).powi(2)).sum();
+    let ss_res: f64 = points
+        .iter()
+        .map(|p| (p.1 - model.predict(p.0)).powi(2))
+        .sum();
+    if ss_tot == 0.0 {
+        if ss_res == 0.0 { 1.0 } else { 0.0 }
+    } else {
+        1.0 - ss_res / ss_tot
+    }
+}
+
+/// Fits all models to `(input size, value)` points with positive coordinates.
+/// Needs at least two distinct sizes.
+pub fn fit_models(points: &[(f64, f64)]) -> Vec<Fit> {
+    let xs: Vec<f64> = points.iter().map(|p| p.0).collect();
+    let ys: Vec<f64> = points.iter().map(|p| p.1).collect();
+    let mut models = Vec::new();
+    if let Some((a, b)) = fit_affine(&xs, &ys) {
+        models.push(Model::Linear { a, b });
+    }
+    let nlogn: Vec<f64> = xs.iter().map(|x| x * x.log2()).collect();
+    if let Some((a, b)) = fit_affine(&nlogn, &ys) {
+        models.push(Model::NLogN { a, b });
+    }
+    let log_x: Vec<f64> = xs.iter().map(|x| x.ln()).collect();
+    let log_y: Vec<f64> = ys.iter().map(|y| y.ln()).collect();
+    if let Some((ln_c, k)) = fit_affine(&log_x, &log_y) {
+        models.push(Model::PowerLaw { c: ln_c.exp(), k });
+    }
+    models
+        .into_iter()
+        .map(|model| Fit {
+            model,
+            r_squared: r_squared(&model, points),
+        })
+        .collect()
+}
+
+/// Scaling analysis of one quantity of one system on one target.
+#[derive(Debug, Clone)]
+pub struct Analysis {
+    pub name: String,
+    pub feat: Option<String>,
+    pub device: Option<String>,
+    pub target: String,
+    pub quantity: Quantity,
+    pub sizes: Vec<usize>,
+    /// The best fit (highest R²; the earlier, simpler model wins ties).
+    pub best: Fit,
+    pub fits: Vec<Fit>,
+    /// Cost of one more `BLOCK_SIZE`-byte block at the largest measured size,
+    /// according to the best fit.
+    pub marginal_per_block: f64,
+    /// Share of the cost at the largest measured size that the linear fit
+    /// attributes to fixed overhead.
+    pub fixed_share: f64,
+    /// Best-fit predictions at the requested sizes.
+    pub extrapolated: Vec<(usize, f64)>,
+}
+
+impl Analysis {
+    pub fn overhead_dominated(&self) -> bool {
+        self.fixed_share > FIXED_OVERHEAD_SHARE
+    }
+}
+
+/// Analyses every (system, feature, device, target) with results at two or
+/// more input sizes, for every measured quantity.
+pub fn analyze(results: &[Metrics], extrapolate_to: &[usize]) -> Vec<Analysis> {
+    type Key<'a> = (&'a str, &'a str, Option<&'a str>, Option<&'a str>);
+    let mut series: BTreeMap<Key, Vec<&Metrics>> = BTreeMap::new();
+    for m in results {
+        let feat = m.feat.as_deref().filter(|f| !f.is_empty());
+        series
+            .entry((
+                m.target.as_str(),
+                m.name.as_str(),
+                feat,
+                m.device.as_deref(),
+            ))
+            .or_default()
+            .push(m);
+    }
+
+    let mut analyses = Vec::new();
+    for ((target, name, feat, device), rows) in series {
+        for quantity in Quantity::ALL {
+            let mut points: Vec<(f64, f64)> = rows
+                .iter()
+                .filter_map(|m| Some((m.input_size as f64, quantity.value(m)?)))
+                .filter(|p| p.0 > 0.0)
+                .collect();
+            points.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let fits = fit_models(&points);
+            let Some(best) = fits.iter().copied().reduce(|best, f| {
+                if f.r_squared > best.r_squared {
+                    f
+                } else {
+                    best
+                }
+            }) else {
+                continue;
+            };
+
+            let n_max = points.last().map(|p| p.0).unwrap_or_default();
+            let fixed_share = fits
+                .iter()
+                .find_map(|f| match f.model {
+                    Model::Linear { a, .. } => Some(a),
+                    _ => None,
+                })
+                .map(|a| (a.max(0.0) / best.model.predict(n_max)).min(1.0))
+                .unwrap_or(0.0);
+
+            analyses.push(Analysis {
+                name: name.to_string(),
+                feat: feat.map(str::to_string),
+                device: device.map(str::to_string),
+                target: target.to_string(),
+                quantity,
+                sizes: points.iter().map(|p| p.0 as usize).collect(),
+                best,
+                marginal_per_block: best.model.predict(n_max + BLOCK_SIZE as f64)
+                    - best.model.predict(n_max),
+                fixed_share,
+                extrapolated: extrapolate_to
+                    .iter()
+                    .map(|&n| (n, best.model.predict(n as f64)))
+                    .collect(),
+                fits,
+            });
+        }
+    }
+    analyses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    #[test]
+    fn test_fit_models_recovers_exact_models() {
+        let linear: Vec<(f64, f64)> = [128.0, 256.0, 512.0, 1024.0]
+            .iter()
+            .map(|&n| (n, 2.0 + 0.5 * n))
+            .collect();
+        let fit = fit_models(&linear)[0];
+        assert_eq!(fit.model.to_string(), "linear");
+        assert!((fit.r_squared - 1.0).abs() < 1e-9);
+        assert!((fit.model.predict(2048.0) - 1026.0).abs() < 1e-6);
+
+        let power: Vec<(f64, f64)> = [128.0, 256.0, 512.0, 1024.0]
+            .iter()
+            .map(|&n: &f64| (n, 3.0 * n.powf(1.5)))
+            .collect();
+        let fit = fit_models(&power)[2];
+        let Model::PowerLaw { c, k } = fit.model else {
+            panic!("expected power law")
+        };
+        assert!((c - 3.0).abs() < 1e-6 && (k - 1.5).abs() < 1e-9);
+    }
+
+    fn row(name: &str, input_size: usize, prove_ms: u64) -> Metrics {
+        let mut m = Metrics::new(
+            name.to_string(),
+            None,
+            true,
+            "sha256".to_string(),
+            input_size,
+            ProvingSystem::Sp1.properties(),
+        );
+        m.proof_duration = Duration::from_millis(prove_ms);
+        m
+    }
+
+    #[test]
+    fn test_analyze_flags_fixed_overhead() {
+        let results: Vec<Metrics> = [128, 256, 512, 1024, 2048]
+            .into_iter()
+            .flat_map(|n| {
+                [
+                    row("flat", n, 10_000 + n as u64 / 64),
+                    row("scaling", n, 10 + 10 * n as u64),
+                ]
+            })
+            .collect();
+        let analyses = analyze(&results, &[16384]);
+        let prove = |name: &str| {
+            analyses
+                .iter()
+                .find(|a| a.name == name && a.quantity == Quantity::ProveTime)
+                .unwrap()
+        };
+
+        assert!(prove("flat").overhead_dominated());
+        assert!(!prove("scaling").overhead_dominated());
+        // 10 ms per byte: a 64-byte block costs 0.64 s, 16 KB about 164 s.
+        assert!((prove("scaling").marginal_per_block - 0.64).abs() < 1e-6);
+        assert!((prove("scaling").extrapolated[0].1 - 163.85).abs() < 1e-3);
+        // Memory and size were not measured.
+        assert_eq!(analyses.len(), 2);
+    }
+}
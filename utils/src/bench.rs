@@ -1,10 +1,13 @@
 use crate::harness::BenchProperties;
+use crate::schema::METRICS_SCHEMA_VERSION;
 use human_repr::{HumanCount, HumanDuration};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use serde_with::{DurationNanoSeconds, serde_as};
 use std::{
     fmt::Display,
+    path::Path,
     process::Command,
     sync::{
         Arc,
@@ -65,21 +68,62 @@ pub fn measure_peak_memory<R, F: FnOnce() -> R>(func: F) -> (R, usize) {
 
 #[serde_as]
 #[skip_serializing_none]
-#[derive(Serialize, Deserialize, Tabled, Clone)]
+#[derive(Serialize, Deserialize, Tabled, JsonSchema, Clone)]
 pub struct Metrics {
+    /// Version of the metrics JSON format, see `utils::schema`.
+    /// Files written before versioning was introduced deserialize as version 0.
+    #[serde(default)]
+    #[tabled(skip)]
+    pub schema_version: u32,
     pub name: String,
     #[tabled(display_with = "display_string")]
     pub feat: Option<String>,
     pub is_zkvm: bool,
     pub target: String,
+    /// Machine the benchmark ran on (e.g. `mac2.metal`), taken from the
+    /// `BENCH_DEVICE` environment variable. Rows of different devices are
+    /// kept apart when merging and comparing results.
+    #[tabled(display_with = "display_string")]
+    pub device: Option<String>,
     #[tabled(display_with = "display_bytes")]
     pub input_size: usize,
     #[serde_as(as = "DurationNanoSeconds")]
+    #[schemars(with = "u64")]
     #[tabled(display_with = "display_duration")]
     pub proof_duration: Duration,
     #[serde_as(as = "DurationNanoSeconds")]
+    #[schemars(with = "u64")]
     #[tabled(display_with = "display_duration")]
     pub verify_duration: Duration,
+    /// Standard deviation of the proving time samples, when the measuring
+    /// tool (Criterion or hyperfine) reports one.
+    #[serde_as(as = "Option<DurationNanoSeconds>")]
+    #[schemars(with = "Option<u64>")]
+    #[tabled(skip)]
+    pub proof_duration_stddev: Option<Duration>,
+    /// Standard deviation of the verification time samples, when reported.
+    #[serde_as(as = "Option<DurationNanoSeconds>")]
+    #[schemars(with = "Option<u64>")]
+    #[tabled(skip)]
+    pub verify_duration_stddev: Option<Duration>,
+    /// Witness generation time (program execution for zkVMs), when the
+    /// system times it separately. It is then not part of `proof_duration`.
+    #[serde_as(as = "Option<DurationNanoSeconds>")]
+    #[schemars(with = "Option<u64>")]
+    #[tabled(skip)]
+    pub witness_duration: Option<Duration>,
+    /// Time of executing the program without proving (`zkVM::execute`), from
+    /// the Criterion `execute` benchmark. zkVMs only.
+    #[serde_as(as = "Option<DurationNanoSeconds>")]
+    #[schemars(with = "Option<u64>")]
+    #[tabled(skip)]
+    pub execution_duration: Option<Duration>,
+    /// Proving time as reported by the zkVM itself (ere's
+    /// `ProgramProvingReport`), next to the harness wall time `proof_duration`.
+    #[serde_as(as = "Option<DurationNanoSeconds>")]
+    #[schemars(with = "Option<u64>")]
+    #[tabled(skip)]
+    pub reported_proof_duration: Option<Duration>,
     #[tabled(display_with = "display_cycles")]
     pub cycles: Option<u64>,
     #[tabled(display_with = "display_bytes")]
@@ -92,24 +136,65 @@ pub struct Metrics {
     #[serde(flatten)]
     #[tabled(skip)]
     pub bench_properties: BenchProperties,
+    /// Normalized metrics computed from the raw ones by `collect_benchmarks`.
+    #[tabled(skip)]
+    pub derived: Option<DerivedMetrics>,
+}
+
+/// Metrics normalized by input size and circuit/program size, so systems
+/// measured in cycles (zkVMs) and in constraints (circuits) can be compared
+/// beyond raw wall-clock times.
+///
+/// Ratios whose denominator is zero (e.g. no constraints reported for a zkVM)
+/// are omitted.
+#[skip_serializing_none]
+#[derive(Serialize, Deserialize, JsonSchema, Clone, Debug, Default, PartialEq)]
+pub struct DerivedMetrics {
+    /// Input bytes proven per second.
+    pub throughput_bytes_per_sec: Option<f64>,
+    /// Proving time per constraint, in nanoseconds.
+    pub ns_per_constraint: Option<f64>,
+    /// Proving time per executed cycle, in nanoseconds.
+    pub ns_per_cycle: Option<f64>,
+    /// Peak proving memory per constraint, in bytes.
+    pub memory_per_constraint: Option<f64>,
+    /// Proof size divided by input size.
+    pub proof_size_ratio: Option<f64>,
+    /// Verification time divided by proving time.
+    pub verify_prove_ratio: Option<f64>,
+    /// Proving time divided by execution time (zkVMs): the cost of proving
+    /// over running the program.
+    pub prove_execute_ratio: Option<f64>,
+}
+
+fn ratio(numerator: f64, denominator: f64) -> Option<f64> {
+    (denominator > 0.0).then(|| numerator / denominator)
 }
 
-fn display_bytes(bytes: &usize) -> String {
+/// Environment variable naming the machine benchmarks run on.
+pub const DEVICE_ENV: &str = "BENCH_DEVICE";
+
+/// The device tag of this machine, from `BENCH_DEVICE`.
+pub fn current_device() -> Option<String> {
+    std::env::var(DEVICE_ENV).ok().filter(|d| !d.is_empty())
+}
+
+pub(crate) fn display_bytes(bytes: &usize) -> String {
     bytes.human_count_bytes().to_string()
 }
 
-fn display_duration(duration: &Duration) -> String {
+pub(crate) fn display_duration(duration: &Duration) -> String {
     duration.human_duration().to_string()
 }
 
-fn display_string(s: &Option<String>) -> String {
+pub(crate) fn display_string(s: &Option<String>) -> String {
     match s {
         Some(v) if !v.is_empty() => v.clone(),
         _ => "-".to_string(),
     }
 }
 
-fn display_cycles(cycles: &Option<u64>) -> String {
+pub(crate) fn display_cycles(cycles: &Option<u64>) -> String {
     match cycles {
         Some(v) => v.human_count_bare().to_string(),
         None => "-".to_string(),
@@ -126,19 +211,48 @@ impl Metrics {
         bench_properties: BenchProperties,
     ) -> Self {
         Metrics {
+            schema_version: METRICS_SCHEMA_VERSION,
             name,
             feat,
             is_zkvm,
             target,
+            device: current_device(),
             input_size: size,
             proof_duration: Duration::default(),
             verify_duration: Duration::default(),
+            proof_duration_stddev: None,
+            verify_duration_stddev: None,
+            witness_duration: None,
+            execution_duration: None,
+            reported_proof_duration: None,
             cycles: None,
             proof_size: 0,
             preprocessing_size: 0,
             num_constraints: 0,
             peak_memory: 0,
             bench_properties,
+            derived: None,
+        }
+    }
+
+    /// Computes the normalized metrics from the raw measurements.
+    pub fn derived_metrics(&self) -> DerivedMetrics {
+        let prove_ns = self.proof_duration.as_nanos() as f64;
+        let constraints = self.num_constraints as f64;
+        DerivedMetrics {
+            throughput_bytes_per_sec: ratio(
+                self.input_size as f64,
+                self.proof_duration.as_secs_f64(),
+            ),
+            ns_per_constraint: ratio(prove_ns, constraints),
+            ns_per_cycle: ratio(prove_ns, self.cycles.unwrap_or(0) as f64),
+            memory_per_constraint: ratio(self.peak_memory as f64, constraints),
+            proof_size_ratio: ratio(self.proof_size as f64, self.input_size as f64),
+            verify_prove_ratio: ratio(self.verify_duration.as_nanos() as f64, prove_ns),
+            prove_execute_ratio: ratio(
+                prove_ns,
+                self.execution_duration.unwrap_or_default().as_nanos() as f64,
+            ),
         }
     }
 }
@@ -176,22 +290,20 @@ pub fn write_csv(out_path: &str, results: &[Metrics]) {
 }
 
 fn metrics_filename(target: &str, size: usize, system: &str, feat: Option<&str>) -> String {
-    match feat {
-        Some(f) if !f.is_empty() => format!("{}_{}_{}_{}_metrics.json", target, size, system, f),
-        _ => format!("{}_{}_{}_metrics.json", target, size, system),
-    }
+    crate::harness::result_filename(target, size, system, feat, "metrics")
 }
 
 pub fn write_json_metrics(
+    output_dir: &Path,
     target_str: &'static str,
     size: usize,
     system_str: &'static str,
     feature: Option<&str>,
     metrics: &Metrics,
 ) {
-    let metrics_file = metrics_filename(target_str, size, system_str, feature);
+    let metrics_file = output_dir.join(metrics_filename(target_str, size, system_str, feature));
 
-    write_json_metrics_file(&metrics_file, metrics);
+    write_json_metrics_file(&metrics_file.to_string_lossy(), metrics);
 }
 
 pub fn write_json_metrics_file(output_path: &str, metrics: &Metrics) {
@@ -225,3 +337,37 @@ pub fn run_measure_mem_script(json_file: &str, binary_path: &str, input_size: us
 
     println!("{}", String::from_utf8_lossy(&output.stdout));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    #[test]
+    fn test_derived_metrics() {
+        let mut m = Metrics::new(
+            "risc0".to_string(),
+            None,
+            true,
+            "sha256".to_string(),
+            1024,
+            ProvingSystem::Risc0.properties(),
+        );
+        m.proof_duration = Duration::from_secs(2);
+        m.verify_duration = Duration::from_millis(20);
+        m.cycles = Some(1_000_000);
+        m.proof_size = 2048;
+        m.peak_memory = 1 << 30;
+        m.execution_duration = Some(Duration::from_millis(50));
+
+        let d = m.derived_metrics();
+        assert_eq!(d.throughput_bytes_per_sec, Some(512.0));
+        assert_eq!(d.ns_per_cycle, Some(2000.0));
+        assert_eq!(d.proof_size_ratio, Some(2.0));
+        assert_eq!(d.verify_prove_ratio, Some(0.01));
+        assert_eq!(d.prove_execute_ratio, Some(40.0));
+        // No constraints are reported for a zkVM.
+        assert_eq!(d.ns_per_constraint, None);
+        assert_eq!(d.memory_per_constraint, None);
+    }
+}
@@ -1,43 +1,162 @@
+use clap::Parser;
 use glob::glob;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{fs, io};
 use utils::bench::Metrics;
+use utils::compare::RowKey;
+use utils::coverage::{Coverage, coverage, expected_cells};
+use utils::harness::{BenchTarget, ProvingSystem};
+use utils::schema::{load_collected, metrics_from_value};
 
-/// Collect all JSON files in subdirectories of the workspace directory
-/// containing benchmark metrics, and write them to a single JSON file
-/// at `../collected_benchmarks.json`.
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Collect benchmark metrics into a single JSON file", long_about = None)]
+struct Cli {
+    /// Upgrade previously collected files (e.g. `results/collected_benchmarks_*.json`)
+    /// to the current metrics schema in place, instead of collecting
+    #[arg(long, value_name = "FILE", num_args = 1..)]
+    upgrade: Vec<PathBuf>,
+    /// Directory whose subdirectories hold `*_metrics.json` files, with Criterion
+    /// output under `<DIR>/target/criterion` (repeatable; default: workspace root).
+    /// `DEVICE=DIR` tags rows without a device with `DEVICE`, so result trees of
+    /// several hosts can be merged
+    #[arg(long = "root", value_name = "[DEVICE=]DIR", value_parser = parse_root)]
+    roots: Vec<InputRoot>,
+    /// Where to write the collected metrics
+    #[arg(long, short = 'o', default_value = "../collected_benchmarks.json")]
+    output: PathBuf,
+    /// Only collect these systems (repeatable)
+    #[arg(long = "system", value_name = "NAME")]
+    systems: Vec<ProvingSystem>,
+    /// Only collect these targets (repeatable)
+    #[arg(long = "target", value_name = "TARGET")]
+    targets: Vec<BenchTarget>,
+    /// Fail without writing the output on any unparsable metrics file, missing
+    /// Criterion or memory report, duplicate row, or row missing from the
+    /// expected matrix
+    #[arg(long)]
+    strict: bool,
+    /// Also write the coverage report as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    coverage: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug)]
+struct InputRoot {
+    device: Option<String>,
+    dir: PathBuf,
+}
+
+fn parse_root(s: &str) -> Result<InputRoot, String> {
+    match s.split_once('=') {
+        Some((device, dir)) if !device.is_empty() && !device.contains('/') => Ok(InputRoot {
+            device: Some(device.to_string()),
+            dir: PathBuf::from(dir),
+        }),
+        _ => Ok(InputRoot {
+            device: None,
+            dir: PathBuf::from(s),
+        }),
+    }
+}
+
+/// Collect all JSON files in subdirectories of the input roots (by default the
+/// workspace directory) containing benchmark metrics, compute their derived
+/// metrics, and write them to a single JSON file (by default
+/// `../collected_benchmarks.json`).
+///
+/// Prints a coverage report of the collected rows against the expected
+/// (system, target, size) matrix.
 fn main() -> io::Result<()> {
+    let cli = Cli::parse();
+    if !cli.upgrade.is_empty() {
+        return upgrade_collected(&cli.upgrade);
+    }
+
+    let roots = if cli.roots.is_empty() {
+        vec![InputRoot {
+            device: None,
+            dir: workspace_dir(),
+        }]
+    } else {
+        cli.roots.clone()
+    };
+    let selected = |m: &Metrics| {
+        (cli.systems.is_empty() || cli.systems.iter().any(|s| s.as_str() == m.name))
+            && (cli.targets.is_empty() || cli.targets.iter().any(|t| t.as_str() == m.target))
+    };
+
     let mut benchmarks: Vec<Metrics> = Vec::new();
+    let mut seen: HashMap<RowKey, PathBuf> = HashMap::new();
     let mut had_errors = false;
-    let root_dir = workspace_dir();
-    for entry in fs::read_dir(root_dir)? {
-        let path = entry?.path();
-        if path.is_dir() {
-            let metrics_file_paths = find_metrics_files(&path);
-            for metrics_file_path in metrics_file_paths {
-                println!("Extracting metrics from {}", metrics_file_path.display());
-                match extract_metrics(&path, &metrics_file_path) {
-                    Ok((metrics, errors)) => {
-                        benchmarks.push(metrics);
-                        had_errors |= errors;
-                    }
-                    Err(e) => {
-                        eprintln!(
-                            "\n===== WARNING: failed to parse metrics file =====\n  file: {}\n  error: {}\n===============================================\n",
-                            metrics_file_path.display(),
-                            e
-                        );
-                        continue;
+    let mut had_parse_errors = false;
+    let mut had_collisions = false;
+    for root in &roots {
+        for entry in fs::read_dir(&root.dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                let metrics_file_paths = find_metrics_files(&path);
+                for metrics_file_path in metrics_file_paths {
+                    println!("Extracting metrics from {}", metrics_file_path.display());
+                    match extract_metrics(&path, &metrics_file_path) {
+                        Ok((mut metrics, errors)) => {
+                            if !selected(&metrics) {
+                                continue;
+                            }
+                            if metrics.device.is_none() {
+                                metrics.device = root.device.clone();
+                            }
+                            // The same row from two files (e.g. two hosts without
+                            // device tags) would silently shadow one another.
+                            if let Some(first) = seen
+                                .get(&RowKey::from(&metrics))
+                                .filter(|p| **p != metrics_file_path)
+                            {
+                                eprintln!(
+                                    "\n===== WARNING: duplicate result, keeping the first =====\n  file: {}\n  first: {}\n  hint: tag the roots with --root DEVICE=DIR\n========================================================\n",
+                                    metrics_file_path.display(),
+                                    first.display()
+                                );
+                                had_collisions = true;
+                                continue;
+                            }
+                            seen.insert(RowKey::from(&metrics), metrics_file_path.clone());
+                            metrics.derived = Some(metrics.derived_metrics());
+                            benchmarks.push(metrics);
+                            had_errors |= errors;
+                        }
+                        Err(e) => {
+                            eprintln!(
+                                "\n===== WARNING: failed to parse metrics file =====\n  file: {}\n  error: {}\n===============================================\n",
+                                metrics_file_path.display(),
+                                e
+                            );
+                            had_parse_errors = true;
+                            continue;
+                        }
                     }
                 }
             }
         }
     }
 
+    let expected = expected_cells(&cli.systems, &cli.targets);
+    let coverage = coverage(&expected, &benchmarks);
+    print_coverage(&coverage);
+    if let Some(path) = &cli.coverage {
+        fs::write(path, serde_json::to_string_pretty(&coverage)?)?;
+    }
+
+    if cli.strict && (had_errors || had_parse_errors || had_collisions || !coverage.is_complete()) {
+        return Err(io::Error::other(
+            "Strict mode: metrics are incomplete, see the logs for details; nothing was written",
+        ));
+    }
+
     let output = serde_json::to_string_pretty(&benchmarks)?;
-    std::fs::write("../collected_benchmarks.json", output)?;
+    std::fs::write(&cli.output, output)?;
 
     if had_errors {
         Err(io::Error::other(
@@ -48,22 +167,57 @@ fn main() -> io::Result<()> {
     }
 }
 
+fn print_coverage(coverage: &Coverage) {
+    println!(
+        "\nCoverage: {}/{} expected (system, target, size) rows found",
+        coverage.found, coverage.expected
+    );
+    for (system, target, size) in &coverage.missing {
+        eprintln!("  MISSING: {system} {target} {size}");
+    }
+    for (system, target, size) in &coverage.unexpected {
+        println!("  not in the expected matrix: {system} {target} {size}");
+    }
+}
+
+/// Rewrite each collected benchmarks file with all entries upgraded to
+/// `utils::schema::METRICS_SCHEMA_VERSION` and derived metrics recomputed.
+fn upgrade_collected(paths: &[PathBuf]) -> io::Result<()> {
+    for path in paths {
+        let mut benchmarks = load_collected(path)?;
+        for metrics in &mut benchmarks {
+            metrics.derived = Some(metrics.derived_metrics());
+        }
+        let output = serde_json::to_string_pretty(&benchmarks)?;
+        fs::write(path, output)?;
+        println!(
+            "Upgraded {} entries in {}",
+            benchmarks.len(),
+            path.display()
+        );
+    }
+    Ok(())
+}
+
 /// Extract `Metrics` from JSON file `metrics_file_path` and fill in any missing
 /// fields by reading from Criterion's JSON files.
 ///
 /// Specifically, this function looks for fields `proof_duration` and
 /// `verify_duration` in the JSON file and fills them in with the mean
-/// execution times reported by Criterion's JSON files, if they are not
-/// already set. It also fills in the `peak_memory` field if it is not
+/// execution times (and their standard deviations) reported by Criterion's
+/// JSON files, if they are not already set. It also fills in the `peak_memory` field if it is not
 /// already set, using the memory usage reported by the `mem_report` JSON
 /// file.
 ///
+/// Files written by older harness versions are upgraded to the current
+/// schema first.
+///
 /// Returns `Metrics` if successful.
 fn extract_metrics(dir: &Path, metrics_file_path: &Path) -> io::Result<(Metrics, bool)> {
     let mut had_errors = false;
     let metrics_json: Value = serde_json::from_str(&fs::read_to_string(metrics_file_path)?)?;
 
-    let mut metrics: Metrics = serde_json::from_value(metrics_json)?;
+    let mut metrics: Metrics = metrics_from_value(metrics_json)?;
 
     let target = &metrics.target;
     let input_size = metrics.input_size;
@@ -84,12 +238,10 @@ fn extract_metrics(dir: &Path, metrics_file_path: &Path) -> io::Result<(Metrics,
             match fs::read_to_string(&crit_path_p) {
                 Ok(contents) => match serde_json::from_str::<Value>(&contents) {
                     Ok(proof_crit) => {
-                        if let Some(est) =
-                            proof_crit.get("mean").and_then(|m| m.get("point_estimate"))
-                            && let Some(f) = est.as_f64()
-                        {
-                            metrics.proof_duration = Duration::from_nanos(f.round() as u64);
+                        if let Some(mean) = criterion_estimate(&proof_crit, "mean") {
+                            metrics.proof_duration = mean;
                         }
+                        metrics.proof_duration_stddev = criterion_estimate(&proof_crit, "std_dev");
                     }
                     Err(e) => {
                         eprintln!(
@@ -132,13 +284,11 @@ fn extract_metrics(dir: &Path, metrics_file_path: &Path) -> io::Result<(Metrics,
             match fs::read_to_string(&crit_path_v) {
                 Ok(contents) => match serde_json::from_str::<Value>(&contents) {
                     Ok(verify_crit) => {
-                        if let Some(est) = verify_crit
-                            .get("mean")
-                            .and_then(|m| m.get("point_estimate"))
-                            && let Some(f) = est.as_f64()
-                        {
-                            metrics.verify_duration = Duration::from_nanos(f.round() as u64);
+                        if let Some(mean) = criterion_estimate(&verify_crit, "mean") {
+                            metrics.verify_duration = mean;
                         }
+                        metrics.verify_duration_stddev =
+                            criterion_estimate(&verify_crit, "std_dev");
                     }
                     Err(e) => {
                         eprintln!(
@@ -167,6 +317,38 @@ fn extract_metrics(dir: &Path, metrics_file_path: &Path) -> io::Result<(Metrics,
         }
     }
 
+    if metrics.is_zkvm && metrics.execution_duration.is_none() {
+        let crit_path_e = match feat {
+            Some(f) if !f.is_empty() => dir.parent().unwrap().join(format!(
+                "target/criterion/{target}_{input_size}_{proving_system}_{f}/{target}_{input_size}_{proving_system}_{f}_execute/new/estimates.json"
+            )),
+            _ => dir.parent().unwrap().join(format!(
+                "target/criterion/{target}_{input_size}_{proving_system}/{target}_{input_size}_{proving_system}_execute/new/estimates.json"
+            )),
+        };
+        // Runs from before the execute benchmark have no estimates; that is
+        // not an error.
+        if crit_path_e.exists() {
+            println!("Reading execution duration from {}", crit_path_e.display());
+            match fs::read_to_string(&crit_path_e)
+                .map_err(|e| e.to_string())
+                .and_then(|c| serde_json::from_str::<Value>(&c).map_err(|e| e.to_string()))
+            {
+                Ok(execute_crit) => {
+                    metrics.execution_duration = criterion_estimate(&execute_crit, "mean");
+                }
+                Err(e) => {
+                    eprintln!(
+                        "\n===== WARNING: failed to read execute estimates =====\n  file: {}\n  error: {}\n====================================================\n",
+                        crit_path_e.display(),
+                        e
+                    );
+                    had_errors = true;
+                }
+            }
+        }
+    }
+
     if metrics.peak_memory == 0 {
         let mem_path = match feat {
             Some(f) if !f.is_empty() => dir.join(format!(
@@ -215,6 +397,13 @@ fn extract_metrics(dir: &Path, metrics_file_path: &Path) -> io::Result<(Metrics,
     Ok((metrics, had_errors))
 }
 
+/// Reads the point estimate of `statistic` (e.g. `mean`, `std_dev`) from a
+/// Criterion `estimates.json`, in nanoseconds.
+fn criterion_estimate(estimates: &Value, statistic: &str) -> Option<Duration> {
+    let ns = estimates.get(statistic)?.get("point_estimate")?.as_f64()?;
+    Some(Duration::from_nanos(ns.round() as u64))
+}
+
 /// Returns the root directory of the current workspace, as determined by the
 /// `cargo locate-project` command.
 fn workspace_dir() -> PathBuf {
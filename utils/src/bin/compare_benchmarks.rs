@@ -0,0 +1,135 @@
+use clap::Parser;
+use std::path::PathBuf;
+use tabled::{Table, Tabled, settings::Style};
+use utils::bench::Metrics;
+use utils::compare::{Quantity, Thresholds, compare, display_change, display_value, select_device};
+use utils::schema::load_collected;
+
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Compare two collected benchmark files and flag regressions", long_about = None)]
+struct Cli {
+    /// Reference run, e.g. `results/collected_benchmarks_<run_id>.json`
+    baseline: PathBuf,
+    /// Run to check against the baseline
+    current: PathBuf,
+    /// Relative change of prove/verify time (in percent) flagged as a regression
+    #[arg(long, default_value_t = 10.0)]
+    time_threshold: f64,
+    /// Relative change of peak memory (in percent) flagged as a regression
+    #[arg(long, default_value_t = 10.0)]
+    memory_threshold: f64,
+    /// Relative change of proof/preprocessing size (in percent) flagged as a regression
+    #[arg(long, default_value_t = 5.0)]
+    size_threshold: f64,
+    /// Timing changes within this many combined standard deviations are treated
+    /// as noise (only when both runs recorded a standard deviation)
+    #[arg(long, default_value_t = 2.0)]
+    noise_sigmas: f64,
+    /// Also fail when a baseline benchmark is missing from the current run
+    #[arg(long)]
+    fail_on_missing: bool,
+    /// Only use baseline rows measured on this device, and match them
+    /// regardless of the current rows' device
+    #[arg(long)]
+    baseline_device: Option<String>,
+    /// Only use current rows measured on this device, and match them
+    /// regardless of the baseline rows' device
+    #[arg(long)]
+    current_device: Option<String>,
+}
+
+#[derive(Tabled)]
+struct ChangeRow {
+    benchmark: String,
+    prove: String,
+    verify: String,
+    #[tabled(rename = "peak memory")]
+    peak_memory: String,
+    #[tabled(rename = "proof size")]
+    proof_size: String,
+    #[tabled(rename = "preprocessing size")]
+    preprocessing_size: String,
+}
+
+/// Drops the device tags of all rows.
+fn select_device_untagged(rows: Vec<Metrics>) -> Vec<Metrics> {
+    rows.into_iter()
+        .map(|mut m| {
+            m.device = None;
+            m
+        })
+        .collect()
+}
+
+/// Prints the relative change of every benchmark present in both runs and
+/// exits with status 1 if any quantity regressed beyond its threshold, or if
+/// no benchmark matched.
+fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let thresholds = Thresholds {
+        time: cli.time_threshold / 100.0,
+        memory: cli.memory_threshold / 100.0,
+        size: cli.size_threshold / 100.0,
+        noise_sigmas: cli.noise_sigmas,
+    };
+
+    let mut baseline = load_collected(&cli.baseline)?;
+    let mut current = load_collected(&cli.current)?;
+    // Pivot by device: to compare two devices, drop the tags on both sides.
+    if cli.baseline_device.is_some() || cli.current_device.is_some() {
+        baseline = match &cli.baseline_device {
+            Some(device) => select_device(&baseline, device),
+            None => select_device_untagged(baseline),
+        };
+        current = match &cli.current_device {
+            Some(device) => select_device(&current, device),
+            None => select_device_untagged(current),
+        };
+    }
+    let comparison = compare(&baseline, &current, &thresholds);
+
+    let rows = comparison.rows.iter().map(|row| ChangeRow {
+        benchmark: row.key.to_string(),
+        prove: display_change(row.change(Quantity::ProveTime)),
+        verify: display_change(row.change(Quantity::VerifyTime)),
+        peak_memory: display_change(row.change(Quantity::PeakMemory)),
+        proof_size: display_change(row.change(Quantity::ProofSize)),
+        preprocessing_size: display_change(row.change(Quantity::PreprocessingSize)),
+    });
+    let mut table = Table::new(rows);
+    table.with(Style::markdown());
+    println!("{table}");
+    println!("\n(`!` marks a regression, `+` an improvement beyond the threshold)");
+
+    for key in &comparison.only_in_baseline {
+        eprintln!("WARNING: missing from current run: {key}");
+    }
+    for key in &comparison.only_in_current {
+        println!("New in current run: {key}");
+    }
+
+    let mut failed = cli.fail_on_missing && !comparison.only_in_baseline.is_empty();
+    // Nothing compared means nothing checked, e.g. runs of different devices.
+    if comparison.rows.is_empty() && !baseline.is_empty() {
+        eprintln!(
+            "ERROR: no benchmark of the baseline matches the current run; \
+             use --baseline-device/--current-device to compare different devices"
+        );
+        failed = true;
+    }
+    for (key, change) in comparison.regressions() {
+        eprintln!(
+            "REGRESSION: {key}: {} {} -> {} ({:+.1}%)",
+            change.quantity,
+            display_value(change.quantity, change.baseline),
+            display_value(change.quantity, change.current),
+            change.relative * 100.0
+        );
+        failed = true;
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
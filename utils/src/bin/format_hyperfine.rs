@@ -1,38 +1,78 @@
 use clap::Parser;
 use glob::glob;
-use regex::Regex;
 use serde::Deserialize;
 use serde_json::Value;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use utils::bench::Metrics;
-use utils::harness::BenchProperties;
-
-#[derive(clap::Args, Debug, Clone, Default)]
+use utils::harness::{
+    Arithmetization, AuditStatus, FieldCurve, Iop, Pcs, SetupKind, parse_result_stem,
+    result_filename,
+};
+use utils::orchestrate::{
+    read_counts, read_num_constraints_json, read_peak_memory_bytes, read_sizes_bytes,
+};
+use utils::properties::{
+    BenchProperties, resolve_is_zkvm, resolve_properties, resolve_properties_from,
+};
+
+/// The fields of `bench_props.json` as flags, an alternative to `--properties`.
+/// Either none are given or all the non-optional ones are.
+#[derive(clap::Args, Debug, Clone)]
+#[group(requires_all = [
+    "proving_system", "field_curve", "iop", "arithm", "security_bits", "setup_kind", "is_audited",
+])]
 struct BenchPropsArgs {
-    #[arg(long)]
+    #[arg(long, required = false)]
     proving_system: String,
+    #[arg(long, required = false)]
+    field_curve: FieldCurve,
+    #[arg(long, required = false)]
+    iop: Iop,
     #[arg(long)]
-    field_curve: String,
-    #[arg(long)]
-    iop: String,
-    #[arg(long)]
-    pcs: Option<String>,
-    #[arg(long)]
-    arithm: String,
-    #[arg(long)]
+    pcs: Option<Pcs>,
+    #[arg(long, required = false)]
+    arithm: Arithmetization,
+    #[arg(long, required = false)]
     security_bits: u64,
     #[arg(long)]
     is_pq: bool,
+    #[arg(long, required = false)]
+    setup_kind: SetupKind,
+    #[arg(long)]
+    recursion_friendly: bool,
     #[arg(long)]
     is_maintained: bool,
     #[arg(long)]
     is_zk: bool,
-    #[arg(long)]
-    is_audited: String,
+    #[arg(long, required = false)]
+    is_audited: AuditStatus,
     #[arg(long)]
     isa: Option<String>,
+    #[arg(long)]
+    proof_system_version: Option<String>,
+}
+
+impl From<BenchPropsArgs> for BenchProperties {
+    fn from(args: BenchPropsArgs) -> Self {
+        BenchProperties {
+            proving_system: args.proving_system.into(),
+            field_curve: args.field_curve,
+            iop: args.iop,
+            pcs: args.pcs,
+            arithm: args.arithm,
+            is_zk: args.is_zk,
+            security_bits: args.security_bits,
+            is_pq: args.is_pq,
+            setup_kind: args.setup_kind,
+            recursion_friendly: args.recursion_friendly,
+            is_maintained: args.is_maintained,
+            is_audited: args.is_audited,
+            isa: args.isa.map(Into::into),
+            proof_system_version: args.proof_system_version.map(Into::into),
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -46,18 +86,29 @@ struct Cli {
     #[arg(long)]
     name: Option<String>,
 
-    /// Optional feature name (default empty)
+    /// Optional feature name (default empty). Only the result files of this
+    /// variant are formatted, so several variants can share a directory.
     #[arg(long, default_value = "")]
     feature: Option<String>,
 
-    /// Mark as zkVM system (default: false)
+    /// Device tag of the machine the benchmarks ran on
+    #[arg(long, env = utils::bench::DEVICE_ENV)]
+    device: Option<String>,
+
+    /// Mark an unregistered system as zkVM (default: false). Registered
+    /// systems use `ProvingSystem::is_zkvm()`.
     #[arg(long, default_value_t = false)]
     is_zkvm: bool,
 
-    /// Optional path to properties file (JSON) to populate BenchProperties
-    #[arg(long)]
+    /// Optional path to properties file (JSON) to populate BenchProperties.
+    /// For systems in the `ProvingSystem` registry it must match the registered properties.
+    #[arg(long, conflicts_with = "BenchPropsArgs")]
     properties: Option<PathBuf>,
 
+    /// The properties as flags instead of `--properties`, with the same checks.
+    #[command(flatten)]
+    bench_props: Option<BenchPropsArgs>,
+
     /// Optional path to number of constraints file (JSON)
     #[arg(long)]
     num_constraints_file: Option<PathBuf>,
@@ -66,6 +117,7 @@ struct Cli {
 #[derive(Deserialize)]
 struct HyperfineRecord {
     mean: f64,
+    stddev: Option<f64>,
 }
 
 /// Formats hyperfine + RAM outputs into Metrics JSON and cleans up
@@ -86,11 +138,30 @@ fn main() -> std::io::Result<()> {
             .to_string()
     });
 
-    // hyperfine files have the form: hyperfine_<target>_<size>_prover_metrics.json
-    let pattern = system_dir.join("hyperfine_*_*_prover_metrics.json");
+    let is_zkvm = resolve_is_zkvm(&proving_system, cli.is_zkvm);
+    let bench_properties = match cli.bench_props {
+        Some(args) => resolve_properties_from(
+            &proving_system,
+            "the command line",
+            Some(args.into()),
+            is_zkvm,
+        ),
+        None => resolve_properties(&proving_system, cli.properties.as_deref(), is_zkvm),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("{e}");
+        std::process::exit(2);
+    });
+
+    let feat = match cli.feature.as_deref() {
+        Some(f) if !f.is_empty() => Some(f.to_string()),
+        _ => None,
+    };
+
+    // hyperfine files have the form:
+    // hyperfine_<target>_<size>_<system>[_<feature>]_prover_metrics.json
+    let pattern = system_dir.join("hyperfine_*_prover_metrics.json");
     let pattern = pattern.to_string_lossy().into_owned();
-    let re =
-        Regex::new(r"hyperfine_(?P<target>[^_]+)_(?P<size>[^_]+)_prover_metrics\.json$").unwrap();
 
     for entry in glob(&pattern).unwrap() {
         let prover_path = match entry {
@@ -102,52 +173,68 @@ fn main() -> std::io::Result<()> {
         };
 
         let file_name = prover_path.file_name().unwrap().to_string_lossy();
-        let caps = match re.captures(&file_name) {
-            Some(c) => c,
-            None => continue,
+        let Some(stem) = file_name
+            .strip_prefix("hyperfine_")
+            .and_then(|s| s.strip_suffix("_prover_metrics.json"))
+        else {
+            continue;
         };
-        let target = caps.name("target").unwrap().as_str().to_string();
-        let size_str = caps.name("size").unwrap().as_str();
-        let input_size: usize = size_str.parse().unwrap_or_else(|_| {
-            eprintln!("Could not parse input size from {file_name}");
-            std::process::exit(2);
-        });
-
-        let verifier_path = system_dir.join(format!(
-            "hyperfine_{target}_{input_size}_verifier_metrics.json"
-        ));
-        let mem_path = system_dir.join(format!("{target}_{input_size}_mem_report.json"));
-        let sizes_path = system_dir.join(format!("{target}_{input_size}_sizes.json"));
-
-        // Parse hyperfine JSONs to extract mean seconds
-        let prover_mean_sec = read_hyperfine_mean_seconds(&prover_path)?;
-        println!("Reading prover time from {}", prover_path.display());
-        let verifier_mean_sec = read_hyperfine_mean_seconds(&verifier_path)?;
-        println!("Reading verifier time from {}", verifier_path.display());
-
-        let feat = match cli.feature.as_deref() {
-            Some(f) if !f.is_empty() => Some(f.to_string()),
-            _ => None,
+        // Skips the results of other feature variants in the same directory.
+        let Some((target, input_size)) = parse_result_stem(stem, &proving_system, feat.as_deref())
+        else {
+            continue;
         };
-
-        let bench_properties = match &cli.properties {
-            Some(p) => load_properties_json(p)?,
-            None => {
-                eprintln!("No properties file provided");
-                std::process::exit(2);
-            }
+        let result_file = |kind: &str| {
+            system_dir.join(result_filename(
+                &target,
+                input_size,
+                &proving_system,
+                feat.as_deref(),
+                kind,
+            ))
         };
 
+        let verifier_path = system_dir.join(format!("hyperfine_{stem}_verifier_metrics.json"));
+        let witness_path = system_dir.join(format!("hyperfine_{stem}_witness_metrics.json"));
+        let mem_path = result_file("mem_report");
+        let sizes_path = result_file("sizes");
+        let counts_path = result_file("counts");
+
+        // Parse hyperfine JSONs to extract mean and standard deviation in seconds
+        let prover = read_hyperfine_record(&prover_path)?;
+        println!("Reading prover time from {}", prover_path.display());
+        let verifier = read_hyperfine_record(&verifier_path)?;
+        println!("Reading verifier time from {}", verifier_path.display());
+
         let mut metrics = Metrics::new(
             proving_system.clone(),
-            feat,
-            cli.is_zkvm,
+            feat.clone(),
+            is_zkvm,
             target.clone(),
             input_size,
-            bench_properties,
+            bench_properties.clone(),
         );
-        metrics.proof_duration = to_duration_ns(prover_mean_sec);
-        metrics.verify_duration = to_duration_ns(verifier_mean_sec);
+        if cli.device.is_some() {
+            metrics.device = cli.device.clone();
+        }
+        metrics.proof_duration = to_duration_ns(prover.mean);
+        metrics.verify_duration = to_duration_ns(verifier.mean);
+        metrics.proof_duration_stddev = prover.stddev.map(to_duration_ns);
+        metrics.verify_duration_stddev = verifier.stddev.map(to_duration_ns);
+
+        if witness_path.exists() {
+            let witness = read_hyperfine_record(&witness_path)?;
+            println!("Reading witness time from {}", witness_path.display());
+            metrics.witness_duration = Some(to_duration_ns(witness.mean));
+        }
+
+        let (cycles, reported_constraints) = if counts_path.exists() {
+            println!("Reading counts from {}", counts_path.display());
+            read_counts(&counts_path)?
+        } else {
+            (None, None)
+        };
+        metrics.cycles = cycles;
 
         if mem_path.exists()
             && let Ok(mem_bytes) = read_peak_memory_bytes(&mem_path)
@@ -164,7 +251,9 @@ fn main() -> std::io::Result<()> {
             metrics.preprocessing_size = preprocessing_size;
         }
 
-        if let Some(num_constraints_file) = &cli.num_constraints_file {
+        if let Some(num_constraints) = reported_constraints {
+            metrics.num_constraints = num_constraints;
+        } else if let Some(num_constraints_file) = &cli.num_constraints_file {
             if let Ok(num_constraints) =
                 read_num_constraints_json(num_constraints_file, &target, input_size)
             {
@@ -182,9 +271,7 @@ fn main() -> std::io::Result<()> {
             }
         }
 
-        let out_file = system_dir.join(format!(
-            "{target}_{input_size}_{proving_system}_metrics.json"
-        ));
+        let out_file = result_file("metrics");
         utils::bench::write_json_metrics_file(out_file.to_str().unwrap(), &metrics);
 
         // Cleanup originals
@@ -192,51 +279,14 @@ fn main() -> std::io::Result<()> {
         let _ = fs::remove_file(&verifier_path);
         let _ = fs::remove_file(&mem_path);
         let _ = fs::remove_file(&sizes_path);
+        let _ = fs::remove_file(&witness_path);
+        let _ = fs::remove_file(&counts_path);
     }
 
     Ok(())
 }
 
-/// Reads the number of constraints from the circuit_sizes.json file
-/// for the given target and size.
-/// The file contents are expected to be in the following format:
-/// {
-///   "target": {
-///     "size": number_of_constraints
-///   }
-/// }
-///
-/// Returns the number of constraints for the given target and size.
-fn read_num_constraints_json(
-    num_constraints_file: &PathBuf,
-    target: &str,
-    size: usize,
-) -> std::io::Result<usize> {
-    let v: Value = serde_json::from_str(&fs::read_to_string(num_constraints_file)?)?;
-
-    let target = v
-        .get(target)
-        .ok_or_else(|| io_err(&format!("missing {target} benchmark target")))?;
-    let size = target.get(size.to_string()).ok_or_else(|| {
-        io_err(&format!(
-            "missing circuit size for the input of {size} bytes for {target} benchmark target"
-        ))
-    })?;
-    Ok(size
-        .as_u64()
-        .ok_or_else(|| {
-            io_err(&format!(
-                "number of constraints value is not a number for the input of {size} bytes for {target} benchmark target"
-            ))
-        })? as usize)
-}
-
-fn load_properties_json(path: &Path) -> std::io::Result<BenchProperties> {
-    let s = fs::read_to_string(path)?;
-    serde_json::from_str::<BenchProperties>(&s).map_err(|e| io_err(&e.to_string()))
-}
-
-fn read_hyperfine_mean_seconds(path: &Path) -> std::io::Result<f64> {
+fn read_hyperfine_record(path: &Path) -> std::io::Result<HyperfineRecord> {
     let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
     let results = v
         .get("results")
@@ -245,28 +295,7 @@ fn read_hyperfine_mean_seconds(path: &Path) -> std::io::Result<f64> {
     let first = results.first().ok_or_else(|| io_err("results empty"))?;
     let rec: HyperfineRecord =
         serde_json::from_value(first.clone()).map_err(|_| io_err("invalid hyperfine record"))?;
-    Ok(rec.mean)
-}
-
-fn read_peak_memory_bytes(path: &Path) -> std::io::Result<usize> {
-    let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
-    v.get("peak_memory")
-        .and_then(|m| m.as_u64())
-        .map(|n| n as usize)
-        .ok_or_else(|| io_err("missing peak_memory"))
-}
-
-fn read_sizes_bytes(path: &Path) -> std::io::Result<(usize, usize)> {
-    let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
-    let proof = v
-        .get("proof_size")
-        .and_then(|m| m.as_u64())
-        .ok_or_else(|| io_err("missing proof_size"))? as usize;
-    let prep = v
-        .get("preprocessing_size")
-        .and_then(|m| m.as_u64())
-        .unwrap_or(0) as usize;
-    Ok((proof, prep))
+    Ok(rec)
 }
 
 fn to_duration_ns(seconds: f64) -> Duration {
@@ -0,0 +1,380 @@
+//! Content-addressed cache of build artifacts (compiled zkVM guests, Noir
+//! circuits, ...), so an edited source or a toolchain upgrade rebuilds the
+//! artifact instead of silently benchmarking a stale one.
+//!
+//! An entry is keyed by a hash of its source tree, the toolchain versions and
+//! the build options (see [`ArtifactKey`]) and stored as a directory
+//! `<root>/<kind>/<name>-<digest>/` next to an `entry.json` describing it.
+//! Building a new entry of a `kind` and `name` removes the older ones.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Environment variable overriding the cache location.
+pub const CACHE_DIR_ENV: &str = "ARTIFACT_CACHE_DIR";
+
+/// Length of the digest prefix in entry directory names.
+const DIGEST_PREFIX_LEN: usize = 16;
+
+const ENTRY_FILE: &str = "entry.json";
+
+/// Prefix of the directories entries are built in.
+const STAGING_PREFIX: &str = ".building-";
+
+fn is_staging(dir: &Path) -> bool {
+    dir.file_name()
+        .is_some_and(|n| n.to_string_lossy().starts_with(STAGING_PREFIX))
+}
+
+/// What an artifact is built from.
+#[derive(Debug, Clone, Default)]
+pub struct ArtifactKey {
+    /// Kind of artifact, e.g. `zkvm-guest` or `noir-circuit`.
+    pub kind: String,
+    /// Name of the artifact within its kind, e.g. `sha256-sp1`.
+    pub name: String,
+    /// Source files and directories. Directories are hashed recursively,
+    /// skipping `target` directories, hidden entries and `exclude`.
+    pub sources: Vec<PathBuf>,
+    /// File or directory names that do not affect the build (e.g. prover inputs).
+    pub exclude: Vec<String>,
+    /// Toolchain versions, e.g. from [`tool_version`].
+    pub toolchain: Vec<String>,
+    /// Build options that change the artifact.
+    pub options: Vec<String>,
+}
+
+impl ArtifactKey {
+    /// Hex SHA-256 of the key: kind, name, toolchain, options and the relative
+    /// paths and contents of all source files.
+    pub fn digest(&self) -> io::Result<String> {
+        let mut hasher = Sha256::new();
+        let mut field = |bytes: &[u8]| {
+            hasher.update((bytes.len() as u64).to_le_bytes());
+            hasher.update(bytes);
+        };
+        field(self.kind.as_bytes());
+        field(self.name.as_bytes());
+        for value in self.toolchain.iter().chain(&self.options) {
+            field(value.as_bytes());
+        }
+        for source in &self.sources {
+            let mut files = Vec::new();
+            self.collect_files(source, &mut files)?;
+            files.sort();
+            for file in files {
+                let relative = file.strip_prefix(source).unwrap_or(&file);
+                field(relative.to_string_lossy().as_bytes());
+                field(&fs::read(&file)?);
+            }
+        }
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    fn collect_files(&self, path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        if !path.is_dir() {
+            files.push(path.to_path_buf());
+            return Ok(());
+        }
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if name.starts_with('.') || name == "target" || self.exclude.iter().any(|e| *e == name)
+            {
+                continue;
+            }
+            self.collect_files(&path, files)?;
+        }
+        Ok(())
+    }
+}
+
+/// The `entry.json` of a cache entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub kind: String,
+    pub name: String,
+    pub digest: String,
+    pub toolchain: Vec<String>,
+    pub options: Vec<String>,
+    /// Unix time the entry was built.
+    pub created: u64,
+    /// Directory holding the artifact files.
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl CacheEntry {
+    /// Total size of the artifact files in bytes.
+    pub fn size(&self) -> u64 {
+        fn dir_size(path: &Path) -> u64 {
+            fs::read_dir(path)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|e| e.file_name() != ENTRY_FILE)
+                .map(|e| match e.metadata() {
+                    Ok(m) if m.is_dir() => dir_size(&e.path()),
+                    Ok(m) => m.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        }
+        dir_size(&self.path)
+    }
+}
+
+/// The first line of `command --version`, or `command unavailable` when it
+/// cannot be run, so a missing tool still yields a stable key.
+pub fn tool_version(command: &str) -> String {
+    subcommand_version(command, &[])
+}
+
+/// [`tool_version`] of a subcommand or toolchain override, e.g. `cargo prove`
+/// or `rustc +succinct`: the first line of `command args.. --version`.
+pub fn subcommand_version(command: &str, args: &[&str]) -> String {
+    Command::new(command)
+        .args(args)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .next()
+                .map(|l| l.trim().to_string())
+        })
+        .unwrap_or_else(|| {
+            let mut name = vec![command];
+            name.extend_from_slice(args);
+            format!("{} unavailable", name.join(" "))
+        })
+}
+
+/// Default cache location: `$ARTIFACT_CACHE_DIR`, otherwise
+/// `target/artifact-cache` in the repository containing the current directory.
+pub fn default_cache_dir() -> PathBuf {
+    if let Ok(dir) = std::env::var(CACHE_DIR_ENV)
+        && !dir.is_empty()
+    {
+        return PathBuf::from(dir);
+    }
+    let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let root = cwd
+        .ancestors()
+        .find(|d| d.join(".git").exists())
+        .unwrap_or(&cwd);
+    root.join("target").join("artifact-cache")
+}
+
+pub struct ArtifactCache {
+    root: PathBuf,
+}
+
+impl ArtifactCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        ArtifactCache { root: root.into() }
+    }
+
+    /// The cache at [`default_cache_dir`].
+    pub fn open_default() -> Self {
+        ArtifactCache::new(default_cache_dir())
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn entry_dir(&self, key: &ArtifactKey, digest: &str) -> PathBuf {
+        self.root
+            .join(&key.kind)
+            .join(format!("{}-{}", key.name, &digest[..DIGEST_PREFIX_LEN]))
+    }
+
+    /// The artifact directory of `key`, if it was built.
+    pub fn get(&self, key: &ArtifactKey) -> io::Result<Option<PathBuf>> {
+        let dir = self.entry_dir(key, &key.digest()?);
+        Ok(dir.join(ENTRY_FILE).exists().then_some(dir))
+    }
+
+    /// The artifact directory of `key`, running `build` to fill it on a miss.
+    /// `build` writes the artifact files into the directory it is given; the
+    /// entry only becomes visible once it succeeded. Older entries of the same
+    /// kind and name are removed.
+    pub fn get_or_build(
+        &self,
+        key: &ArtifactKey,
+        build: impl FnOnce(&Path) -> io::Result<()>,
+    ) -> io::Result<PathBuf> {
+        let digest = key.digest()?;
+        let dir = self.entry_dir(key, &digest);
+        if dir.join(ENTRY_FILE).exists() {
+            return Ok(dir);
+        }
+
+        let staging = self.root.join(&key.kind).join(format!(
+            "{STAGING_PREFIX}{}-{}",
+            key.name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&staging);
+        fs::create_dir_all(&staging)?;
+        if let Err(e) = build(&staging) {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+        let entry = CacheEntry {
+            kind: key.kind.clone(),
+            name: key.name.clone(),
+            digest,
+            toolchain: key.toolchain.clone(),
+            options: key.options.clone(),
+            created: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            path: PathBuf::new(),
+        };
+        fs::write(
+            staging.join(ENTRY_FILE),
+            serde_json::to_string_pretty(&entry)?,
+        )?;
+
+        for stale in self.entries()? {
+            if stale.kind == key.kind && stale.name == key.name {
+                fs::remove_dir_all(&stale.path)?;
+            }
+        }
+        if fs::rename(&staging, &dir).is_err() {
+            // Built concurrently by another process.
+            let _ = fs::remove_dir_all(&staging);
+        }
+        Ok(dir)
+    }
+
+    /// All entries, ordered by kind and name.
+    pub fn entries(&self) -> io::Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+        if !self.root.is_dir() {
+            return Ok(entries);
+        }
+        for kind in fs::read_dir(&self.root)? {
+            let kind = kind?.path();
+            if !kind.is_dir() {
+                continue;
+            }
+            for dir in fs::read_dir(&kind)? {
+                let dir = dir?.path();
+                if is_staging(&dir) {
+                    continue;
+                }
+                let Ok(json) = fs::read_to_string(dir.join(ENTRY_FILE)) else {
+                    continue;
+                };
+                let mut entry: CacheEntry = serde_json::from_str(&json)
+                    .map_err(|e| io::Error::other(format!("{}: {e}", dir.display())))?;
+                entry.path = dir;
+                entries.push(entry);
+            }
+        }
+        entries.sort_by(|a, b| (&a.kind, &a.name).cmp(&(&b.kind, &b.name)));
+        Ok(entries)
+    }
+
+    /// Removes the entries matching `kind` and `name` (all when `None`), and
+    /// leftovers of interrupted builds. Returns the number of removed entries.
+    pub fn clean(&self, kind: Option<&str>, name: Option<&str>) -> io::Result<usize> {
+        let mut removed = 0;
+        for entry in self.entries()? {
+            if kind.is_none_or(|k| k == entry.kind) && name.is_none_or(|n| n == entry.name) {
+                fs::remove_dir_all(&entry.path)?;
+                removed += 1;
+            }
+        }
+        if self.root.is_dir() {
+            for kind_dir in fs::read_dir(&self.root)? {
+                let kind_dir = kind_dir?.path();
+                if !kind_dir.is_dir() {
+                    continue;
+                }
+                for dir in fs::read_dir(&kind_dir)? {
+                    let dir = dir?.path();
+                    if is_staging(&dir) {
+                        let _ = fs::remove_dir_all(&dir);
+                    }
+                }
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_cache() {
+        let dir = std::env::temp_dir().join(format!("utils_cache_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let src = dir.join("guest");
+        fs::create_dir_all(src.join("target")).unwrap();
+        fs::write(src.join("main.rs"), "fn main() {}").unwrap();
+        fs::write(src.join("target/out.bin"), "old build").unwrap();
+        let cache = ArtifactCache::new(dir.join("cache"));
+        let key = ArtifactKey {
+            kind: "zkvm-guest".to_string(),
+            name: "sha256".to_string(),
+            sources: vec![src.clone()],
+            toolchain: vec!["rustc 1.0".to_string()],
+            ..Default::default()
+        };
+
+        let mut builds = 0;
+        let mut build = |out: &Path| {
+            builds += 1;
+            fs::write(out.join("program.bin"), "program")
+        };
+        let first = cache.get_or_build(&key, &mut build).unwrap();
+        assert_eq!(cache.get_or_build(&key, &mut build).unwrap(), first);
+        // Build outputs are not part of the key.
+        fs::write(src.join("target/out.bin"), "new build").unwrap();
+        assert_eq!(cache.get(&key).unwrap(), Some(first.clone()));
+
+        // Editing the source or upgrading the toolchain rebuilds and drops
+        // the stale entry.
+        fs::write(src.join("main.rs"), "fn main() { loop {} }").unwrap();
+        assert_eq!(cache.get(&key).unwrap(), None);
+        let second = cache.get_or_build(&key, &mut build).unwrap();
+        assert_ne!(second, first);
+        assert!(!first.exists());
+        let upgraded = ArtifactKey {
+            toolchain: vec!["rustc 2.0".to_string()],
+            ..key.clone()
+        };
+        assert_ne!(upgraded.digest().unwrap(), key.digest().unwrap());
+        assert_eq!(builds, 2);
+
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].size(), "program".len() as u64);
+        assert_eq!(cache.clean(Some("noir-circuit"), None).unwrap(), 0);
+        assert_eq!(cache.clean(None, Some("sha256")).unwrap(), 1);
+        assert!(cache.entries().unwrap().is_empty());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_subcommand_version_of_missing_tool() {
+        assert_eq!(
+            subcommand_version("utils-missing-tool", &["prove"]),
+            "utils-missing-tool prove unavailable"
+        );
+    }
+}
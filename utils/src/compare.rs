@@ -0,0 +1,391 @@
+//! Comparison of two collected benchmark runs, e.g. a fresh run against
+//! `results/collected_benchmarks_<run_id>.json`, to gate prover upgrades on
+//! performance regressions.
+//!
+//! Rows are matched by (name, feat, target, input size, device); when one run
+//! has no device tags (e.g. it predates them) and the other a single device,
+//! the device is ignored. For every measured
+//! quantity the relative change is classified against a threshold; timings
+//! that also carry a standard deviation are only flagged when the change
+//! exceeds the combined sampling noise as well.
+
+use crate::bench::Metrics;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::time::Duration;
+
+/// Identifies the same benchmark across runs. Rows of different devices are
+/// different benchmarks.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RowKey {
+    pub name: String,
+    pub feat: Option<String>,
+    pub target: String,
+    pub input_size: usize,
+    pub device: Option<String>,
+}
+
+impl From<&Metrics> for RowKey {
+    fn from(m: &Metrics) -> Self {
+        RowKey {
+            name: m.name.clone(),
+            feat: m.feat.clone().filter(|f| !f.is_empty()),
+            target: m.target.clone(),
+            input_size: m.input_size,
+            device: m.device.clone(),
+        }
+    }
+}
+
+impl fmt::Display for RowKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(feat) = &self.feat {
+            write!(f, " ({feat})")?;
+        }
+        write!(f, " {} {}", self.target, self.input_size)?;
+        if let Some(device) = &self.device {
+            write!(f, " @ {device}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A compared quantity. Lower is better for all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quantity {
+    ProveTime,
+    VerifyTime,
+    PeakMemory,
+    ProofSize,
+    PreprocessingSize,
+}
+
+impl Quantity {
+    pub const ALL: [Quantity; 5] = [
+        Quantity::ProveTime,
+        Quantity::VerifyTime,
+        Quantity::PeakMemory,
+        Quantity::ProofSize,
+        Quantity::PreprocessingSize,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quantity::ProveTime => "prove time",
+            Quantity::VerifyTime => "verify time",
+            Quantity::PeakMemory => "peak memory",
+            Quantity::ProofSize => "proof size",
+            Quantity::PreprocessingSize => "preprocessing size",
+        }
+    }
+
+    fn value(&self, m: &Metrics) -> f64 {
+        match self {
+            Quantity::ProveTime => m.proof_duration.as_secs_f64(),
+            Quantity::VerifyTime => m.verify_duration.as_secs_f64(),
+            Quantity::PeakMemory => m.peak_memory as f64,
+            Quantity::ProofSize => m.proof_size as f64,
+            Quantity::PreprocessingSize => m.preprocessing_size as f64,
+        }
+    }
+
+    fn stddev(&self, m: &Metrics) -> Option<f64> {
+        match self {
+            Quantity::ProveTime => m.proof_duration_stddev.map(|d| d.as_secs_f64()),
+            Quantity::VerifyTime => m.verify_duration_stddev.map(|d| d.as_secs_f64()),
+            _ => None,
+        }
+    }
+
+    fn threshold(&self, t: &Thresholds) -> f64 {
+        match self {
+            Quantity::ProveTime | Quantity::VerifyTime => t.time,
+            Quantity::PeakMemory => t.memory,
+            Quantity::ProofSize | Quantity::PreprocessingSize => t.size,
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Relative change (as a fraction, `0.1` = 10%) beyond which a quantity is
+/// flagged, and how many standard deviations of sampling noise a timing
+/// change must additionally exceed.
+#[derive(Debug, Clone, Copy)]
+pub struct Thresholds {
+    pub time: f64,
+    pub memory: f64,
+    pub size: f64,
+    pub noise_sigmas: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds {
+            time: 0.10,
+            memory: 0.10,
+            size: 0.05,
+            noise_sigmas: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Within the threshold, or within the sampling noise.
+    Unchanged,
+    Improved,
+    Regressed,
+}
+
+/// Change of one quantity between the baseline and the current run.
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub quantity: Quantity,
+    pub baseline: f64,
+    pub current: f64,
+    /// `current / baseline - 1`.
+    pub relative: f64,
+    pub verdict: Verdict,
+}
+
+impl Change {
+    fn new(quantity: Quantity, baseline: &Metrics, current: &Metrics, t: &Thresholds) -> Self {
+        let (b, c) = (quantity.value(baseline), quantity.value(current));
+        let relative = c / b - 1.0;
+        let within_noise = match (quantity.stddev(baseline), quantity.stddev(current)) {
+            (Some(sb), Some(sc)) => (c - b).abs() <= t.noise_sigmas * sb.hypot(sc),
+            _ => false,
+        };
+        let verdict = if relative.abs() <= quantity.threshold(t) || within_noise {
+            Verdict::Unchanged
+        } else if relative > 0.0 {
+            Verdict::Regressed
+        } else {
+            Verdict::Improved
+        };
+        Change {
+            quantity,
+            baseline: b,
+            current: c,
+            relative,
+            verdict,
+        }
+    }
+}
+
+/// All changes of one benchmark present in both runs. Quantities that were
+/// not measured (zero) in either run are left out.
+#[derive(Debug, Clone)]
+pub struct RowComparison {
+    pub key: RowKey,
+    pub changes: Vec<Change>,
+}
+
+impl RowComparison {
+    pub fn change(&self, quantity: Quantity) -> Option<&Change> {
+        self.changes.iter().find(|c| c.quantity == quantity)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Comparison {
+    pub rows: Vec<RowComparison>,
+    pub only_in_baseline: Vec<RowKey>,
+    pub only_in_current: Vec<RowKey>,
+}
+
+impl Comparison {
+    /// All regressed quantities, with the benchmark they belong to.
+    pub fn regressions(&self) -> impl Iterator<Item = (&RowKey, &Change)> {
+        self.rows.iter().flat_map(|row| {
+            row.changes
+                .iter()
+                .filter(|c| c.verdict == Verdict::Regressed)
+                .map(move |c| (&row.key, c))
+        })
+    }
+
+    pub fn has_regressions(&self) -> bool {
+        self.regressions().next().is_some()
+    }
+}
+
+/// Keeps the rows measured on `device` and drops their device tag, so they can
+/// be matched against the rows of another device (e.g. to compare two hosts
+/// within the same dataset).
+pub fn select_device(rows: &[Metrics], device: &str) -> Vec<Metrics> {
+    rows.iter()
+        .filter(|m| m.device.as_deref() == Some(device))
+        .cloned()
+        .map(|mut m| {
+            m.device = None;
+            m
+        })
+        .collect()
+}
+
+/// Whether the device tags cannot tell rows apart: one run is untagged and
+/// the other measured on at most one device.
+fn ignore_device(baseline: &[Metrics], current: &[Metrics]) -> bool {
+    fn devices(rows: &[Metrics]) -> BTreeSet<Option<&str>> {
+        rows.iter().map(|m| m.device.as_deref()).collect()
+    }
+    let (b, c) = (devices(baseline), devices(current));
+    let untagged = |d: &BTreeSet<Option<&str>>| d.iter().all(Option::is_none);
+    (untagged(&b) && c.len() <= 1) || (untagged(&c) && b.len() <= 1)
+}
+
+/// Matches the rows of both runs and classifies every change.
+pub fn compare(baseline: &[Metrics], current: &[Metrics], thresholds: &Thresholds) -> Comparison {
+    let ignore_device = ignore_device(baseline, current);
+    let index = |rows: &[Metrics]| -> BTreeMap<RowKey, Metrics> {
+        rows.iter()
+            .map(|m| {
+                let mut key = RowKey::from(m);
+                if ignore_device {
+                    key.device = None;
+                }
+                (key, m.clone())
+            })
+            .collect()
+    };
+    let baseline = index(baseline);
+    let mut current = index(current);
+
+    let mut comparison = Comparison::default();
+    for (key, b) in baseline {
+        let Some(c) = current.remove(&key) else {
+            comparison.only_in_baseline.push(key);
+            continue;
+        };
+        let changes = Quantity::ALL
+            .into_iter()
+            .filter(|q| q.value(&b) > 0.0 && q.value(&c) > 0.0)
+            .map(|q| Change::new(q, &b, &c, thresholds))
+            .collect();
+        comparison.rows.push(RowComparison { key, changes });
+    }
+    comparison.only_in_current = current.into_keys().collect();
+    comparison
+}
+
+/// Formats a change for display, e.g. `+12.3% !` for a regression.
+pub fn display_change(change: Option<&Change>) -> String {
+    match change {
+        None => "-".to_string(),
+        Some(c) => {
+            let marker = match c.verdict {
+                Verdict::Unchanged => "",
+                Verdict::Improved => " +",
+                Verdict::Regressed => " !",
+            };
+            format!("{:+.1}%{}", c.relative * 100.0, marker)
+        }
+    }
+}
+
+/// Formats a baseline/current value of `quantity` in human-readable units.
+pub fn display_value(quantity: Quantity, value: f64) -> String {
+    match quantity {
+        Quantity::ProveTime | Quantity::VerifyTime => {
+            crate::bench::display_duration(&Duration::from_secs_f64(value))
+        }
+        _ => crate::bench::display_bytes(&(value as usize)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    fn row(name: &str, prove_ms: u64, proof_size: usize) -> Metrics {
+        let mut m = Metrics::new(
+            name.to_string(),
+            None,
+            true,
+            "sha256".to_string(),
+            128,
+            ProvingSystem::Sp1.properties(),
+        );
+        m.proof_duration = Duration::from_millis(prove_ms);
+        m.proof_size = proof_size;
+        m
+    }
+
+    #[test]
+    fn test_compare_flags_regressions_beyond_threshold() {
+        let baseline = [row("sp1", 1000, 1000), row("risc0", 1000, 1000)];
+        let current = [row("sp1", 1200, 1010), row("openvm", 1000, 1000)];
+        let cmp = compare(&baseline, &current, &Thresholds::default());
+
+        assert_eq!(cmp.rows.len(), 1);
+        assert_eq!(cmp.only_in_baseline[0].name, "risc0");
+        assert_eq!(cmp.only_in_current[0].name, "openvm");
+
+        let row = &cmp.rows[0];
+        let prove = row.change(Quantity::ProveTime).unwrap();
+        assert_eq!(prove.verdict, Verdict::Regressed);
+        assert!((prove.relative - 0.2).abs() < 1e-9);
+        assert_eq!(
+            row.change(Quantity::ProofSize).unwrap().verdict,
+            Verdict::Unchanged
+        );
+        // Unmeasured quantities are not compared.
+        assert!(row.change(Quantity::PeakMemory).is_none());
+        assert!(cmp.has_regressions());
+    }
+
+    #[test]
+    fn test_compare_by_device() {
+        let mut a = row("sp1", 1000, 1000);
+        a.device = Some("mac2.metal".to_string());
+        let mut b = row("sp1", 2000, 1000);
+        b.device = Some("c7i.8xlarge".to_string());
+        let rows = [a, b];
+
+        // Rows of different devices are different benchmarks.
+        let cmp = compare(&rows[..1], &rows[1..], &Thresholds::default());
+        assert!(cmp.rows.is_empty());
+
+        let cmp = compare(
+            &select_device(&rows, "mac2.metal"),
+            &select_device(&rows, "c7i.8xlarge"),
+            &Thresholds::default(),
+        );
+        assert!(cmp.has_regressions());
+
+        // An untagged baseline still matches a run of a single device.
+        let untagged = row("sp1", 1000, 1000);
+        let cmp = compare(
+            std::slice::from_ref(&untagged),
+            &rows[1..],
+            &Thresholds::default(),
+        );
+        assert!(cmp.has_regressions());
+        // With several devices in the current run the match is ambiguous.
+        let cmp = compare(&[untagged], &rows, &Thresholds::default());
+        assert!(cmp.rows.is_empty());
+    }
+
+    #[test]
+    fn test_compare_ignores_changes_within_noise() {
+        let mut b = row("sp1", 1000, 1000);
+        let mut c = row("sp1", 1300, 1000);
+        b.proof_duration_stddev = Some(Duration::from_millis(150));
+        c.proof_duration_stddev = Some(Duration::from_millis(150));
+        let cmp = compare(&[b.clone()], &[c.clone()], &Thresholds::default());
+        assert!(!cmp.has_regressions());
+
+        c.proof_duration_stddev = Some(Duration::from_millis(10));
+        b.proof_duration_stddev = Some(Duration::from_millis(10));
+        let cmp = compare(&[b], &[c], &Thresholds::default());
+        assert!(cmp.has_regressions());
+    }
+}
@@ -0,0 +1,132 @@
+//! Coverage of a collected dataset: which (system, target, input size) rows
+//! are expected according to the harness registry (`ProvingSystem::targets`)
+//! and `input_sizes_for`, and which of them were actually found. Systems the
+//! harness refuses to benchmark (`validated_properties` fails, e.g. an
+//! undetermined security level) are not expected by default.
+
+use crate::bench::Metrics;
+use crate::harness::{BenchTarget, ProvingSystem};
+use crate::input_sizes_for;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// A (system, target, input size) cell of the benchmark matrix.
+pub type Cell = (String, String, usize);
+
+#[derive(Debug, Default, Serialize)]
+pub struct Coverage {
+    pub expected: usize,
+    pub found: usize,
+    /// Expected rows without results.
+    pub missing: Vec<Cell>,
+    /// Rows with results that the registry does not expect, e.g. a system
+    /// benchmarked on a new target that was not added to `targets()` yet.
+    pub unexpected: Vec<Cell>,
+}
+
+impl Coverage {
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+/// The expected matrix, restricted to `systems` and `targets` when given.
+/// Without `systems`, the benchmarked systems: those whose properties validate.
+pub fn expected_cells(systems: &[ProvingSystem], targets: &[BenchTarget]) -> BTreeSet<Cell> {
+    let systems: Vec<ProvingSystem> = if systems.is_empty() {
+        ProvingSystem::ALL
+            .into_iter()
+            .filter(|s| s.validated_properties().is_ok())
+            .collect()
+    } else {
+        systems.to_vec()
+    };
+    let mut cells = BTreeSet::new();
+    for system in &systems {
+        for &target in system.targets() {
+            if !targets.is_empty() && !targets.contains(&target) {
+                continue;
+            }
+            for size in input_sizes_for(target) {
+                cells.insert((
+                    system.as_str().to_string(),
+                    target.as_str().to_string(),
+                    size,
+                ));
+            }
+        }
+    }
+    cells
+}
+
+/// Compares the expected matrix with the collected rows. Feature variants of
+/// a system all count towards the same cell.
+pub fn coverage(expected: &BTreeSet<Cell>, collected: &[Metrics]) -> Coverage {
+    let found: BTreeSet<Cell> = collected
+        .iter()
+        .map(|m| (m.name.clone(), m.target.clone(), m.input_size))
+        .collect();
+    Coverage {
+        expected: expected.len(),
+        found: expected.intersection(&found).count(),
+        missing: expected.difference(&found).cloned().collect(),
+        unexpected: found.difference(expected).cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_reports_missing_rows() {
+        let expected = expected_cells(&[ProvingSystem::Risc0], &[BenchTarget::Ecdsa]);
+        assert_eq!(
+            expected.iter().collect::<Vec<_>>(),
+            [&("risc0".to_string(), "ecdsa".to_string(), 32)]
+        );
+
+        let row = |name: &str, target: &str, size: usize| {
+            Metrics::new(
+                name.to_string(),
+                None,
+                true,
+                target.to_string(),
+                size,
+                ProvingSystem::Risc0.properties(),
+            )
+        };
+        let cov = coverage(&expected, &[row("risc0", "sha256", 128)]);
+        assert!(!cov.is_complete());
+        assert_eq!(cov.missing.len(), 1);
+        assert_eq!(cov.unexpected.len(), 1);
+
+        let cov = coverage(&expected, &[row("risc0", "ecdsa", 32)]);
+        assert!(cov.is_complete());
+        assert_eq!((cov.expected, cov.found), (1, 1));
+    }
+
+    #[test]
+    fn test_full_run_without_unbenchmarked_systems_is_complete() {
+        assert!(ProvingSystem::Nexus.validated_properties().is_err());
+        let expected = expected_cells(&[], &[]);
+        assert!(expected.iter().all(|(name, _, _)| name != "nexus"));
+
+        let rows: Vec<Metrics> = expected
+            .iter()
+            .map(|(name, target, size)| {
+                Metrics::new(
+                    name.clone(),
+                    None,
+                    false,
+                    target.clone(),
+                    *size,
+                    ProvingSystem::Risc0.properties(),
+                )
+            })
+            .collect();
+        let cov = coverage(&expected, &rows);
+        assert!(cov.is_complete());
+        assert!(cov.unexpected.is_empty());
+    }
+}
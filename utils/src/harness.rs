@@ -1,13 +1,22 @@
-use std::borrow::Cow;
+use std::panic::AssertUnwindSafe;
+use std::path::Path;
 use std::str::FromStr;
+use std::time::Duration;
 
 use crate::bench::{Metrics, compile_binary, run_measure_mem_script, write_json_metrics};
+use crate::inputs::GeneratedInput;
 use crate::metadata::selected_sha2_inputs;
+use crate::negative::{negative_cases, negative_input};
+pub use crate::properties::{
+    Arithmetization, AuditStatus, BenchProperties, FieldCurve, Iop, Pcs, SetupKind,
+};
+use crate::write_json;
 use criterion::{BatchSize, Criterion};
+use serde::Serialize;
 
 const SAMPLE_SIZE: usize = 10;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BenchTarget {
     Sha256,
     Ecdsa,
@@ -16,6 +25,9 @@ pub enum BenchTarget {
 }
 
 impl BenchTarget {
+    pub const ALL: [BenchTarget; 3] =
+        [BenchTarget::Sha256, BenchTarget::Ecdsa, BenchTarget::Keccak];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             BenchTarget::Sha256 => "sha256",
@@ -52,10 +64,29 @@ pub enum ProvingSystem {
     Miden,
     CairoM,
     Nexus,
+    Barretenberg,
+    Ligetron,
     // Extend as needed
 }
 
 impl ProvingSystem {
+    pub const ALL: [ProvingSystem; 14] = [
+        ProvingSystem::Binius64,
+        ProvingSystem::Expander,
+        ProvingSystem::Plonky2,
+        ProvingSystem::OpenVM,
+        ProvingSystem::Provekit,
+        ProvingSystem::Circom,
+        ProvingSystem::Risc0,
+        ProvingSystem::Sp1,
+        ProvingSystem::Jolt,
+        ProvingSystem::Miden,
+        ProvingSystem::CairoM,
+        ProvingSystem::Nexus,
+        ProvingSystem::Barretenberg,
+        ProvingSystem::Ligetron,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             ProvingSystem::Binius64 => "binius64",
@@ -70,141 +101,67 @@ impl ProvingSystem {
             ProvingSystem::Miden => "miden",
             ProvingSystem::CairoM => "cairo-m",
             ProvingSystem::Nexus => "nexus",
+            ProvingSystem::Barretenberg => "barretenberg",
+            ProvingSystem::Ligetron => "ligetron",
         }
     }
 
     pub fn is_zkvm(&self) -> bool {
         matches!(
             self,
-            ProvingSystem::Risc0
+            ProvingSystem::OpenVM
+                | ProvingSystem::Risc0
                 | ProvingSystem::Sp1
                 | ProvingSystem::Jolt
                 | ProvingSystem::Miden
                 | ProvingSystem::CairoM
                 | ProvingSystem::Nexus
+                | ProvingSystem::Ligetron
         )
     }
-}
-
-#[derive(Clone, Debug)]
-pub struct BenchHarnessConfig<'a> {
-    pub target: BenchTarget,
-    pub system: ProvingSystem,
-    pub feature: Option<&'a str>,
-    pub mem_binary_name: &'a str,
-}
-
-use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub enum AuditStatus {
-    #[serde(rename = "audited")]
-    Audited,
-    #[serde(rename = "not_audited")]
-    NotAudited,
-    #[serde(rename = "partially_audited")]
-    PartiallyAudited,
-}
-
-impl FromStr for AuditStatus {
-    type Err = String;
 
-    fn from_str(s: &str) -> Result<AuditStatus, String> {
-        match s {
-            "audited" => Ok(AuditStatus::Audited),
-            "not_audited" => Ok(AuditStatus::NotAudited),
-            "partially_audited" => Ok(AuditStatus::PartiallyAudited),
-            _ => Err(format!("Invalid audit status: {}", s)),
+    /// Targets benchmarked for this system (see `<system>/benches/` and the
+    /// `<target>_prove.sh` scripts of non-Rust systems).
+    pub fn targets(&self) -> &'static [BenchTarget] {
+        match self {
+            ProvingSystem::Binius64 => &[BenchTarget::Sha256, BenchTarget::Keccak],
+            ProvingSystem::Provekit | ProvingSystem::Risc0 | ProvingSystem::Barretenberg => {
+                &[BenchTarget::Sha256, BenchTarget::Ecdsa]
+            }
+            ProvingSystem::Expander
+            | ProvingSystem::Plonky2
+            | ProvingSystem::OpenVM
+            | ProvingSystem::Circom
+            | ProvingSystem::Sp1
+            | ProvingSystem::Jolt
+            | ProvingSystem::Miden
+            | ProvingSystem::CairoM
+            | ProvingSystem::Nexus
+            | ProvingSystem::Ligetron => &[BenchTarget::Sha256],
         }
     }
 }
 
-#[skip_serializing_none]
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-pub struct BenchProperties {
-    // Classification
-    pub proving_system: Cow<'static, str>,
-    pub field_curve: Cow<'static, str>,
-    pub iop: Cow<'static, str>,
-    pub pcs: Option<Cow<'static, str>>,
-    pub arithm: Cow<'static, str>,
-    pub is_zk: bool,
-
-    // Security
-    pub security_bits: u64,
-    pub is_pq: bool,
-
-    // Maintenance / audit / zk
-    pub is_maintained: bool,
-    pub is_audited: AuditStatus,
-
-    // zkVM specifics
-    pub isa: Option<Cow<'static, str>>,
-}
+impl FromStr for ProvingSystem {
+    type Err = String;
 
-impl BenchProperties {
-    #[allow(clippy::too_many_arguments)]
-    /// Create a new BenchProperties struct.
-    /// # Arguments
-    /// * `proving_system` - The proving system name.
-    /// * `field_curve` - The finite field or curve used by the system.
-    /// * `iop` - The IOP used by the system.
-    /// * `pcs` - The PCS used by the system (if applicable).
-    /// * `arithm` - The arithmetization used by the system.
-    /// * `is_zk` - Whether the system is a zkVM.
-    /// * `security_bits` - The security (soundness) parameter of the system.
-    /// * `is_pq` - Whether the system is post-quantum-sound.
-    /// * `is_maintained` - Whether the system codebase is maintained.
-    /// * `is_audited` - The audit status of the system.
-    /// * `isa` - The instruction set architecture of the system (for zkVMs).
-    pub fn new(
-        proving_system: &'static str,
-        field_curve: &'static str,
-        iop: &'static str,
-        pcs: Option<&'static str>,
-        arithm: &'static str,
-        is_zk: bool,
-        security_bits: u64,
-        is_pq: bool,
-        is_maintained: bool,
-        is_audited: AuditStatus,
-        isa: Option<&'static str>,
-    ) -> Self {
-        // Serde deserialization default implementation does not allow static strings, so we need to convert them to Cow::Borrowed.
-        Self {
-            proving_system: Cow::Borrowed(proving_system),
-            field_curve: Cow::Borrowed(field_curve),
-            iop: Cow::Borrowed(iop),
-            pcs: pcs.map(Cow::Borrowed),
-            arithm: Cow::Borrowed(arithm),
-            is_zk,
-            security_bits,
-            is_pq,
-            is_maintained,
-            is_audited,
-            isa: isa.map(Cow::Borrowed),
-        }
+    fn from_str(s: &str) -> Result<ProvingSystem, String> {
+        ProvingSystem::ALL
+            .into_iter()
+            .find(|system| system.as_str() == s)
+            .ok_or_else(|| format!("Invalid proving system: {}", s))
     }
 }
 
-impl Default for BenchProperties {
-    fn default() -> Self {
-        Self {
-            proving_system: Cow::Borrowed(""),
-            field_curve: Cow::Borrowed(""),
-            iop: Cow::Borrowed(""),
-            pcs: None,
-            arithm: Cow::Borrowed(""),
-            is_zk: false,
-            security_bits: 0,
-            is_pq: false,
-            is_maintained: false,
-            is_audited: AuditStatus::NotAudited,
-            isa: None,
-        }
-    }
+#[derive(Clone, Debug)]
+pub struct BenchHarnessConfig<'a> {
+    pub target: BenchTarget,
+    pub system: ProvingSystem,
+    pub feature: Option<&'a str>,
+    pub mem_binary_name: &'a str,
+    /// Directory the result JSON files are written to; benches use `.`, the
+    /// crate directory.
+    pub output_dir: &'a Path,
 }
 
 fn feat_suffix(feat: Option<&str>) -> String {
@@ -229,11 +186,33 @@ fn bench_id(target: &str, size: usize, system: &str, feat: Option<&str>, which:
     )
 }
 
-fn mem_report_filename(target: &str, size: usize, system: &str, feat: Option<&str>) -> String {
-    match feat {
-        Some(f) if !f.is_empty() => format!("{}_{}_{}_{}_mem_report.json", target, size, system, f),
-        _ => format!("{}_{}_{}_mem_report.json", target, size, system),
+/// Name of a per-benchmark result file, `{target}_{size}_{system}[_{feat}]_{kind}.json`,
+/// e.g. `sha256_128_barretenberg_keccak_sizes.json`. Shared by the Rust
+/// harness and the non-Rust pipeline (`benchmark.sh`, `format_hyperfine`).
+pub fn result_filename(
+    target: &str,
+    size: usize,
+    system: &str,
+    feat: Option<&str>,
+    kind: &str,
+) -> String {
+    format!("{}_{}.json", group_id(target, size, system, feat), kind)
+}
+
+/// Inverse of the `{target}_{size}_{system}[_{feat}]` stem of
+/// [`result_filename`] for a known system and feature. Targets may contain
+/// underscores; stems of other systems or feature variants yield `None`.
+pub fn parse_result_stem(stem: &str, system: &str, feat: Option<&str>) -> Option<(String, usize)> {
+    let rest = stem.strip_suffix(&format!("_{}{}", system, feat_suffix(feat)))?;
+    let (target, size) = rest.rsplit_once('_')?;
+    if target.is_empty() || !size.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
     }
+    Some((target.to_string(), size.parse().ok()?))
+}
+
+fn mem_report_filename(target: &str, size: usize, system: &str, feat: Option<&str>) -> String {
+    result_filename(target, size, system, feat, "mem_report")
 }
 
 fn input_sizes_for(target: BenchTarget) -> Vec<usize> {
@@ -255,10 +234,11 @@ pub fn run_benchmarks_fn<
     PrepSizeFn,
     ProofSizeFn,
     ExecutionCyclesFn: Fn(&PreparedContext) -> u64,
+    ProvingTimeFn: Fn(&Proof) -> Duration,
+    SoundnessFn: FnMut(usize, &GeneratedInput) -> Option<PreparedContext>,
 >(
     c: &mut Criterion,
     cfg: BenchHarnessConfig<'_>,
-    properties: BenchProperties,
     mut prepare: PrepareFn,
     mut num_constraints: NumConstraintsFn,
     mut prove: ProveFn,
@@ -266,6 +246,8 @@ pub fn run_benchmarks_fn<
     mut preprocessing_size: PrepSizeFn,
     mut proof_size: ProofSizeFn,
     execution_cycles: Option<ExecutionCyclesFn>,
+    proving_time: Option<ProvingTimeFn>,
+    soundness: Option<SoundnessFn>,
 ) where
     PrepareFn: FnMut(usize) -> PreparedContext + Copy,
     ProveFn: FnMut(&PreparedContext) -> Proof + Copy,
@@ -276,6 +258,14 @@ pub fn run_benchmarks_fn<
 {
     let target_str = cfg.target.as_str();
     let system_str = cfg.system.as_str();
+    let properties = cfg
+        .system
+        .validated_properties()
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    if soundness_phase(&cfg, soundness, prove, &mut verify) {
+        return;
+    }
 
     for size in input_sizes_for(cfg.target) {
         let prepared_context = prepare(size);
@@ -286,18 +276,35 @@ pub fn run_benchmarks_fn<
         metrics.num_constraints = num_constraints(&prepared_context);
         let proof = prove(&prepared_context);
         metrics.proof_size = proof_size(&proof);
+        metrics.reported_proof_duration = proving_time.as_ref().map(|f| f(&proof));
 
         if let Some(ref cycles_fn) = execution_cycles {
             let c = cycles_fn(&prepared_context);
             metrics.cycles = if c == 0 { None } else { Some(c) };
         }
 
-        write_json_metrics(target_str, size, system_str, cfg.feature, &metrics);
+        write_json_metrics(
+            cfg.output_dir,
+            target_str,
+            size,
+            system_str,
+            cfg.feature,
+            &metrics,
+        );
 
         measure_ram(&cfg, target_str, system_str, cfg.mem_binary_name, size);
 
         let mut group = init_bench_group(c, &cfg, target_str, system_str, size);
 
+        // A variant (e.g. another proof kind) executes the same program as
+        // the untagged benchmark, so only the latter measures execution.
+        if let (Some(cycles_fn), None) = (&execution_cycles, cfg.feature) {
+            let execute_id = bench_id(target_str, size, system_str, cfg.feature, "execute");
+            group.bench_function(execute_id, |bench| {
+                bench.iter(|| cycles_fn(&prepared_context));
+            });
+        }
+
         let prove_id = bench_id(target_str, size, system_str, cfg.feature, "prove");
         group.bench_function(prove_id, move |bench| {
             bench.iter_batched(
@@ -340,10 +347,11 @@ pub fn run_benchmarks_with_state_fn<
     PrepSizeFn,
     ProofSizeFn,
     ExecutionCyclesFn: Fn(&PreparedContext) -> u64,
+    ProvingTimeFn: Fn(&Proof) -> Duration,
+    SoundnessFn: FnMut(usize, &GeneratedInput, SharedState) -> Option<PreparedContext>,
 >(
     c: &mut Criterion,
     cfg: BenchHarnessConfig<'_>,
-    properties: BenchProperties,
     shared: SharedState,
     mut prepare: PrepareFn,
     mut num_constraints: NumConstraintsFn,
@@ -352,6 +360,8 @@ pub fn run_benchmarks_with_state_fn<
     mut preprocessing_size: PrepSizeFn,
     mut proof_size: ProofSizeFn,
     execution_cycles: Option<ExecutionCyclesFn>,
+    proving_time: Option<ProvingTimeFn>,
+    soundness: Option<SoundnessFn>,
 ) where
     PrepareFn: FnMut(usize, SharedState) -> PreparedContext + Copy,
     NumConstraintsFn: FnMut(&PreparedContext, &SharedState) -> usize,
@@ -362,6 +372,21 @@ pub fn run_benchmarks_with_state_fn<
 {
     let target_str = cfg.target.as_str();
     let system_str = cfg.system.as_str();
+    let properties = cfg
+        .system
+        .validated_properties()
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    let soundness =
+        soundness.map(|mut f| move |size, input: &GeneratedInput| f(size, input, shared));
+    if soundness_phase(
+        &cfg,
+        soundness,
+        |prepared: &PreparedContext| prove(prepared, &shared),
+        |prepared: &PreparedContext, proof: &Proof| verify(prepared, proof, &shared),
+    ) {
+        return;
+    }
 
     for size in input_sizes_for(cfg.target) {
         let prepared_context = prepare(size, shared);
@@ -372,18 +397,35 @@ pub fn run_benchmarks_with_state_fn<
         metrics.num_constraints = num_constraints(&prepared_context, &shared);
         let proof = prove(&prepared_context, &shared);
         metrics.proof_size = proof_size(&proof, &shared);
+        metrics.reported_proof_duration = proving_time.as_ref().map(|f| f(&proof));
 
         if let Some(ref cycles_fn) = execution_cycles {
             let c = cycles_fn(&prepared_context);
             metrics.cycles = if c == 0 { None } else { Some(c) };
         }
 
-        write_json_metrics(target_str, size, system_str, cfg.feature, &metrics);
+        write_json_metrics(
+            cfg.output_dir,
+            target_str,
+            size,
+            system_str,
+            cfg.feature,
+            &metrics,
+        );
 
         measure_ram(&cfg, target_str, system_str, cfg.mem_binary_name, size);
 
         let mut group = init_bench_group(c, &cfg, target_str, system_str, size);
 
+        // A variant (e.g. another proof kind) executes the same program as
+        // the untagged benchmark, so only the latter measures execution.
+        if let (Some(cycles_fn), None) = (&execution_cycles, cfg.feature) {
+            let execute_id = bench_id(target_str, size, system_str, cfg.feature, "execute");
+            group.bench_function(execute_id, |bench| {
+                bench.iter(|| cycles_fn(&prepared_context));
+            });
+        }
+
         let prove_id = bench_id(target_str, size, system_str, cfg.feature, "prove");
         group.bench_function(prove_id, move |bench| {
             bench.iter_batched(
@@ -456,15 +498,176 @@ fn measure_ram(
 ) {
     compile_binary(mem_bin_name_ref);
     let bin_path = format!("../target/release/{}", mem_bin_name_ref);
-    let mem_json = mem_report_filename(target_str, size, system_str, cfg.feature);
-    run_measure_mem_script(&mem_json, &bin_path, size);
+    let mem_json = cfg.output_dir.join(mem_report_filename(
+        target_str,
+        size,
+        system_str,
+        cfg.feature,
+    ));
+    run_measure_mem_script(&mem_json.to_string_lossy(), &bin_path, size);
+}
+
+/// What a system did with an invalid instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundnessOutcome {
+    /// Preparing or proving the instance failed.
+    ProveRefused,
+    /// A proof was produced and verification rejected it.
+    VerifyRejected,
+    /// A proof was produced and verified: the circuit is under-constrained.
+    Accepted,
+    /// The system cannot express the instance (e.g. the digest is not an input).
+    Skipped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct SoundnessResult {
+    pub case: String,
+    pub outcome: SoundnessOutcome,
+}
+
+/// Runs `f`, turning a panic into `None`. The panic message is still printed
+/// by the process-wide hook, which is left alone.
+fn catch_panic<T>(f: impl FnOnce() -> T) -> Option<T> {
+    std::panic::catch_unwind(AssertUnwindSafe(f)).ok()
+}
+
+/// Whether and how `define_benchmark_harness!` benches run the soundness
+/// phase, from `BENCH_SOUNDNESS`: unset (off), `1` (before the benchmarks) or
+/// `only` (instead of them).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoundnessMode {
+    Off,
+    Before,
+    Only,
+}
+
+impl SoundnessMode {
+    pub fn from_env() -> SoundnessMode {
+        match std::env::var("BENCH_SOUNDNESS").ok().as_deref() {
+            None | Some("") | Some("0") => SoundnessMode::Off,
+            Some("only") => SoundnessMode::Only,
+            Some(_) => SoundnessMode::Before,
+        }
+    }
+}
+
+/// Runs the soundness phase if `BENCH_SOUNDNESS` asks for it and the bench
+/// passed a `soundness` prepare function. Returns whether the benchmarks are
+/// to be skipped.
+fn soundness_phase<PreparedContext, Proof>(
+    cfg: &BenchHarnessConfig<'_>,
+    prepare_input: Option<impl FnMut(usize, &GeneratedInput) -> Option<PreparedContext>>,
+    prove: impl FnMut(&PreparedContext) -> Proof,
+    verify: impl FnMut(&PreparedContext, &Proof),
+) -> bool {
+    let mode = SoundnessMode::from_env();
+    if mode == SoundnessMode::Off {
+        return false;
+    }
+    match prepare_input {
+        Some(prepare_input) => {
+            run_soundness_fn(cfg.clone(), prepare_input, prove, verify);
+        }
+        None => println!(
+            "soundness {} {}: no soundness phase defined",
+            cfg.target.as_str(),
+            cfg.system.as_str()
+        ),
+    }
+    mode == SoundnessMode::Only
+}
+
+/// Soundness phase: feeds every negative instance of the target (see
+/// `negative`) at the smallest input size through `prepare_input`, `prove`
+/// and `verify`, which signal a failure by panicking like the benchmark
+/// closures do. `prepare_input` returns `None` for an instance the system
+/// cannot express. Writes `{target}_{size}_{system}[_{feat}]_soundness.json`
+/// to `cfg.output_dir` and panics if any instance was accepted.
+pub fn run_soundness_fn<PreparedContext, Proof, PrepareFn, ProveFn, VerifyFn>(
+    cfg: BenchHarnessConfig<'_>,
+    mut prepare_input: PrepareFn,
+    mut prove: ProveFn,
+    mut verify: VerifyFn,
+) -> Vec<SoundnessResult>
+where
+    PrepareFn: FnMut(usize, &GeneratedInput) -> Option<PreparedContext>,
+    ProveFn: FnMut(&PreparedContext) -> Proof,
+    VerifyFn: FnMut(&PreparedContext, &Proof),
+{
+    let target_str = cfg.target.as_str();
+    let system_str = cfg.system.as_str();
+    let size = input_sizes_for(cfg.target).into_iter().min().unwrap_or(0);
+
+    let mut results = Vec::new();
+    for case in negative_cases(cfg.target) {
+        let outcome = match negative_input(cfg.target, size, case) {
+            None => SoundnessOutcome::Skipped,
+            Some(input) => match catch_panic(|| prepare_input(size, &input)) {
+                Some(None) => SoundnessOutcome::Skipped,
+                None => SoundnessOutcome::ProveRefused,
+                Some(Some(prepared)) => match catch_panic(|| prove(&prepared)) {
+                    None => SoundnessOutcome::ProveRefused,
+                    Some(proof) => match catch_panic(|| verify(&prepared, &proof)) {
+                        None => SoundnessOutcome::VerifyRejected,
+                        Some(()) => SoundnessOutcome::Accepted,
+                    },
+                },
+            },
+        };
+        println!("soundness {target_str} {system_str} {case}: {outcome:?}");
+        results.push(SoundnessResult {
+            case: case.to_string(),
+            outcome,
+        });
+    }
+
+    let file = result_filename(target_str, size, system_str, cfg.feature, "soundness");
+    write_json(&results, &cfg.output_dir.join(file).to_string_lossy());
+
+    let accepted: Vec<&str> = results
+        .iter()
+        .filter(|r| r.outcome == SoundnessOutcome::Accepted)
+        .map(|r| r.case.as_str())
+        .collect();
+    if !accepted.is_empty() {
+        panic!(
+            "{system_str} accepted invalid {target_str} instances: {}",
+            accepted.join(", ")
+        );
+    }
+    results
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __optional_proving_time {
+    () => {
+        None::<fn(&_) -> ::std::time::Duration>
+    };
+    ($proving_time:expr) => {
+        Some($proving_time)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __optional_soundness {
+    (($($shared:ty)?)) => {
+        None::<fn(usize, &$crate::inputs::GeneratedInput $(, $shared)?) -> Option<_>>
+    };
+    (($($shared:ty)?) $soundness:expr) => {
+        Some($soundness)
+    };
 }
 
 #[macro_export]
 macro_rules! __define_benchmark_harness {
     // With shared state
-    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, $properties:expr, { $($shared_init:tt)* },
+    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, { $($shared_init:tt)* },
         $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr
+        $(; soundness = $soundness:expr)?
     ) => {
         fn criterion_benchmarks(c: &mut ::criterion::Criterion) {
             let system = $system;
@@ -473,11 +676,11 @@ macro_rules! __define_benchmark_harness {
                 system,
                 feature: $feature,
                 mem_binary_name: $mem_binary_name,
+                output_dir: ::std::path::Path::new("."),
             };
             ::utils::harness::run_benchmarks_with_state_fn(
                 c,
                 cfg,
-                $properties,
                 &{ $($shared_init)* },
                 $prepare,
                 $num_constraints,
@@ -486,14 +689,17 @@ macro_rules! __define_benchmark_harness {
                 $prep_size,
                 $proof_size,
                 None::<fn(&_) -> u64>,
+                $crate::__optional_proving_time!(),
+                $crate::__optional_soundness!((_) $($soundness)?),
             );
         }
         ::criterion::criterion_group!($public_group_ident, criterion_benchmarks);
         ::criterion::criterion_main!($public_group_ident);
     };
-    // No shared state, with execution_cycles
-    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, $properties:expr,
-        $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr, $execution_cycles:expr
+    // With shared state and execution_cycles
+    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, { $($shared_init:tt)* },
+        $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr, $execution_cycles:expr $(, $proving_time:expr)?
+        $(; soundness = $soundness:expr)?
     ) => {
         fn criterion_benchmarks(c: &mut ::criterion::Criterion) {
             let system = $system;
@@ -502,11 +708,12 @@ macro_rules! __define_benchmark_harness {
                 system,
                 feature: $feature,
                 mem_binary_name: $mem_binary_name,
+                output_dir: ::std::path::Path::new("."),
             };
-            ::utils::harness::run_benchmarks_fn(
+            ::utils::harness::run_benchmarks_with_state_fn(
                 c,
                 cfg,
-                $properties,
+                &{ $($shared_init)* },
                 $prepare,
                 $num_constraints,
                 $prove,
@@ -514,14 +721,17 @@ macro_rules! __define_benchmark_harness {
                 $prep_size,
                 $proof_size,
                 Some($execution_cycles),
+                $crate::__optional_proving_time!($($proving_time)?),
+                $crate::__optional_soundness!((_) $($soundness)?),
             );
         }
         ::criterion::criterion_group!($public_group_ident, criterion_benchmarks);
         ::criterion::criterion_main!($public_group_ident);
     };
-    // With shared state and execution_cycles
-    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, $properties:expr, { $($shared_init:tt)* },
-        $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr, $execution_cycles:expr
+    // No shared state, with execution_cycles
+    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr,
+        $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr, $execution_cycles:expr $(, $proving_time:expr)?
+        $(; soundness = $soundness:expr)?
     ) => {
         fn criterion_benchmarks(c: &mut ::criterion::Criterion) {
             let system = $system;
@@ -530,12 +740,11 @@ macro_rules! __define_benchmark_harness {
                 system,
                 feature: $feature,
                 mem_binary_name: $mem_binary_name,
+                output_dir: ::std::path::Path::new("."),
             };
-            ::utils::harness::run_benchmarks_with_state_fn(
+            ::utils::harness::run_benchmarks_fn(
                 c,
                 cfg,
-                $properties,
-                &{ $($shared_init)* },
                 $prepare,
                 $num_constraints,
                 $prove,
@@ -543,14 +752,17 @@ macro_rules! __define_benchmark_harness {
                 $prep_size,
                 $proof_size,
                 Some($execution_cycles),
+                $crate::__optional_proving_time!($($proving_time)?),
+                $crate::__optional_soundness!(() $($soundness)?),
             );
         }
         ::criterion::criterion_group!($public_group_ident, criterion_benchmarks);
         ::criterion::criterion_main!($public_group_ident);
     };
     // No shared state, no execution_cycles
-    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr, $properties:expr,
+    ($public_group_ident:ident, $target:expr, $system:expr, $feature:expr, $mem_binary_name:expr,
         $prepare:expr, $num_constraints:expr, $prove:expr, $verify:expr, $prep_size:expr, $proof_size:expr
+        $(; soundness = $soundness:expr)?
     ) => {
         fn criterion_benchmarks(c: &mut ::criterion::Criterion) {
             let system = $system;
@@ -559,11 +771,11 @@ macro_rules! __define_benchmark_harness {
                 system,
                 feature: $feature,
                 mem_binary_name: $mem_binary_name,
+                output_dir: ::std::path::Path::new("."),
             };
             ::utils::harness::run_benchmarks_fn(
                 c,
                 cfg,
-                $properties,
                 $prepare,
                 $num_constraints,
                 $prove,
@@ -571,6 +783,8 @@ macro_rules! __define_benchmark_harness {
                 $prep_size,
                 $proof_size,
                 None::<fn(&_) -> u64>,
+                $crate::__optional_proving_time!(),
+                $crate::__optional_soundness!(() $($soundness)?),
             );
         }
         ::criterion::criterion_group!($public_group_ident, criterion_benchmarks);
@@ -590,3 +804,71 @@ macro_rules! define_benchmark_harness {
         $crate::__define_benchmark_harness!(keccak, $crate::harness::BenchTarget::Keccak, $($rest)*);
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sha2::Digest;
+
+    #[test]
+    fn test_result_stem_round_trip() {
+        let name = result_filename("sha256", 128, "barretenberg", Some("keccak"), "sizes");
+        assert_eq!(name, "sha256_128_barretenberg_keccak_sizes.json");
+
+        let stem = group_id("poseidon_bn254", 64, "barretenberg", Some("keccak"));
+        assert_eq!(
+            parse_result_stem(&stem, "barretenberg", Some("keccak")),
+            Some(("poseidon_bn254".to_string(), 64))
+        );
+        // Other variants of the same system are not picked up.
+        assert_eq!(parse_result_stem(&stem, "barretenberg", None), None);
+        assert_eq!(
+            parse_result_stem("sha256_128_barretenberg", "barretenberg", Some("")),
+            Some(("sha256".to_string(), 128))
+        );
+    }
+
+    #[test]
+    fn test_soundness_phase() {
+        let dir = std::env::temp_dir().join(format!("utils_soundness_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let cfg = || BenchHarnessConfig {
+            target: BenchTarget::Sha256,
+            system: ProvingSystem::Provekit,
+            feature: None,
+            mem_binary_name: "",
+            output_dir: &dir,
+        };
+        // A circuit that checks the digest but ignores `input_len`.
+        let prepare = |_: usize, input: &GeneratedInput| Some(input.clone());
+        let prove = |input: &GeneratedInput| {
+            let digest = sha2::Sha256::digest(input.bytes("input").unwrap());
+            assert_eq!(&digest[..], input.bytes("digest").unwrap());
+        };
+        let panics =
+            std::panic::catch_unwind(|| run_soundness_fn(cfg(), prepare, prove, |_, _| {}));
+        assert!(panics.is_err());
+
+        let file = dir.join(result_filename(
+            "sha256",
+            128,
+            "provekit",
+            None,
+            "soundness",
+        ));
+        let results: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(results[0]["outcome"], "prove_refused");
+        assert_eq!(results[1]["outcome"], "accepted");
+
+        // Skipping everything is not a failure.
+        let results = run_soundness_fn(cfg(), |_, _| None::<()>, |_| (), |_, _| {});
+        assert!(
+            results
+                .iter()
+                .all(|r| r.outcome == SoundnessOutcome::Skipped)
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,286 @@
+//! Local store of benchmark results across runs.
+//!
+//! The store is an append-only JSONL file (by default `results/history.jsonl`)
+//! with one line per metrics entry, tagged with the run id, the run date and
+//! the environment it ran on. Entries are upgraded to the current metrics
+//! schema on load, so old runs stay readable as the format evolves.
+
+use crate::bench::Metrics;
+use crate::schema::{load_collected, metrics_from_value};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Default location of the store, relative to the repository root.
+pub const DEFAULT_STORE: &str = "results/history.jsonl";
+
+/// A metrics entry of one run.
+#[derive(Serialize, Clone)]
+pub struct HistoryRecord {
+    pub run_id: String,
+    /// Date of the run, `YYYY-MM-DD`.
+    pub date: String,
+    /// Machine or CI runner the run was measured on.
+    pub environment: String,
+    #[serde(flatten)]
+    pub metrics: Metrics,
+}
+
+impl HistoryRecord {
+    /// Calendar quarter of the run, e.g. `2025-Q4`.
+    pub fn quarter(&self) -> String {
+        let month: u32 = self
+            .date
+            .get(5..7)
+            .and_then(|m| m.parse().ok())
+            .unwrap_or(1);
+        format!("{}-Q{}", &self.date[..4], (month - 1) / 3 + 1)
+    }
+}
+
+/// Loads all records of the store. A missing store is empty.
+pub fn load_history(store: &Path) -> io::Result<Vec<HistoryRecord>> {
+    if !store.exists() {
+        return Ok(Vec::new());
+    }
+    let mut records = Vec::new();
+    for (i, line) in BufReader::new(fs::File::open(store)?).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(&line)?;
+        records.push(
+            record_from_value(value)
+                .map_err(|e| io::Error::other(format!("{}:{}: {e}", store.display(), i + 1)))?,
+        );
+    }
+    Ok(records)
+}
+
+fn record_from_value(mut value: Value) -> io::Result<HistoryRecord> {
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| io::Error::other("history entry is not a JSON object"))?;
+    let mut take = |key: &str| match obj.remove(key) {
+        Some(Value::String(s)) => Ok(s),
+        _ => Err(io::Error::other(format!("history entry lacks `{key}`"))),
+    };
+    let (run_id, date, environment) = (take("run_id")?, take("date")?, take("environment")?);
+    if !is_iso_date(&date) {
+        return Err(io::Error::other(format!("invalid date `{date}`")));
+    }
+    Ok(HistoryRecord {
+        run_id,
+        date,
+        environment,
+        metrics: metrics_from_value(value)?,
+    })
+}
+
+fn is_iso_date(date: &str) -> bool {
+    let b = date.as_bytes();
+    b.len() == 10
+        && b[4] == b'-'
+        && b[7] == b'-'
+        && b.iter()
+            .enumerate()
+            .all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit())
+}
+
+/// Run id of a `collected_benchmarks_<run_id>.json` file name.
+pub fn run_id_from_path(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+    let id = stem.strip_prefix("collected_benchmarks_")?;
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+/// Appends all entries of a collected benchmarks file to the store as run
+/// `run_id`. Importing a run id that is already in the store is an error.
+///
+/// Returns the number of imported entries.
+pub fn import_collected(
+    store: &Path,
+    collected: &Path,
+    run_id: &str,
+    date: &str,
+    environment: &str,
+) -> io::Result<usize> {
+    if !is_iso_date(date) {
+        return Err(io::Error::other(format!(
+            "invalid date `{date}`, expected YYYY-MM-DD"
+        )));
+    }
+    if load_history(store)?.iter().any(|r| r.run_id == run_id) {
+        return Err(io::Error::other(format!(
+            "run {run_id} is already in {}",
+            store.display()
+        )));
+    }
+
+    let metrics = load_collected(collected)?;
+    if let Some(parent) = store.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(store)?;
+    for metrics in &metrics {
+        let record = HistoryRecord {
+            run_id: run_id.to_string(),
+            date: date.to_string(),
+            environment: environment.to_string(),
+            metrics: metrics.clone(),
+        };
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    }
+    Ok(metrics.len())
+}
+
+/// Today's date (UTC) as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        / 86_400;
+    civil_date(days as i64)
+}
+
+/// Converts days since 1970-01-01 to a `YYYY-MM-DD` date (proleptic
+/// Gregorian calendar).
+fn civil_date(days: i64) -> String {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Summary of one benchmark over the runs of a quarter.
+pub struct QuarterSummary {
+    pub quarter: String,
+    pub input_size: usize,
+    pub runs: usize,
+    /// Median over the runs of the quarter.
+    pub proof_duration: Duration,
+    pub peak_memory: usize,
+    pub proof_size: usize,
+    /// Prover versions recorded for the runs (`BenchProperties::proof_system_version`).
+    pub versions: BTreeSet<String>,
+    pub environments: BTreeSet<String>,
+}
+
+/// Groups the records of (`system`, `target`, optionally `input_size`) by
+/// input size and quarter, in chronological order.
+pub fn quarterly_history(
+    records: &[HistoryRecord],
+    system: &str,
+    target: &str,
+    input_size: Option<usize>,
+) -> Vec<QuarterSummary> {
+    let mut groups: BTreeMap<(usize, String), Vec<&HistoryRecord>> = BTreeMap::new();
+    for r in records {
+        let m = &r.metrics;
+        if m.name == system && m.target == target && input_size.is_none_or(|s| s == m.input_size) {
+            groups
+                .entry((m.input_size, r.quarter()))
+                .or_default()
+                .push(r);
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|((input_size, quarter), rs)| QuarterSummary {
+            quarter,
+            input_size,
+            runs: rs.len(),
+            proof_duration: median(rs.iter().map(|r| r.metrics.proof_duration)),
+            peak_memory: median(rs.iter().map(|r| r.metrics.peak_memory)),
+            proof_size: median(rs.iter().map(|r| r.metrics.proof_size)),
+            versions: rs
+                .iter()
+                .map(|r| {
+                    r.metrics
+                        .bench_properties
+                        .proof_system_version
+                        .as_deref()
+                        .unwrap_or("unknown")
+                        .to_string()
+                })
+                .collect(),
+            environments: rs.iter().map(|r| r.environment.clone()).collect(),
+        })
+        .collect()
+}
+
+/// Lower median of a non-empty set of values.
+fn median<T: Ord + Copy>(values: impl Iterator<Item = T>) -> T {
+    let mut values: Vec<T> = values.collect();
+    values.sort_unstable();
+    values[(values.len() - 1) / 2]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    fn collected_file(dir: &Path, name: &str, prove_ms: u64, version: &str) -> std::path::PathBuf {
+        let mut props = ProvingSystem::Sp1.properties();
+        props.proof_system_version = Some(version.to_string().into());
+        let mut m = Metrics::new(
+            "sp1".to_string(),
+            None,
+            true,
+            "sha256".to_string(),
+            128,
+            props,
+        );
+        m.proof_duration = Duration::from_millis(prove_ms);
+        let path = dir.join(name);
+        fs::write(&path, serde_json::to_string(&[m]).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_import_and_quarterly_history() {
+        let dir = std::env::temp_dir().join(format!("utils_history_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let store = dir.join("history.jsonl");
+
+        let a = collected_file(&dir, "collected_benchmarks_1.json", 1000, "v1");
+        let b = collected_file(&dir, "collected_benchmarks_2.json", 800, "v2");
+        assert_eq!(run_id_from_path(&a).as_deref(), Some("1"));
+        import_collected(&store, &a, "1", "2025-11-20", "ci").unwrap();
+        import_collected(&store, &b, "2", "2026-02-03", "ci").unwrap();
+        assert!(import_collected(&store, &b, "2", "2026-02-03", "ci").is_err());
+
+        let records = load_history(&store).unwrap();
+        let history = quarterly_history(&records, "sp1", "sha256", Some(128));
+        let quarters: Vec<_> = history.iter().map(|q| q.quarter.as_str()).collect();
+        assert_eq!(quarters, ["2025-Q4", "2026-Q1"]);
+        assert_eq!(history[1].proof_duration, Duration::from_millis(800));
+        assert!(history[1].versions.contains("v2"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(20_515), "2026-03-03");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+    }
+}
@@ -0,0 +1,342 @@
+//! Encodings of the generated benchmark inputs in the formats the benchmarked
+//! systems read, so prepare scripts and crates do not convert hex by hand.
+
+use crate::{
+    generate_ecdsa_input, generate_ecdsa_k256_input, generate_keccak_input, generate_sha256_input,
+};
+use hex::ToHex;
+use serde_json::{Map, Value, json};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum InputFormat {
+    /// Hex-encoded byte fields, one per line (integer fields are skipped)
+    #[default]
+    Hex,
+    /// Noir `Prover.toml`
+    Toml,
+    /// circom input JSON: an object of decimal string arrays
+    Circom,
+    /// The `args` array of a ligetron prover config
+    Ligetron,
+    /// Raw bytes of the byte fields, concatenated (integer fields are skipped)
+    Binary,
+    /// Space-separated decimal bytes, one field per line
+    Decimal,
+}
+
+impl InputFormat {
+    pub const ALL: [InputFormat; 6] = [
+        InputFormat::Hex,
+        InputFormat::Toml,
+        InputFormat::Circom,
+        InputFormat::Ligetron,
+        InputFormat::Binary,
+        InputFormat::Decimal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InputFormat::Hex => "hex",
+            InputFormat::Toml => "toml",
+            InputFormat::Circom => "circom",
+            InputFormat::Ligetron => "ligetron",
+            InputFormat::Binary => "binary",
+            InputFormat::Decimal => "decimal",
+        }
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for InputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<InputFormat, String> {
+        InputFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str() == s)
+            .ok_or_else(|| format!("Invalid input format: {}", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldValue {
+    Bytes(Vec<u8>),
+    Int(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputField {
+    pub name: String,
+    pub value: FieldValue,
+}
+
+/// The named fields of a generated input. Field names follow the circuits in
+/// this repo; `select` picks and renames them for other circuits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeneratedInput {
+    pub fields: Vec<InputField>,
+}
+
+impl GeneratedInput {
+    fn new(fields: Vec<(&str, FieldValue)>) -> Self {
+        let fields = fields
+            .into_iter()
+            .map(|(name, value)| InputField {
+                name: name.to_string(),
+                value,
+            })
+            .collect();
+        GeneratedInput { fields }
+    }
+
+    pub(crate) fn hash(input: Vec<u8>, digest: Vec<u8>) -> Self {
+        let len = input.len();
+        GeneratedInput::new(vec![
+            ("input", FieldValue::Bytes(input)),
+            ("input_len", FieldValue::Int(len)),
+            ("digest", FieldValue::Bytes(digest)),
+        ])
+    }
+
+    pub(crate) fn ecdsa(digest: Vec<u8>, x: Vec<u8>, y: Vec<u8>, signature: Vec<u8>) -> Self {
+        GeneratedInput::new(vec![
+            ("message_hash", FieldValue::Bytes(digest)),
+            ("public_key_x", FieldValue::Bytes(x)),
+            ("public_key_y", FieldValue::Bytes(y)),
+            ("signature", FieldValue::Bytes(signature)),
+        ])
+    }
+
+    /// `input`, `input_len` and `digest` of `generate_sha256_input`.
+    pub fn sha256(size: usize) -> Self {
+        let (input, digest) = generate_sha256_input(size);
+        GeneratedInput::hash(input, digest)
+    }
+
+    /// `input`, `input_len` and `digest` of `generate_keccak_input`.
+    pub fn keccak(size: usize) -> Self {
+        let (input, digest) = generate_keccak_input(size);
+        GeneratedInput::hash(input, digest)
+    }
+
+    /// `message_hash`, `public_key_x`, `public_key_y` and `signature` of
+    /// `generate_ecdsa_input` (secp256r1).
+    pub fn ecdsa_p256() -> Self {
+        let (digest, (x, y), signature) = generate_ecdsa_input();
+        GeneratedInput::ecdsa(digest, x, y, signature)
+    }
+
+    /// Same fields as `ecdsa_p256`, from `generate_ecdsa_k256_input`.
+    pub fn ecdsa_k256() -> Self {
+        let (digest, (x, y), signature) = generate_ecdsa_k256_input();
+        GeneratedInput::ecdsa(digest, x, y, signature)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| &f.value)
+    }
+
+    /// The bytes of a byte field.
+    pub fn bytes(&self, name: &str) -> Option<&[u8]> {
+        match self.field(name)? {
+            FieldValue::Bytes(bytes) => Some(bytes),
+            FieldValue::Int(_) => None,
+        }
+    }
+
+    /// The value of an integer field.
+    pub fn int(&self, name: &str) -> Option<usize> {
+        match self.field(name)? {
+            FieldValue::Int(value) => Some(*value),
+            FieldValue::Bytes(_) => None,
+        }
+    }
+
+    /// `input` and `digest` of a hash input, for circuits that hash a fixed
+    /// length message. `None` if `input_len` is not the length of `input`,
+    /// which such a circuit cannot express.
+    pub fn fixed_length_hash(&self) -> Option<(&[u8], &[u8])> {
+        let message = self.bytes("input")?;
+        (self.int("input_len")? == message.len()).then_some((message, self.bytes("digest")?))
+    }
+
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut FieldValue> {
+        self.fields
+            .iter_mut()
+            .find(|f| f.name == name)
+            .map(|f| &mut f.value)
+    }
+
+    /// Keeps the fields of `spec`, in its order. An entry is a field name,
+    /// optionally renamed with `name:new_name`. An empty spec keeps all fields.
+    pub fn select<S: AsRef<str>>(&self, spec: &[S]) -> Result<Self, String> {
+        if spec.is_empty() {
+            return Ok(self.clone());
+        }
+        let fields = spec
+            .iter()
+            .map(|entry| {
+                let entry = entry.as_ref();
+                let (name, rename) = entry.split_once(':').unwrap_or((entry, entry));
+                let field = self.fields.iter().find(|f| f.name == name).ok_or_else(|| {
+                    let names: Vec<&str> = self.fields.iter().map(|f| f.name.as_str()).collect();
+                    format!("unknown field {name} (fields: {})", names.join(", "))
+                })?;
+                Ok(InputField {
+                    name: rename.to_string(),
+                    value: field.value.clone(),
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(GeneratedInput { fields })
+    }
+
+    /// Encodes the fields. Text formats end with a newline.
+    pub fn render(&self, format: InputFormat) -> Vec<u8> {
+        let decimals = |bytes: &[u8], sep: &str| {
+            bytes
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<_>>()
+                .join(sep)
+        };
+        let lines = |line: &dyn Fn(&InputField) -> Option<String>| {
+            self.fields
+                .iter()
+                .filter_map(line)
+                .map(|l| l + "\n")
+                .collect::<String>()
+                .into_bytes()
+        };
+        match format {
+            InputFormat::Hex => lines(&|f| match &f.value {
+                FieldValue::Bytes(b) => Some(b.encode_hex::<String>()),
+                FieldValue::Int(_) => None,
+            }),
+            InputFormat::Decimal => lines(&|f| {
+                Some(match &f.value {
+                    FieldValue::Bytes(b) => decimals(b, " "),
+                    FieldValue::Int(n) => n.to_string(),
+                })
+            }),
+            InputFormat::Toml => lines(&|f| {
+                Some(match &f.value {
+                    FieldValue::Bytes(b) => format!("{} = [{}]", f.name, decimals(b, ", ")),
+                    FieldValue::Int(n) => format!("{} = {n}", f.name),
+                })
+            }),
+            InputFormat::Circom => {
+                let object: Map<String, Value> = self
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let value = match &f.value {
+                            FieldValue::Bytes(b) => {
+                                json!(b.iter().map(u8::to_string).collect::<Vec<_>>())
+                            }
+                            FieldValue::Int(n) => json!(n.to_string()),
+                        };
+                        (f.name.clone(), value)
+                    })
+                    .collect();
+                (Value::Object(object).to_string() + "\n").into_bytes()
+            }
+            InputFormat::Ligetron => {
+                let args: Vec<Value> = self
+                    .fields
+                    .iter()
+                    .map(|f| match &f.value {
+                        FieldValue::Bytes(b) => {
+                            json!({ "hex": format!("0x{}", b.encode_hex::<String>()) })
+                        }
+                        FieldValue::Int(n) => json!({ "i64": n }),
+                    })
+                    .collect();
+                (Value::Array(args).to_string() + "\n").into_bytes()
+            }
+            InputFormat::Binary => self
+                .fields
+                .iter()
+                .flat_map(|f| match &f.value {
+                    FieldValue::Bytes(b) => b.clone(),
+                    FieldValue::Int(_) => Vec::new(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_formats() {
+        let input = GeneratedInput::new(vec![
+            ("input", FieldValue::Bytes(vec![1, 255])),
+            ("input_len", FieldValue::Int(2)),
+        ]);
+        let text = |format| String::from_utf8(input.render(format)).unwrap();
+        assert_eq!(text(InputFormat::Hex), "01ff\n");
+        assert_eq!(text(InputFormat::Decimal), "1 255\n2\n");
+        assert_eq!(text(InputFormat::Toml), "input = [1, 255]\ninput_len = 2\n");
+        assert_eq!(
+            text(InputFormat::Circom),
+            r#"{"input":["1","255"],"input_len":"2"}"#.to_string() + "\n"
+        );
+        assert_eq!(
+            text(InputFormat::Ligetron),
+            r#"[{"hex":"0x01ff"},{"i64":2}]"#.to_string() + "\n"
+        );
+        assert_eq!(input.render(InputFormat::Binary), vec![1, 255]);
+    }
+
+    #[test]
+    fn test_select() {
+        let input = GeneratedInput::sha256(128);
+        let (message, digest) = generate_sha256_input(128);
+        let selected = input.select(&["digest:hash", "input:in"]).unwrap();
+        assert_eq!(
+            selected.fields,
+            vec![
+                InputField {
+                    name: "hash".to_string(),
+                    value: FieldValue::Bytes(digest),
+                },
+                InputField {
+                    name: "in".to_string(),
+                    value: FieldValue::Bytes(message),
+                },
+            ]
+        );
+        assert!(
+            input
+                .select(&["nope"])
+                .unwrap_err()
+                .contains("unknown field nope")
+        );
+        // The default hex rendering is the historic `utils sha256` output.
+        let hex = String::from_utf8(input.render(InputFormat::Hex)).unwrap();
+        assert_eq!(hex.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_fixed_length_hash() {
+        let mut input = GeneratedInput::sha256(128);
+        let (message, digest) = generate_sha256_input(128);
+        assert_eq!(input.fixed_length_hash(), Some((&message[..], &digest[..])));
+        *input.field_mut("input_len").unwrap() = FieldValue::Int(127);
+        assert_eq!(input.int("input_len"), Some(127));
+        assert_eq!(input.fixed_length_hash(), None);
+    }
+}
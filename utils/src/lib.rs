@@ -6,9 +6,25 @@ use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
+pub mod analyze;
 pub mod bench;
+pub mod cache;
+pub mod compare;
+pub mod coverage;
 pub mod harness;
+pub mod history;
+pub mod inputs;
+pub mod lint;
 pub mod metadata;
+pub mod negative;
+pub mod orchestrate;
+pub mod properties;
+pub mod publish;
+pub mod report;
+pub mod sanity;
+pub mod schema;
+pub mod select;
+pub mod vectors;
 pub mod zkvm;
 
 use k256::ecdsa::{Signature as K256Signature, SigningKey as K256SigningKey};
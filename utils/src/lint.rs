@@ -0,0 +1,319 @@
+//! Static checks of a script-based system directory against the contract of
+//! `benchmark.sh` / `utils orchestrate` (see `orchestrate`), so a new system
+//! fails fast instead of at the end of a benchmark run.
+
+use crate::harness::{BenchProperties, BenchTarget, ProvingSystem};
+use crate::input_sizes_for;
+use crate::orchestrate::{read_num_constraints_json, run_script};
+use crate::properties::{resolve_is_zkvm, resolve_properties};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    /// The system cannot be benchmarked (or published) as is.
+    Error,
+    /// A result will be incomplete, e.g. sizes are not measured.
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Issue {
+    pub level: Level,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        };
+        write!(f, "{level}: {}", self.message)
+    }
+}
+
+/// What to lint.
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+    pub system_dir: PathBuf,
+    /// Proving system name (defaults to the directory name).
+    pub name: Option<String>,
+    /// Whether an unregistered system is a zkVM; see `resolve_is_zkvm`.
+    pub is_zkvm: bool,
+    /// Run `{target}_prepare.sh` for the smallest input size of every target
+    /// with this binary as `UTILS_BIN`.
+    pub dry_run_prepare: Option<PathBuf>,
+}
+
+const REQUIRED_SCRIPTS: [&str; 3] = ["prepare", "prove", "verify"];
+const OPTIONAL_SCRIPTS: [&str; 3] = ["measure", "prove_for_verify", "witness"];
+
+fn is_executable(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+/// Targets the directory has scripts for.
+fn targets_with_scripts(dir: &Path) -> Vec<BenchTarget> {
+    BenchTarget::ALL
+        .into_iter()
+        .filter(|t| {
+            REQUIRED_SCRIPTS
+                .iter()
+                .chain(&OPTIONAL_SCRIPTS)
+                .any(|step| dir.join(format!("{}_{step}.sh", t.as_str())).exists())
+        })
+        .collect()
+}
+
+/// Runs all checks. Issues are ordered by level, errors first.
+pub fn lint_system(cfg: &LintConfig) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut error = |message: String| {
+        issues.push(Issue {
+            level: Level::Error,
+            message,
+        })
+    };
+    let dir = &cfg.system_dir;
+    if !dir.is_dir() {
+        error(format!("{} is not a directory", dir.display()));
+        return issues;
+    }
+    let name = cfg.name.clone().unwrap_or_else(|| {
+        fs::canonicalize(dir)
+            .ok()
+            .and_then(|d| d.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_default()
+    });
+
+    // bench_props.json: optional for registered systems, which publish the
+    // registry's properties.
+    let props_file = dir.join("bench_props.json");
+    let is_zkvm = resolve_is_zkvm(&name, cfg.is_zkvm);
+    if !props_file.exists() {
+        if let Err(e) = resolve_properties(&name, None, is_zkvm) {
+            error(e.to_string());
+        }
+    } else {
+        match fs::read_to_string(&props_file) {
+            Err(e) => error(format!("cannot read {}: {e}", props_file.display())),
+            Ok(s) => match serde_json::from_str::<BenchProperties>(&s) {
+                Err(e) => error(format!("{} is not valid: {e}", props_file.display())),
+                Ok(_) => {
+                    if let Err(e) = resolve_properties(&name, Some(&props_file), is_zkvm) {
+                        error(e.to_string());
+                    }
+                }
+            },
+        }
+    }
+
+    // Scripts: the registered targets of a known system, otherwise the
+    // targets the directory has scripts for.
+    let registered = ProvingSystem::from_str(&name).ok();
+    let targets = match registered {
+        Some(system) => system.targets().to_vec(),
+        None => targets_with_scripts(dir),
+    };
+    if targets.is_empty() {
+        error(format!(
+            "no {{target}}_prepare.sh scripts in {}",
+            dir.display()
+        ));
+    }
+    let mut warnings = Vec::new();
+    for target in &targets {
+        let target = target.as_str();
+        for step in REQUIRED_SCRIPTS {
+            let script = dir.join(format!("{target}_{step}.sh"));
+            if !script.exists() {
+                error(format!("missing {}", script.display()));
+            } else if !is_executable(&script) {
+                error(format!("{} is not executable", script.display()));
+            }
+        }
+        for step in OPTIONAL_SCRIPTS {
+            let script = dir.join(format!("{target}_{step}.sh"));
+            if !script.exists() {
+                if step == "measure" {
+                    warnings.push(format!(
+                        "missing {}: proof and preprocessing sizes will not be recorded",
+                        script.display()
+                    ));
+                }
+            } else if !is_executable(&script) {
+                error(format!("{} is not executable", script.display()));
+            }
+        }
+    }
+    if let Some(system) = registered {
+        for target in targets_with_scripts(dir) {
+            if !targets.contains(&target) {
+                warnings.push(format!(
+                    "scripts for {} are not run: add it to ProvingSystem::targets() of {}",
+                    target.as_str(),
+                    system.as_str()
+                ));
+            }
+        }
+    }
+
+    // circuit_sizes.json is written by the measure scripts during a run; when
+    // present it must cover every input size.
+    let constraints_file = dir.join("circuit_sizes.json");
+    if !constraints_file.exists() {
+        warnings.push(format!(
+            "no {} yet: constraint coverage is checked once the measure scripts wrote it",
+            constraints_file.display()
+        ));
+    } else {
+        for &target in &targets {
+            for size in input_sizes_for(target) {
+                if let Err(e) = read_num_constraints_json(&constraints_file, target.as_str(), size)
+                {
+                    error(format!("{}: {e}", constraints_file.display()));
+                }
+            }
+        }
+    }
+
+    if let Some(utils_bin) = &cfg.dry_run_prepare {
+        for &target in &targets {
+            let prepare = dir.join(format!("{}_prepare.sh", target.as_str()));
+            let Some(size) = input_sizes_for(target).into_iter().min() else {
+                continue;
+            };
+            if !prepare.exists() {
+                continue;
+            }
+            let state = std::env::temp_dir().join(format!(
+                "lint_{name}_{}_{}_state.json",
+                target.as_str(),
+                std::process::id()
+            ));
+            let env = [
+                ("UTILS_BIN", utils_bin.to_string_lossy().into_owned()),
+                ("INPUT_SIZE", size.to_string()),
+                ("STATE_JSON", state.to_string_lossy().into_owned()),
+                ("FEATURE", String::new()),
+            ];
+            let env: Vec<(&str, &str)> = env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+            match run_script(&prepare, &env, false) {
+                Err(e) => error(format!("dry run of {}: {e}", prepare.display())),
+                Ok(_) => match fs::read_to_string(&state) {
+                    Err(_) => error(format!(
+                        "{} did not write STATE_JSON for size {size}",
+                        prepare.display()
+                    )),
+                    Ok(s) if serde_json::from_str::<serde_json::Value>(&s).is_err() => error(
+                        format!("{} wrote a STATE_JSON that is not JSON", prepare.display()),
+                    ),
+                    Ok(_) => {}
+                },
+            }
+            let _ = fs::remove_file(&state);
+        }
+    }
+
+    issues.extend(warnings.into_iter().map(|message| Issue {
+        level: Level::Warning,
+        message,
+    }));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_script(path: &Path, body: &str, executable: bool) {
+        fs::write(path, body).unwrap();
+        let mode = if executable { 0o755 } else { 0o644 };
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn test_lint_system() {
+        let dir = std::env::temp_dir().join(format!("utils_lint_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let cfg = LintConfig {
+            system_dir: dir.clone(),
+            name: Some("fake".to_string()),
+            is_zkvm: false,
+            dry_run_prepare: Some(PathBuf::from("utils")),
+        };
+        let errors = |issues: &[Issue]| {
+            issues
+                .iter()
+                .filter(|i| i.level == Level::Error)
+                .map(|i| i.message.clone())
+                .collect::<Vec<_>>()
+        };
+
+        write_script(
+            &dir.join("ecdsa_prepare.sh"),
+            r#"echo "{\"size\": $INPUT_SIZE}" > "$STATE_JSON""#,
+            true,
+        );
+        write_script(&dir.join("ecdsa_prove.sh"), "true", false);
+        // An unregistered system must provide bench_props.json...
+        assert!(
+            errors(&lint_system(&cfg))
+                .iter()
+                .any(|e| e.contains("no properties were provided"))
+        );
+        // ...a registered one publishes the registry's properties.
+        let registered = LintConfig {
+            name: Some("barretenberg".to_string()),
+            ..cfg.clone()
+        };
+        assert!(
+            !errors(&lint_system(&registered))
+                .iter()
+                .any(|e| e.contains("properties"))
+        );
+        fs::write(dir.join("bench_props.json"), "{}").unwrap();
+        let issues = lint_system(&cfg);
+        let errs = errors(&issues);
+        assert!(
+            errs.iter()
+                .any(|e| e.contains("bench_props.json is not valid"))
+        );
+        assert!(
+            errs.iter()
+                .any(|e| e.contains("ecdsa_prove.sh is not executable"))
+        );
+        assert!(
+            errs.iter()
+                .any(|e| e.starts_with("missing") && e.ends_with("ecdsa_verify.sh"))
+        );
+        assert_eq!(issues.last().unwrap().level, Level::Warning);
+
+        fs::write(dir.join("circuit_sizes.json"), r#"{"ecdsa": {}}"#).unwrap();
+        let errs = errors(&lint_system(&cfg));
+        assert!(errs.iter().any(|e| e.contains("missing circuit size")));
+
+        write_script(&dir.join("ecdsa_prove.sh"), "true", true);
+        write_script(&dir.join("ecdsa_verify.sh"), "true", true);
+        write_script(&dir.join("ecdsa_measure.sh"), "true", true);
+        fs::write(
+            dir.join("bench_props.json"),
+            serde_json::to_string(&ProvingSystem::Barretenberg.properties()).unwrap(),
+        )
+        .unwrap();
+        fs::write(dir.join("circuit_sizes.json"), r#"{"ecdsa": {"32": 1000}}"#).unwrap();
+        assert!(lint_system(&cfg).is_empty());
+
+        // The dry run catches a prepare script that does not write its state.
+        write_script(&dir.join("ecdsa_prepare.sh"), "true", true);
+        assert_eq!(errors(&lint_system(&cfg)).len(), 1);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,6 +1,13 @@
-use clap::{Parser, Subcommand};
-use hex::ToHex;
+use clap::{Parser, Subcommand, ValueEnum};
+use human_repr::{HumanCount, HumanDuration};
+use std::io::Write;
+use std::path::PathBuf;
+use tabled::{Table, Tabled, settings::Style};
 use utils::BenchTarget;
+use utils::harness::SetupKind;
+use utils::inputs::{GeneratedInput, InputFormat};
+use utils::negative::NegativeCase;
+use utils::vectors::VectorSet;
 
 /// CLI to generate benchmark inputs and query available sizes
 #[derive(Parser, Debug)]
@@ -17,16 +24,348 @@ enum Command {
         /// Input size in bytes (default 128)
         #[arg(long, short = 'n', default_value_t = 128)]
         size: usize,
+        #[command(flatten)]
+        emit: EmitArgs,
+    },
+
+    /// Generate inputs for keccak256: prints hex-encoded message bytes then hex digest
+    Keccak {
+        /// Input size in bytes (default 128)
+        #[arg(long, short = 'n', default_value_t = 128)]
+        size: usize,
+        #[command(flatten)]
+        emit: EmitArgs,
     },
 
     /// Generate inputs for ecdsa: prints hex-encoded hashed message, public key, and signature
-    Ecdsa,
+    Ecdsa {
+        #[command(flatten)]
+        emit: EmitArgs,
+    },
+
+    /// Generate inputs for secp256k1 ecdsa, in the same layout as `ecdsa`
+    EcdsaK256 {
+        #[command(flatten)]
+        emit: EmitArgs,
+    },
+
+    /// List the invalid instances of a target for soundness checks, or
+    /// generate one in the layout of the input generators
+    Negative {
+        #[arg(long)]
+        target: BenchTarget,
+        /// wrong-digest, length-mismatch (sha256, keccak), flipped-signature-bit,
+        /// high-s, off-curve-key (ecdsa). Emit this case instead of listing
+        /// the cases of the target
+        #[arg(long)]
+        case: Option<NegativeCase>,
+        /// Input size in bytes (default 128)
+        #[arg(long, short = 'n', default_value_t = 128)]
+        size: usize,
+        #[command(flatten)]
+        emit: EmitArgs,
+    },
+
+    /// List the bundled standard test vectors of a set, or emit one of them
+    /// like the input generators do
+    Vectors {
+        /// sha256, keccak256, ecdsa-p256 or ecdsa-k256
+        #[arg(long)]
+        set: VectorSet,
+        /// Emit this vector instead of listing the set
+        #[arg(long)]
+        name: Option<String>,
+        #[command(flatten)]
+        emit: EmitArgs,
+    },
 
     /// Query available sha256 input sizes from metadata
     Sizes {
         #[command(subcommand)]
         command: SizesCommand,
     },
+
+    /// Print the JSON Schema of the published metrics format
+    Schema {
+        /// Which document to describe
+        #[arg(long, value_enum, default_value_t = SchemaKind::Metrics)]
+        kind: SchemaKind,
+        /// Write the schema to this file instead of stdout
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+
+    /// Render a collected benchmarks file as Markdown, CSV and HTML reports
+    Report {
+        /// Collected benchmarks JSON (e.g. collected_benchmarks.json)
+        #[arg(long, short = 'i')]
+        input: PathBuf,
+        /// Directory to write report.md, report.csv and report.html into
+        #[arg(long, short = 'o', default_value = "report")]
+        out_dir: PathBuf,
+        /// Formats to render (default: all)
+        #[arg(long, value_enum, value_delimiter = ',')]
+        format: Vec<ReportFormat>,
+        /// Only report results measured on this device
+        #[arg(long)]
+        device: Option<String>,
+    },
+
+    /// Fit scaling models to collected results and extrapolate to larger inputs
+    Analyze {
+        /// Collected benchmarks JSON (e.g. collected_benchmarks.json)
+        #[arg(long, short = 'i')]
+        input: PathBuf,
+        /// Only analyse this target
+        #[arg(long)]
+        target: Option<BenchTarget>,
+        /// Input sizes in bytes to extrapolate to (default: 16 KB JWT, 64 KB document)
+        #[arg(long, value_delimiter = ',', default_values_t = [16384, 65536])]
+        extrapolate: Vec<usize>,
+    },
+
+    /// Pick systems for a use case: Pareto frontier over prove time, memory and
+    /// proof size among the systems meeting the requirements
+    Select {
+        /// Collected benchmarks JSON (e.g. collected_benchmarks.json)
+        #[arg(long, short = 'i')]
+        input: PathBuf,
+        #[arg(long)]
+        target: BenchTarget,
+        /// Input size in bytes
+        #[arg(long)]
+        size: usize,
+        /// Maximum prove time in seconds
+        #[arg(long)]
+        max_prove_time: Option<f64>,
+        /// Maximum peak memory, e.g. 2GB
+        #[arg(long, value_parser = utils::select::parse_bytes)]
+        max_memory: Option<usize>,
+        /// Maximum proof size, e.g. 100KB
+        #[arg(long, value_parser = utils::select::parse_bytes)]
+        max_proof_size: Option<usize>,
+        #[arg(long)]
+        min_security_bits: Option<u64>,
+        /// Require post-quantum security
+        #[arg(long)]
+        pq: bool,
+        /// Require zero-knowledge
+        #[arg(long)]
+        zk: bool,
+        /// Require a completed audit
+        #[arg(long)]
+        audited: bool,
+        /// Require an actively maintained system
+        #[arg(long)]
+        maintained: bool,
+        /// Require this setup kind (transparent, universal, circuit_specific)
+        #[arg(long)]
+        setup: Option<SetupKind>,
+        /// Weight of prove time when ranking the frontier
+        #[arg(long, default_value_t = 1.0)]
+        weight_time: f64,
+        /// Weight of peak memory when ranking the frontier
+        #[arg(long, default_value_t = 1.0)]
+        weight_memory: f64,
+        /// Weight of proof size when ranking the frontier
+        #[arg(long, default_value_t = 1.0)]
+        weight_proof_size: f64,
+    },
+
+    /// Run plausibility checks over collected results and report findings
+    Check {
+        /// Collected benchmarks JSON (e.g. collected_benchmarks.json)
+        #[arg(long, short = 'i')]
+        input: PathBuf,
+        /// Print findings as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+        /// Ignore findings with this reason code (repeatable), e.g. ZERO_SECURITY_BITS
+        #[arg(long, value_name = "CODE")]
+        allow: Vec<String>,
+        /// Exit with status 1 if there are findings
+        #[arg(long)]
+        deny: bool,
+    },
+
+    /// Benchmark a script-based (non-Rust) system and write its metrics files,
+    /// implementing the `benchmark.sh` script contract
+    Orchestrate {
+        /// Directory with the {target}_*.sh scripts (and bench_props.json
+        /// for unregistered systems)
+        #[arg(long)]
+        system_dir: PathBuf,
+        /// Proving system name (default: name of the system directory)
+        #[arg(long)]
+        name: Option<String>,
+        /// Feature variant, passed to the scripts as FEATURE
+        #[arg(long)]
+        feature: Option<String>,
+        /// Mark an unregistered system as zkVM (registered systems use the registry)
+        #[arg(long)]
+        is_zkvm: bool,
+        /// Targets to run (default: all targets with a prepare script)
+        #[arg(long, value_delimiter = ',')]
+        targets: Vec<BenchTarget>,
+        /// Timed runs of the prover and the verifier
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        /// Single timed run (overrides --runs)
+        #[arg(long)]
+        quick: bool,
+        /// Runs averaged for peak memory
+        #[arg(long, default_value_t = 10)]
+        mem_runs: usize,
+        /// Skip the memory measurement
+        #[arg(long)]
+        no_ram: bool,
+        /// Show the output of the timed scripts
+        #[arg(long)]
+        logging: bool,
+    },
+
+    /// Check a script-based system directory against the orchestrator contract
+    LintSystem {
+        /// Directory with the {target}_*.sh scripts (and bench_props.json
+        /// for unregistered systems)
+        #[arg(long)]
+        system_dir: PathBuf,
+        /// Proving system name (default: name of the system directory)
+        #[arg(long)]
+        name: Option<String>,
+        /// Mark an unregistered system as zkVM (registered systems use the registry)
+        #[arg(long)]
+        is_zkvm: bool,
+        /// Also run each prepare script for the smallest input size
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Convert collected results to the ethproofs.org upload payload, validate
+    /// it and POST it
+    Publish {
+        /// Collected benchmarks JSON (e.g. collected_benchmarks.json)
+        #[arg(long, short = 'i')]
+        input: PathBuf,
+        /// Upload endpoint
+        #[arg(long, default_value = utils::publish::DEFAULT_URL)]
+        url: String,
+        /// Bearer token of the endpoint
+        #[arg(long, env = utils::publish::API_KEY_ENV, hide_env_values = true)]
+        api_key: Option<String>,
+        /// Name of the upload (default: collected-benchmarks-<run_id>)
+        #[arg(long)]
+        filename: Option<String>,
+        /// Only publish results measured on this device
+        #[arg(long)]
+        device: Option<String>,
+        /// Print the request instead of sending it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Inspect or clean the artifact cache (compiled guests and circuits)
+    Cache {
+        /// Cache directory (default: $ARTIFACT_CACHE_DIR, else
+        /// target/artifact-cache in the repository)
+        #[arg(long, global = true)]
+        dir: Option<PathBuf>,
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
+    /// Local store of results across runs (append-only JSONL)
+    History {
+        /// Path of the store
+        #[arg(long, global = true, default_value = utils::history::DEFAULT_STORE)]
+        store: PathBuf,
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// List the cached artifacts
+    List,
+    /// Remove cached artifacts, all of them unless filtered
+    Clean {
+        /// Only entries of this kind, e.g. zkvm-guest or noir-circuit
+        #[arg(long)]
+        kind: Option<String>,
+        /// Only entries with this name, e.g. sha256-ere_sp1
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HistoryCommand {
+    /// Append a collected benchmarks file to the store
+    Import {
+        /// Collected benchmarks JSON, e.g. results/collected_benchmarks_<run_id>.json
+        file: PathBuf,
+        /// Run id (default: parsed from the file name)
+        #[arg(long)]
+        run_id: Option<String>,
+        /// Date of the run, YYYY-MM-DD (default: today)
+        #[arg(long)]
+        date: Option<String>,
+        /// Machine or CI runner the run was measured on
+        #[arg(long, default_value = "unknown")]
+        environment: String,
+    },
+    /// Show how a benchmark changed across quarters
+    Show {
+        #[arg(long)]
+        system: String,
+        #[arg(long)]
+        target: BenchTarget,
+        /// Input size in bytes (default: all sizes)
+        #[arg(long)]
+        size: Option<usize>,
+    },
+}
+
+/// Output encoding of the input generators
+#[derive(clap::Args, Debug)]
+struct EmitArgs {
+    /// Output format: hex, toml (Noir Prover.toml), circom (input JSON),
+    /// ligetron (args JSON), binary or decimal
+    #[arg(long, default_value_t = InputFormat::Hex)]
+    format: InputFormat,
+    /// Fields to emit, in order, optionally renamed as name:new_name
+    /// (sha256/keccak: input, input_len, digest; ecdsa: message_hash,
+    /// public_key_x, public_key_y, signature). Default: all fields
+    #[arg(long, value_delimiter = ',')]
+    fields: Vec<String>,
+}
+
+impl EmitArgs {
+    fn emit(&self, input: GeneratedInput) {
+        let input = input.select(&self.fields).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(2);
+        });
+        std::io::stdout()
+            .write_all(&input.render(self.format))
+            .expect("write input to stdout");
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum ReportFormat {
+    Markdown,
+    Csv,
+    Html,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SchemaKind {
+    /// A single `*_metrics.json` entry (collected files are arrays of these)
+    Metrics,
+    /// The `bench_props.json` file of a non-Rust system
+    BenchProperties,
 }
 
 #[derive(Subcommand, Debug)]
@@ -50,21 +389,285 @@ enum SizesCommand {
     },
 }
 
+/// `name (feat) @ device`, as in `utils::report::series_label`.
+fn system_label(name: &str, feat: Option<&str>, device: Option<&str>) -> String {
+    let mut label = match feat {
+        Some(f) => format!("{name} ({f})"),
+        None => name.to_string(),
+    };
+    if let Some(device) = device {
+        label.push_str(&format!(" @ {device}"));
+    }
+    label
+}
+
+/// Prints one table per target and quantity, and lists the systems whose
+/// measurements are dominated by fixed overhead.
+fn print_analysis(analyses: &[utils::analyze::Analysis], extrapolate: &[usize]) {
+    use utils::analyze::{BLOCK_SIZE, FIXED_OVERHEAD_SHARE, Quantity};
+
+    // Keyed by target and position in `Quantity::ALL`, to keep that order.
+    let mut tables: std::collections::BTreeMap<(String, usize), Vec<Vec<String>>> =
+        Default::default();
+    for a in analyses {
+        let system = system_label(&a.name, a.feat.as_deref(), a.device.as_deref());
+        let mut row = vec![
+            system,
+            a.sizes.len().to_string(),
+            a.best.model.to_string(),
+            format!("{:.3}", a.best.r_squared),
+            a.quantity.display(a.marginal_per_block),
+            format!("{:.0}%", a.fixed_share * 100.0),
+        ];
+        row.extend(a.extrapolated.iter().map(|(_, v)| a.quantity.display(*v)));
+        if a.overhead_dominated() {
+            row[0].push_str(" *");
+        }
+        tables
+            .entry((
+                a.target.clone(),
+                Quantity::ALL.iter().position(|q| *q == a.quantity).unwrap(),
+            ))
+            .or_default()
+            .push(row);
+    }
+
+    for ((target, quantity), rows) in tables {
+        let quantity = Quantity::ALL[quantity];
+        let mut builder = tabled::builder::Builder::default();
+        let mut header = vec![
+            "system".to_string(),
+            "points".to_string(),
+            "best model".to_string(),
+            "R²".to_string(),
+            format!("per {BLOCK_SIZE} B block"),
+            "fixed share".to_string(),
+        ];
+        header.extend(extrapolate.iter().map(|n| format!("@{n} B")));
+        builder.push_record(header);
+        for row in rows {
+            builder.push_record(row);
+        }
+        let mut table = builder.build();
+        table.with(Style::markdown());
+        println!("## {target}: {quantity}\n\n{table}\n");
+    }
+    println!(
+        "* fixed overhead exceeds {:.0}% of the cost at the largest measured size; \
+         the measured points say little about scaling and extrapolations are unreliable.",
+        FIXED_OVERHEAD_SHARE * 100.0
+    );
+}
+
+#[derive(Tabled)]
+struct SelectionRow {
+    system: String,
+    status: String,
+    prove: String,
+    #[tabled(rename = "peak memory")]
+    peak_memory: String,
+    #[tabled(rename = "proof size")]
+    proof_size: String,
+    pq: bool,
+    #[tabled(rename = "security bits")]
+    security_bits: u64,
+    setup: String,
+    audit: String,
+    #[tabled(rename = "qualified by")]
+    passed: String,
+    #[tabled(rename = "disqualified by")]
+    failed: String,
+}
+
+fn print_selection(candidates: &[utils::select::Candidate]) {
+    use utils::select::Status;
+
+    let rows = candidates.iter().map(|c| {
+        let m = c.metrics;
+        let props = &m.bench_properties;
+        SelectionRow {
+            system: c.label(),
+            status: match &c.status {
+                Status::Frontier { score } => format!("frontier (score {score:.2})"),
+                Status::Dominated { by } => format!("dominated by {by}"),
+                Status::Disqualified => "disqualified".to_string(),
+            },
+            prove: m.proof_duration.human_duration().to_string(),
+            peak_memory: m.peak_memory.human_count_bytes().to_string(),
+            proof_size: m.proof_size.human_count_bytes().to_string(),
+            pq: props.is_pq,
+            security_bits: props.security_bits,
+            setup: props.setup_kind.to_string(),
+            audit: props.is_audited.to_string(),
+            passed: c.passed.join("; "),
+            failed: c.failed.join("; "),
+        }
+    });
+    let mut table = Table::new(rows);
+    table.with(Style::markdown());
+    println!("{table}");
+    println!(
+        "\nScore: weighted sum of log2 ratios to the best frontier value per objective (lower is better)."
+    );
+}
+
+#[derive(Tabled)]
+struct FindingRow {
+    code: &'static str,
+    system: String,
+    target: String,
+    size: String,
+    message: String,
+}
+
+fn print_findings(findings: &[utils::sanity::Finding]) {
+    let rows = findings.iter().map(|f| FindingRow {
+        code: f.reason.code(),
+        system: system_label(&f.name, f.feat.as_deref(), f.device.as_deref()),
+        target: f.target.clone(),
+        size: f
+            .input_size
+            .map_or_else(|| "all".to_string(), |s| s.to_string()),
+        message: f.message.clone(),
+    });
+    let mut table = Table::new(rows);
+    table.with(Style::markdown());
+    println!("{table}");
+    println!("\n{} finding(s)", findings.len());
+}
+
+#[derive(Tabled)]
+struct CacheRow {
+    kind: String,
+    name: String,
+    digest: String,
+    size: String,
+    age: String,
+    toolchain: String,
+}
+
+fn print_cache_entries(entries: &[utils::cache::CacheEntry]) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let rows = entries.iter().map(|e| CacheRow {
+        kind: e.kind.clone(),
+        name: e.name.clone(),
+        digest: e.digest.get(..12).unwrap_or(&e.digest).to_string(),
+        size: e.size().human_count_bytes().to_string(),
+        age: std::time::Duration::from_secs(now.saturating_sub(e.created))
+            .human_duration()
+            .to_string(),
+        toolchain: e.toolchain.join(", "),
+    });
+    let mut table = Table::new(rows);
+    table.with(Style::markdown());
+    println!("{table}");
+}
+
+#[derive(Tabled)]
+struct HistoryRow {
+    size: usize,
+    quarter: String,
+    runs: usize,
+    prove: String,
+    change: String,
+    #[tabled(rename = "peak memory")]
+    peak_memory: String,
+    #[tabled(rename = "proof size")]
+    proof_size: String,
+    #[tabled(rename = "prover version")]
+    versions: String,
+    environment: String,
+}
+
+/// Prints the quarterly summaries with the prove time change relative to the
+/// previous quarter, and whether the prover version changed in between (if
+/// not, the change comes from the harness or the environment).
+fn print_history(history: &[utils::history::QuarterSummary]) {
+    let mut rows = Vec::new();
+    let mut previous: Option<&utils::history::QuarterSummary> = None;
+    for q in history {
+        let change = match previous.filter(|p| p.input_size == q.input_size) {
+            Some(p) if !p.proof_duration.is_zero() => {
+                let relative =
+                    q.proof_duration.as_secs_f64() / p.proof_duration.as_secs_f64() - 1.0;
+                let cause = if p.versions == q.versions {
+                    "same prover"
+                } else {
+                    "prover changed"
+                };
+                format!("{:+.1}% ({cause})", relative * 100.0)
+            }
+            _ => "-".to_string(),
+        };
+        rows.push(HistoryRow {
+            size: q.input_size,
+            quarter: q.quarter.clone(),
+            runs: q.runs,
+            prove: q.proof_duration.human_duration().to_string(),
+            change,
+            peak_memory: q.peak_memory.human_count_bytes().to_string(),
+            proof_size: q.proof_size.human_count_bytes().to_string(),
+            versions: q.versions.iter().cloned().collect::<Vec<_>>().join(", "),
+            environment: q
+                .environments
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", "),
+        });
+        previous = Some(q);
+    }
+    let mut table = Table::new(rows);
+    table.with(Style::markdown());
+    println!("{table}");
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Command::Sha256 { size } => {
-            let (message_bytes, digest) = utils::generate_sha256_input(size);
-            println!("{}", message_bytes.encode_hex::<String>());
-            println!("{}", digest.encode_hex::<String>());
-        }
-        Command::Ecdsa => {
-            let (digest, (pub_key_x, pub_key_y), signature) = utils::generate_ecdsa_input();
-            println!("{}", digest.encode_hex::<String>());
-            println!("{}", pub_key_x.encode_hex::<String>());
-            println!("{}", pub_key_y.encode_hex::<String>());
-            println!("{}", signature.encode_hex::<String>());
+        Command::Sha256 { size, emit } => emit.emit(GeneratedInput::sha256(size)),
+        Command::Keccak { size, emit } => emit.emit(GeneratedInput::keccak(size)),
+        Command::Ecdsa { emit } => emit.emit(GeneratedInput::ecdsa_p256()),
+        Command::EcdsaK256 { emit } => emit.emit(GeneratedInput::ecdsa_k256()),
+        Command::Negative {
+            target,
+            case,
+            size,
+            emit,
+        } => match case {
+            None => {
+                for case in utils::negative::negative_cases(target) {
+                    println!("{case}");
+                }
+            }
+            Some(case) => match utils::negative::negative_input(target, size, case) {
+                Some(input) => emit.emit(input),
+                None => {
+                    eprintln!("{case} does not apply to {}", target.as_str());
+                    std::process::exit(2);
+                }
+            },
+        },
+        Command::Vectors { set, name, emit } => {
+            let vectors = utils::vectors::vectors(set);
+            match name {
+                None => {
+                    for vector in &vectors {
+                        println!("{}\t{}", vector.name(), vector.describe());
+                    }
+                }
+                Some(name) => match vectors.iter().find(|v| v.name() == name) {
+                    Some(vector) => emit.emit(vector.input()),
+                    None => {
+                        eprintln!("no vector {name} in {set}");
+                        std::process::exit(2);
+                    }
+                },
+            }
         }
         Command::Sizes {
             command: SizesCommand::List { target },
@@ -89,5 +692,318 @@ fn main() {
                 std::process::exit(2);
             }
         }
+        Command::Schema { kind, output } => {
+            let schema = match kind {
+                SchemaKind::Metrics => utils::schema::metrics_json_schema(),
+                SchemaKind::BenchProperties => utils::schema::bench_properties_json_schema(),
+            };
+            let json = serde_json::to_string_pretty(&schema).expect("serialize schema");
+            match output {
+                Some(path) => std::fs::write(&path, json + "\n").expect("write schema"),
+                None => println!("{}", json),
+            }
+        }
+        Command::Report {
+            input,
+            out_dir,
+            format,
+            device,
+        } => {
+            let mut results = utils::schema::load_collected(&input).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {}", input.display(), e);
+                std::process::exit(2);
+            });
+            if let Some(device) = device {
+                results.retain(|m| m.device.as_deref() == Some(device.as_str()));
+            }
+            let formats = if format.is_empty() {
+                vec![
+                    ReportFormat::Markdown,
+                    ReportFormat::Csv,
+                    ReportFormat::Html,
+                ]
+            } else {
+                format
+            };
+            std::fs::create_dir_all(&out_dir).expect("create report directory");
+            for format in formats {
+                let (file, contents) = match format {
+                    ReportFormat::Markdown => {
+                        ("report.md", utils::report::render_markdown(&results))
+                    }
+                    ReportFormat::Csv => (
+                        "report.csv",
+                        utils::report::render_csv(&results).expect("render CSV report"),
+                    ),
+                    ReportFormat::Html => ("report.html", utils::report::render_html(&results)),
+                };
+                let path = out_dir.join(file);
+                std::fs::write(&path, contents).expect("write report");
+                println!("wrote {}", path.display());
+            }
+        }
+        Command::Analyze {
+            input,
+            target,
+            extrapolate,
+        } => {
+            let mut results = utils::schema::load_collected(&input).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {}", input.display(), e);
+                std::process::exit(2);
+            });
+            if let Some(target) = target {
+                results.retain(|m| m.target == target.as_str());
+            }
+            print_analysis(
+                &utils::analyze::analyze(&results, &extrapolate),
+                &extrapolate,
+            );
+        }
+        Command::Select {
+            input,
+            target,
+            size,
+            max_prove_time,
+            max_memory,
+            max_proof_size,
+            min_security_bits,
+            pq,
+            zk,
+            audited,
+            maintained,
+            setup,
+            weight_time,
+            weight_memory,
+            weight_proof_size,
+        } => {
+            let results = utils::schema::load_collected(&input).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {}", input.display(), e);
+                std::process::exit(2);
+            });
+            let requirements = utils::select::Requirements {
+                max_prove_time: max_prove_time.map(std::time::Duration::from_secs_f64),
+                max_memory,
+                max_proof_size,
+                min_security_bits,
+                require_pq: pq,
+                require_zk: zk,
+                require_audited: audited,
+                require_maintained: maintained,
+                setup_kind: setup,
+            };
+            let weights = utils::select::Weights {
+                prove_time: weight_time,
+                memory: weight_memory,
+                proof_size: weight_proof_size,
+            };
+            let candidates =
+                utils::select::select(&results, target.as_str(), size, &requirements, &weights);
+            if candidates.is_empty() {
+                eprintln!("no results for {} at {size} bytes", target.as_str());
+                std::process::exit(1);
+            }
+            print_selection(&candidates);
+        }
+        Command::Check {
+            input,
+            json,
+            allow,
+            deny,
+        } => {
+            let results = utils::schema::load_collected(&input).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {}", input.display(), e);
+                std::process::exit(2);
+            });
+            let mut findings = utils::sanity::check_all(&results);
+            findings.retain(|f| !allow.iter().any(|code| code == f.reason.code()));
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&findings).expect("serialize findings")
+                );
+            } else if findings.is_empty() {
+                println!("No findings.");
+            } else {
+                print_findings(&findings);
+            }
+            if deny && !findings.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Command::Orchestrate {
+            system_dir,
+            name,
+            feature,
+            is_zkvm,
+            targets,
+            runs,
+            quick,
+            mem_runs,
+            no_ram,
+            logging,
+        } => {
+            let cfg = utils::orchestrate::OrchestrateConfig {
+                system_dir,
+                name,
+                feature,
+                is_zkvm,
+                targets,
+                runs: if quick { 1 } else { runs },
+                mem_runs: if no_ram { 0 } else { mem_runs },
+                show_output: logging,
+                utils_bin: std::env::current_exe().expect("path of the utils binary"),
+            };
+            match utils::orchestrate::orchestrate(&cfg) {
+                Ok(written) => println!("wrote {} metrics files", written.len()),
+                Err(e) => {
+                    eprintln!("orchestrate failed: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::LintSystem {
+            system_dir,
+            name,
+            is_zkvm,
+            dry_run,
+        } => {
+            let cfg = utils::lint::LintConfig {
+                system_dir,
+                name,
+                is_zkvm,
+                dry_run_prepare: dry_run
+                    .then(|| std::env::current_exe().expect("path of the utils binary")),
+            };
+            let issues = utils::lint::lint_system(&cfg);
+            for issue in &issues {
+                println!("{issue}");
+            }
+            if issues.iter().any(|i| i.level == utils::lint::Level::Error) {
+                std::process::exit(1);
+            }
+            if issues.is_empty() {
+                println!("{} follows the system contract", cfg.system_dir.display());
+            }
+        }
+        Command::Publish {
+            input,
+            url,
+            api_key,
+            filename,
+            device,
+            dry_run,
+        } => {
+            let mut results = utils::schema::load_collected(&input).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {}", input.display(), e);
+                std::process::exit(2);
+            });
+            if let Some(device) = device {
+                results.retain(|m| m.device.as_deref() == Some(device.as_str()));
+            }
+            let filename = filename.unwrap_or_else(|| {
+                let run_id = utils::history::run_id_from_path(&input);
+                format!(
+                    "collected-benchmarks-{}",
+                    run_id.as_deref().unwrap_or("local")
+                )
+            });
+            let request = utils::publish::UploadRequest::new(&url, &filename, &results)
+                .unwrap_or_else(|errors| {
+                    for e in errors {
+                        eprintln!("invalid: {e}");
+                    }
+                    std::process::exit(2);
+                });
+            if dry_run {
+                println!("{}", request.render());
+                return;
+            }
+            let Some(api_key) = api_key else {
+                eprintln!(
+                    "no API key, pass --api-key or set {}",
+                    utils::publish::API_KEY_ENV
+                );
+                std::process::exit(2);
+            };
+            match request.send(&api_key) {
+                Ok(response) => println!("uploaded {} entries: {response}", results.len()),
+                Err(e) => {
+                    eprintln!("upload failed: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        Command::Cache { dir, command } => {
+            let cache = utils::cache::ArtifactCache::new(
+                dir.unwrap_or_else(utils::cache::default_cache_dir),
+            );
+            let result = match command {
+                CacheCommand::List => cache.entries().map(|entries| {
+                    if entries.is_empty() {
+                        println!("no cached artifacts in {}", cache.root().display());
+                    } else {
+                        print_cache_entries(&entries);
+                    }
+                }),
+                CacheCommand::Clean { kind, name } => cache
+                    .clean(kind.as_deref(), name.as_deref())
+                    .map(|n| println!("removed {n} entries from {}", cache.root().display())),
+            };
+            if let Err(e) = result {
+                eprintln!("cache: {e}");
+                std::process::exit(2);
+            }
+        }
+        Command::History {
+            store,
+            command:
+                HistoryCommand::Import {
+                    file,
+                    run_id,
+                    date,
+                    environment,
+                },
+        } => {
+            let Some(run_id) = run_id.or_else(|| utils::history::run_id_from_path(&file)) else {
+                eprintln!("cannot infer run id from {}, pass --run-id", file.display());
+                std::process::exit(2);
+            };
+            let date = date.unwrap_or_else(utils::history::today);
+            match utils::history::import_collected(&store, &file, &run_id, &date, &environment) {
+                Ok(n) => println!(
+                    "imported {n} entries of run {run_id} into {}",
+                    store.display()
+                ),
+                Err(e) => {
+                    eprintln!("import failed: {e}");
+                    std::process::exit(2);
+                }
+            }
+        }
+        Command::History {
+            store,
+            command:
+                HistoryCommand::Show {
+                    system,
+                    target,
+                    size,
+                },
+        } => {
+            let records = utils::history::load_history(&store).unwrap_or_else(|e| {
+                eprintln!("failed to load {}: {e}", store.display());
+                std::process::exit(2);
+            });
+            let history =
+                utils::history::quarterly_history(&records, &system, target.as_str(), size);
+            if history.is_empty() {
+                eprintln!(
+                    "no entries for {system} {} in {}",
+                    target.as_str(),
+                    store.display()
+                );
+                std::process::exit(1);
+            }
+            print_history(&history);
+        }
     }
 }
@@ -0,0 +1,168 @@
+//! Invalid instances of the benchmark targets. A sound system either refuses to
+//! prove them or produces a proof that fails verification; see
+//! `harness::run_soundness_fn`.
+
+use crate::BenchTarget;
+use crate::inputs::{FieldValue, GeneratedInput};
+use p256::ecdsa::Signature;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NegativeCase {
+    /// The expected digest has one bit flipped.
+    WrongDigest,
+    /// `input_len` is one less than the message, the digest is of the full
+    /// message. Catches circuits that hash the whole array and ignore the length.
+    LengthMismatch,
+    /// The signature has one bit of `r` flipped.
+    FlippedSignatureBit,
+    /// The signature uses `n - s` instead of the normalized low `s`.
+    HighS,
+    /// The public key `y` coordinate is changed, so the point is not on the curve.
+    OffCurveKey,
+}
+
+impl NegativeCase {
+    pub const ALL: [NegativeCase; 5] = [
+        NegativeCase::WrongDigest,
+        NegativeCase::LengthMismatch,
+        NegativeCase::FlippedSignatureBit,
+        NegativeCase::HighS,
+        NegativeCase::OffCurveKey,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NegativeCase::WrongDigest => "wrong-digest",
+            NegativeCase::LengthMismatch => "length-mismatch",
+            NegativeCase::FlippedSignatureBit => "flipped-signature-bit",
+            NegativeCase::HighS => "high-s",
+            NegativeCase::OffCurveKey => "off-curve-key",
+        }
+    }
+
+    pub fn applies_to(&self, target: BenchTarget) -> bool {
+        match self {
+            NegativeCase::WrongDigest | NegativeCase::LengthMismatch => {
+                matches!(target, BenchTarget::Sha256 | BenchTarget::Keccak)
+            }
+            NegativeCase::FlippedSignatureBit | NegativeCase::HighS | NegativeCase::OffCurveKey => {
+                target == BenchTarget::Ecdsa
+            }
+        }
+    }
+}
+
+impl fmt::Display for NegativeCase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NegativeCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<NegativeCase, String> {
+        NegativeCase::ALL
+            .into_iter()
+            .find(|case| case.as_str() == s)
+            .ok_or_else(|| format!("Invalid negative case: {}", s))
+    }
+}
+
+/// The cases that apply to `target`.
+pub fn negative_cases(target: BenchTarget) -> Vec<NegativeCase> {
+    NegativeCase::ALL
+        .into_iter()
+        .filter(|case| case.applies_to(target))
+        .collect()
+}
+
+fn bytes_mut<'a>(input: &'a mut GeneratedInput, name: &str) -> &'a mut Vec<u8> {
+    match input.field_mut(name) {
+        Some(FieldValue::Bytes(bytes)) => bytes,
+        _ => unreachable!("generated inputs have a {name} byte field"),
+    }
+}
+
+/// The benchmark input of `target` and `size` (see `GeneratedInput`), made
+/// invalid as described by `case`. `None` if the case does not apply.
+pub fn negative_input(
+    target: BenchTarget,
+    size: usize,
+    case: NegativeCase,
+) -> Option<GeneratedInput> {
+    if !case.applies_to(target) {
+        return None;
+    }
+    let mut input = match target {
+        BenchTarget::Sha256 => GeneratedInput::sha256(size),
+        BenchTarget::Keccak => GeneratedInput::keccak(size),
+        BenchTarget::Ecdsa => GeneratedInput::ecdsa_p256(),
+    };
+    match case {
+        NegativeCase::WrongDigest => bytes_mut(&mut input, "digest")[0] ^= 1,
+        NegativeCase::LengthMismatch => {
+            let len = size.checked_sub(1)?;
+            *input.field_mut("input_len")? = FieldValue::Int(len);
+        }
+        NegativeCase::FlippedSignatureBit => bytes_mut(&mut input, "signature")[31] ^= 1,
+        NegativeCase::HighS => {
+            let signature = bytes_mut(&mut input, "signature");
+            let (r, s) = Signature::from_slice(signature).ok()?.split_scalars();
+            let high_s = Signature::from_scalars(r, -s).ok()?;
+            *signature = high_s.to_bytes().to_vec();
+        }
+        NegativeCase::OffCurveKey => bytes_mut(&mut input, "public_key_y")[31] ^= 1,
+    }
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::VerifyingKey;
+    use sha2::{Digest, Sha256};
+
+    fn bytes(input: &GeneratedInput, name: &str) -> Vec<u8> {
+        input.bytes(name).unwrap().to_vec()
+    }
+
+    #[test]
+    fn test_negative_inputs() {
+        assert_eq!(
+            negative_cases(BenchTarget::Sha256),
+            vec![NegativeCase::WrongDigest, NegativeCase::LengthMismatch]
+        );
+        assert!(negative_input(BenchTarget::Sha256, 128, NegativeCase::HighS).is_none());
+
+        let input = negative_input(BenchTarget::Sha256, 128, NegativeCase::LengthMismatch).unwrap();
+        let message = bytes(&input, "input");
+        assert_eq!(bytes(&input, "digest"), Sha256::digest(&message).to_vec());
+        assert_ne!(
+            bytes(&input, "digest"),
+            Sha256::digest(&message[..127]).to_vec()
+        );
+        let wrong = negative_input(BenchTarget::Sha256, 128, NegativeCase::WrongDigest).unwrap();
+        assert_ne!(bytes(&wrong, "digest"), Sha256::digest(&message).to_vec());
+
+        let valid = GeneratedInput::ecdsa_p256();
+        let high_s = negative_input(BenchTarget::Ecdsa, 32, NegativeCase::HighS).unwrap();
+        let signature = Signature::from_slice(&bytes(&high_s, "signature")).unwrap();
+        assert!(signature.normalize_s().is_some());
+        assert_eq!(
+            signature.normalize_s().unwrap().to_bytes().to_vec(),
+            bytes(&valid, "signature")
+        );
+
+        let off_curve = negative_input(BenchTarget::Ecdsa, 32, NegativeCase::OffCurveKey).unwrap();
+        let point = [
+            &[4u8][..],
+            &bytes(&off_curve, "public_key_x"),
+            &bytes(&off_curve, "public_key_y"),
+        ]
+        .concat();
+        assert!(VerifyingKey::from_sec1_bytes(&point).is_err());
+    }
+}
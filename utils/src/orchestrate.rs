@@ -0,0 +1,439 @@
+//! Rust implementation of the script contract of non-Rust systems, previously
+//! driven by `benchmark.sh` through hyperfine, `measure_mem_avg.sh` and
+//! `format_hyperfine`.
+//!
+//! A system directory holds `bench_props.json` (only for systems outside the
+//! `ProvingSystem` registry), an optional `circuit_sizes.json` and, per
+//! target, the scripts
+//!
+//! - `{target}_prepare.sh`: compiles the circuit and writes `STATE_JSON`
+//!   (gets `UTILS_BIN`, `INPUT_SIZE` and `STATE_JSON`),
+//! - `{target}_prove.sh` / `{target}_verify.sh`: the timed steps (get
+//!   `STATE_JSON`),
+//! - `{target}_witness.sh` (optional): witness generation (program execution
+//!   for zkVMs), timed separately as `witness_duration`. It runs before every
+//!   prove, which then only proves from the witness it left in the state,
+//! - `{target}_prove_for_verify.sh` (optional): produces the proof and
+//!   verification key the verifier needs, instead of `_prove.sh`,
+//! - `{target}_measure.sh` (optional): writes proof and preprocessing sizes
+//!   to `SIZES_JSON` (gets `SIZES_JSON` and `STATE_JSON`).
+//!
+//! Every script also gets `FEATURE` (empty for the default variant), and all
+//! but prepare get `COUNTS_JSON`: a script that knows the gate or cycle count
+//! may write `{"num_constraints": N}` and/or `{"cycles": N}` there, which takes
+//! precedence over `circuit_sizes.json`. Targets without a prepare script are
+//! skipped.
+
+use crate::bench::{Metrics, write_json_metrics_file};
+use crate::harness::{BenchTarget, result_filename};
+use crate::input_sizes_for;
+use crate::properties::{resolve_is_zkvm, resolve_properties};
+use serde_json::Value;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Settings of one orchestrated run, mirroring the `benchmark.sh` flags.
+#[derive(Debug, Clone)]
+pub struct OrchestrateConfig {
+    pub system_dir: PathBuf,
+    /// Proving system name (defaults to the directory name).
+    pub name: Option<String>,
+    pub feature: Option<String>,
+    /// Whether an unregistered system is a zkVM; see `resolve_is_zkvm`.
+    pub is_zkvm: bool,
+    /// Targets to run (default: every target with a prepare script).
+    pub targets: Vec<BenchTarget>,
+    /// Timed runs of the prover and the verifier.
+    pub runs: usize,
+    /// Runs averaged for peak memory; 0 skips the memory measurement.
+    pub mem_runs: usize,
+    /// Show the output of the timed scripts.
+    pub show_output: bool,
+    /// Path passed to the scripts as `UTILS_BIN`.
+    pub utils_bin: PathBuf,
+}
+
+/// Wall-clock time and peak resident memory of one script run.
+#[derive(Debug, Clone, Copy)]
+pub struct RunStats {
+    pub wall: Duration,
+    pub max_rss: usize,
+}
+
+/// Mean and sample standard deviation (`None` for a single sample).
+pub fn mean_stddev(samples: &[Duration]) -> (Duration, Option<Duration>) {
+    let n = samples.len();
+    if n == 0 {
+        return (Duration::ZERO, None);
+    }
+    let secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+    let mean = secs.iter().sum::<f64>() / n as f64;
+    let stddev = (n > 1).then(|| {
+        let var = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        Duration::from_secs_f64(var.sqrt())
+    });
+    (Duration::from_secs_f64(mean), stddev)
+}
+
+/// Runs `bash script` with `env` and waits for it, measuring wall-clock time
+/// and the peak RSS of the script and everything it waited for (what
+/// `/usr/bin/time` reports). Fails if the script exits unsuccessfully.
+pub fn run_script(script: &Path, env: &[(&str, &str)], show_output: bool) -> io::Result<RunStats> {
+    let mut command = Command::new("bash");
+    command.arg(script).envs(env.iter().copied());
+    if !show_output {
+        command.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let start = Instant::now();
+    let child = command.spawn()?;
+    let (status, max_rss) = wait_with_rusage(child.id())?;
+    let wall = start.elapsed();
+    if !libc::WIFEXITED(status) || libc::WEXITSTATUS(status) != 0 {
+        return Err(io::Error::other(format!(
+            "{} failed (wait status {status})",
+            script.display()
+        )));
+    }
+    Ok(RunStats { wall, max_rss })
+}
+
+/// Reaps `pid` and returns its wait status and peak RSS in bytes.
+fn wait_with_rusage(pid: u32) -> io::Result<(i32, usize)> {
+    unsafe {
+        let mut status = 0;
+        let mut usage: libc::rusage = std::mem::zeroed();
+        if libc::wait4(pid as libc::pid_t, &mut status, 0, &mut usage) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // Linux reports ru_maxrss in KiB, macOS in bytes.
+        #[cfg(target_os = "linux")]
+        let max_rss = usage.ru_maxrss as usize * 1024;
+        #[cfg(not(target_os = "linux"))]
+        let max_rss = usage.ru_maxrss as usize;
+        Ok((status, max_rss))
+    }
+}
+
+/// Reads the number of constraints from the circuit_sizes.json file
+/// for the given target and size.
+/// The file contents are expected to be in the following format:
+/// {
+///   "target": {
+///     "size": number_of_constraints
+///   }
+/// }
+///
+/// Returns the number of constraints for the given target and size.
+pub fn read_num_constraints_json(
+    num_constraints_file: &Path,
+    target: &str,
+    size: usize,
+) -> io::Result<usize> {
+    let v: Value = serde_json::from_str(&fs::read_to_string(num_constraints_file)?)?;
+
+    let target = v
+        .get(target)
+        .ok_or_else(|| io_err(&format!("missing {target} benchmark target")))?;
+    let size = target.get(size.to_string()).ok_or_else(|| {
+        io_err(&format!(
+            "missing circuit size for the input of {size} bytes for {target} benchmark target"
+        ))
+    })?;
+    Ok(size
+        .as_u64()
+        .ok_or_else(|| {
+            io_err(&format!(
+                "number of constraints value is not a number for the input of {size} bytes for {target} benchmark target"
+            ))
+        })? as usize)
+}
+
+pub fn read_peak_memory_bytes(path: &Path) -> io::Result<usize> {
+    let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    v.get("peak_memory")
+        .and_then(|m| m.as_u64())
+        .map(|n| n as usize)
+        .ok_or_else(|| io_err("missing peak_memory"))
+}
+
+/// Reads `(proof_size, preprocessing_size)` from a `*_sizes.json` file.
+pub fn read_sizes_bytes(path: &Path) -> io::Result<(usize, usize)> {
+    let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let proof = v
+        .get("proof_size")
+        .and_then(|m| m.as_u64())
+        .ok_or_else(|| io_err("missing proof_size"))? as usize;
+    let prep = v
+        .get("preprocessing_size")
+        .and_then(|m| m.as_u64())
+        .unwrap_or(0) as usize;
+    Ok((proof, prep))
+}
+
+/// Reads the optional `(cycles, num_constraints)` a script reported in its
+/// `COUNTS_JSON` file.
+pub fn read_counts(path: &Path) -> io::Result<(Option<u64>, Option<usize>)> {
+    let v: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    let count = |key: &str| -> io::Result<Option<u64>> {
+        match v.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(n) => n
+                .as_u64()
+                .map(Some)
+                .ok_or_else(|| io_err(&format!("{key} in {} is not a count", path.display()))),
+        }
+    };
+    Ok((
+        count("cycles")?,
+        count("num_constraints")?.map(|n| n as usize),
+    ))
+}
+
+fn io_err(msg: &str) -> io::Error {
+    io::Error::other(msg)
+}
+
+/// Benchmarks every target and input size of the system and writes one
+/// `{target}_{size}_{system}[_{feature}]_metrics.json` per benchmark into the
+/// system directory. Returns the written files.
+pub fn orchestrate(cfg: &OrchestrateConfig) -> io::Result<Vec<PathBuf>> {
+    let dir = &cfg.system_dir;
+    if !dir.is_dir() {
+        return Err(io_err(&format!(
+            "system dir does not exist: {}",
+            dir.display()
+        )));
+    }
+    let name = match &cfg.name {
+        Some(name) => name.clone(),
+        None => fs::canonicalize(dir)?
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .ok_or_else(|| io_err("cannot infer the system name, pass --name"))?,
+    };
+    let feat = cfg.feature.as_deref().filter(|f| !f.is_empty());
+    let is_zkvm = resolve_is_zkvm(&name, cfg.is_zkvm);
+    let props_file = dir.join("bench_props.json");
+    let properties = resolve_properties(
+        &name,
+        props_file.exists().then_some(props_file.as_path()),
+        is_zkvm,
+    )?;
+    let constraints_file = dir.join("circuit_sizes.json");
+    let state_dir = dir.join(".bench_state");
+    fs::create_dir_all(&state_dir)?;
+
+    let utils_bin = cfg.utils_bin.to_string_lossy().into_owned();
+    let feature = feat.unwrap_or("").to_string();
+    let targets = if cfg.targets.is_empty() {
+        &BenchTarget::ALL[..]
+    } else {
+        &cfg.targets[..]
+    };
+
+    let mut written = Vec::new();
+    for &bench_target in targets {
+        let target = bench_target.as_str();
+        let script = |step: &str| dir.join(format!("{target}_{step}.sh"));
+        let prepare = script("prepare");
+        if !prepare.is_file() {
+            eprintln!("Skipping target {target}: {} not found", prepare.display());
+            continue;
+        }
+        let (prove, verify, measure) = (script("prove"), script("verify"), script("measure"));
+        let witness = Some(script("witness")).filter(|p| p.is_file());
+        let prove_for_verify = Some(script("prove_for_verify"))
+            .filter(|p| p.is_file())
+            .unwrap_or_else(|| prove.clone());
+
+        for size in input_sizes_for(bench_target) {
+            let file = |kind: &str| dir.join(result_filename(target, size, &name, feat, kind));
+            let stem = result_filename(target, size, &name, feat, "state");
+            let prover_state = state_dir.join(format!("prover_{stem}"));
+            let verifier_state = state_dir.join(format!("verifier_{stem}"));
+            let size_str = size.to_string();
+            let prover_state_str = prover_state.to_string_lossy().into_owned();
+            let verifier_state_str = verifier_state.to_string_lossy().into_owned();
+            let prepare_env = |state: &'_ str| {
+                [
+                    ("UTILS_BIN", utils_bin.clone()),
+                    ("INPUT_SIZE", size_str.clone()),
+                    ("STATE_JSON", state.to_string()),
+                    ("FEATURE", feature.clone()),
+                ]
+            };
+            let run = |script: &Path, env: &[(&str, String)], show: bool| {
+                let env: Vec<(&str, &str)> = env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+                run_script(script, &env, show)
+            };
+            let counts_json = file("counts");
+            let _ = fs::remove_file(&counts_json);
+            let state_env = |state: &'_ str| {
+                [
+                    ("STATE_JSON", state.to_string()),
+                    ("FEATURE", feature.clone()),
+                    ("COUNTS_JSON", counts_json.to_string_lossy().into_owned()),
+                ]
+            };
+            // Runs the witness hook, if any, and returns its time.
+            let run_witness = |state: &str, show: bool| -> io::Result<Option<Duration>> {
+                witness
+                    .as_deref()
+                    .map(|w| run(w, &state_env(state), show).map(|stats| stats.wall))
+                    .transpose()
+            };
+
+            println!("==> [{target}] size {size}");
+            run(&prepare, &prepare_env(&prover_state_str), cfg.show_output)?;
+
+            let mut metrics = Metrics::new(
+                name.clone(),
+                feat.map(str::to_string),
+                is_zkvm,
+                target.to_string(),
+                size,
+                properties.clone(),
+            );
+
+            let sizes_json = file("sizes");
+            if measure.is_file() {
+                let mut env = state_env(&prover_state_str).to_vec();
+                env.push(("SIZES_JSON", sizes_json.to_string_lossy().into_owned()));
+                // A failing measurement only loses the sizes, as in benchmark.sh.
+                match run(&measure, &env, cfg.show_output)
+                    .and_then(|_| read_sizes_bytes(&sizes_json))
+                {
+                    Ok((proof_size, preprocessing_size)) => {
+                        metrics.proof_size = proof_size;
+                        metrics.preprocessing_size = preprocessing_size;
+                    }
+                    Err(e) => eprintln!("WARNING: size measurement failed: {e}"),
+                }
+                let _ = fs::remove_file(&sizes_json);
+            }
+
+            let mut witness_times = Vec::with_capacity(cfg.runs);
+            let mut prove_times = Vec::with_capacity(cfg.runs);
+            for _ in 0..cfg.runs {
+                run(&prepare, &prepare_env(&prover_state_str), false)?;
+                witness_times.extend(run_witness(&prover_state_str, cfg.show_output)?);
+                prove_times.push(run(&prove, &state_env(&prover_state_str), cfg.show_output)?.wall);
+            }
+            let mut verify_times = Vec::with_capacity(cfg.runs);
+            for _ in 0..cfg.runs {
+                run(&prepare, &prepare_env(&verifier_state_str), false)?;
+                run_witness(&verifier_state_str, false)?;
+                run(&prove_for_verify, &state_env(&verifier_state_str), false)?;
+                verify_times
+                    .push(run(&verify, &state_env(&verifier_state_str), cfg.show_output)?.wall);
+            }
+            (metrics.proof_duration, metrics.proof_duration_stddev) = mean_stddev(&prove_times);
+            (metrics.verify_duration, metrics.verify_duration_stddev) = mean_stddev(&verify_times);
+            if !witness_times.is_empty() {
+                metrics.witness_duration = Some(mean_stddev(&witness_times).0);
+            }
+
+            if cfg.mem_runs > 0 {
+                run(&prepare, &prepare_env(&prover_state_str), false)?;
+                run_witness(&prover_state_str, false)?;
+                let mut total = 0;
+                for _ in 0..cfg.mem_runs {
+                    total += run(&prove, &state_env(&prover_state_str), false)?.max_rss;
+                }
+                metrics.peak_memory = total / cfg.mem_runs;
+            }
+
+            if constraints_file.exists() {
+                metrics.num_constraints =
+                    read_num_constraints_json(&constraints_file, target, size)?;
+            }
+            if counts_json.exists() {
+                let (cycles, num_constraints) = read_counts(&counts_json)?;
+                metrics.cycles = cycles.or(metrics.cycles);
+                metrics.num_constraints = num_constraints.unwrap_or(metrics.num_constraints);
+                let _ = fs::remove_file(&counts_json);
+            }
+
+            let out = file("metrics");
+            write_json_metrics_file(&out.to_string_lossy(), &metrics);
+            println!("    wrote {}", out.display());
+            written.push(out);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mean_stddev() {
+        let (mean, sd) = mean_stddev(&[Duration::from_secs(1), Duration::from_secs(3)]);
+        assert_eq!(mean, Duration::from_secs(2));
+        assert!((sd.unwrap().as_secs_f64() - 2f64.sqrt()).abs() < 1e-9);
+        assert_eq!(mean_stddev(&[Duration::from_secs(1)]).1, None);
+    }
+
+    #[test]
+    fn test_orchestrate_fake_system() {
+        let dir =
+            std::env::temp_dir().join(format!("utils_orchestrate_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let scripts = [
+            (
+                "ecdsa_prepare.sh",
+                r#"echo "{\"size\": $INPUT_SIZE}" > "$STATE_JSON""#,
+            ),
+            (
+                "ecdsa_witness.sh",
+                r#"echo '{"cycles": 4242}' > "$COUNTS_JSON""#,
+            ),
+            (
+                "ecdsa_prove.sh",
+                r#"test -f "$STATE_JSON" && test "$FEATURE" = keccak"#,
+            ),
+            ("ecdsa_verify.sh", "true"),
+            (
+                "ecdsa_measure.sh",
+                r#"echo '{"proof_size": 512}' > "$SIZES_JSON""#,
+            ),
+        ];
+        for (name, body) in scripts {
+            fs::write(dir.join(name), body).unwrap();
+        }
+        fs::write(dir.join("circuit_sizes.json"), r#"{"ecdsa": {"32": 1000}}"#).unwrap();
+        fs::write(
+            dir.join("bench_props.json"),
+            serde_json::to_string(&crate::ProvingSystem::Barretenberg.properties()).unwrap(),
+        )
+        .unwrap();
+
+        let cfg = OrchestrateConfig {
+            system_dir: dir.clone(),
+            name: Some("fake".to_string()),
+            feature: Some("keccak".to_string()),
+            is_zkvm: false,
+            targets: Vec::new(),
+            runs: 2,
+            mem_runs: 1,
+            show_output: false,
+            utils_bin: PathBuf::from("utils"),
+        };
+        let written = orchestrate(&cfg).unwrap();
+        assert_eq!(written, [dir.join("ecdsa_32_fake_keccak_metrics.json")]);
+
+        let m = crate::schema::load_metrics_file(&written[0]).unwrap();
+        assert_eq!(m.feat.as_deref(), Some("keccak"));
+        assert_eq!((m.proof_size, m.num_constraints), (512, 1000));
+        assert!(m.proof_duration > Duration::ZERO && m.proof_duration_stddev.is_some());
+        assert!(m.peak_memory > 0);
+        assert!(m.witness_duration.is_some());
+        assert_eq!(m.cycles, Some(4242));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,651 @@
+//! Descriptive properties of each proving system and the canonical registry
+//! `ProvingSystem::properties()` that every benchmark publishes from.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::harness::ProvingSystem;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Implements `as_str`, `Display` and `FromStr` for a property enum from a
+/// single variant-to-string table. The strings match the serde names.
+macro_rules! property_enum_strings {
+    ($ty:ident, $what:literal, { $($variant:ident => $s:literal),* $(,)? }) => {
+        impl $ty {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($ty::$variant => $s,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $ty {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $ty {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<$ty, String> {
+                match s {
+                    $($s => Ok($ty::$variant),)*
+                    _ => Err(format!(concat!("Invalid ", $what, ": {}"), s)),
+                }
+            }
+        }
+    };
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum AuditStatus {
+    #[serde(rename = "audited")]
+    Audited,
+    #[serde(rename = "not_audited")]
+    NotAudited,
+    #[serde(rename = "partially_audited")]
+    PartiallyAudited,
+}
+
+property_enum_strings!(AuditStatus, "audit status", {
+    Audited => "audited",
+    NotAudited => "not_audited",
+    PartiallyAudited => "partially_audited",
+});
+
+/// How the proving/verification keys are generated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum SetupKind {
+    /// No trusted setup (hash-based or transparent PCS).
+    #[serde(rename = "transparent")]
+    Transparent,
+    /// One trusted setup (e.g. a KZG SRS) shared by all circuits up to a size bound.
+    #[serde(rename = "universal")]
+    Universal,
+    /// A trusted setup per circuit (e.g. Groth16 zkeys).
+    #[serde(rename = "circuit_specific")]
+    CircuitSpecific,
+}
+
+property_enum_strings!(SetupKind, "setup kind", {
+    Transparent => "transparent",
+    Universal => "universal",
+    CircuitSpecific => "circuit_specific",
+});
+
+/// Finite field or curve the system computes over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum FieldCurve {
+    #[serde(rename = "BN254")]
+    Bn254,
+    BabyBear,
+    Goldilocks,
+    M31,
+    #[serde(rename = "GHASH binary field")]
+    Ghash,
+}
+
+property_enum_strings!(FieldCurve, "field/curve", {
+    Bn254 => "BN254",
+    BabyBear => "BabyBear",
+    Goldilocks => "Goldilocks",
+    M31 => "M31",
+    Ghash => "GHASH binary field",
+});
+
+/// Interactive oracle proof (or proof system core) used by the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Iop {
+    #[serde(rename = "STARK")]
+    Stark,
+    #[serde(rename = "FRI")]
+    Fri,
+    #[serde(rename = "Circle FRI")]
+    CircleFri,
+    Groth16,
+    #[serde(rename = "GKR")]
+    Gkr,
+    Spartan,
+    UltraHonk,
+    Ligero,
+    Binius64,
+    Sumcheck,
+}
+
+property_enum_strings!(Iop, "IOP", {
+    Stark => "STARK",
+    Fri => "FRI",
+    CircleFri => "Circle FRI",
+    Groth16 => "Groth16",
+    Gkr => "GKR",
+    Spartan => "Spartan",
+    UltraHonk => "UltraHonk",
+    Ligero => "Ligero",
+    Binius64 => "Binius64",
+    Sumcheck => "Sumcheck",
+});
+
+/// Polynomial commitment scheme used by the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Pcs {
+    #[serde(rename = "FRI")]
+    Fri,
+    #[serde(rename = "Circle FRI")]
+    CircleFri,
+    #[serde(rename = "KZG")]
+    Kzg,
+    #[serde(rename = "WHIR")]
+    Whir,
+    Orion,
+    Ligero,
+    Binius64,
+    Dory,
+}
+
+property_enum_strings!(Pcs, "PCS", {
+    Fri => "FRI",
+    CircleFri => "Circle FRI",
+    Kzg => "KZG",
+    Whir => "WHIR",
+    Orion => "Orion",
+    Ligero => "Ligero",
+    Binius64 => "Binius64",
+    Dory => "Dory",
+});
+
+/// Arithmetization (constraint representation) used by the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum Arithmetization {
+    #[serde(rename = "AIR")]
+    Air,
+    #[serde(rename = "R1CS")]
+    R1cs,
+    Plonkish,
+    #[serde(rename = "GKR")]
+    Gkr,
+    #[serde(rename = "ACIR")]
+    Acir,
+    Ligero,
+    Binius64,
+}
+
+property_enum_strings!(Arithmetization, "arithmetization", {
+    Air => "AIR",
+    R1cs => "R1CS",
+    Plonkish => "Plonkish",
+    Gkr => "GKR",
+    Acir => "ACIR",
+    Ligero => "Ligero",
+    Binius64 => "Binius64",
+});
+
+#[skip_serializing_none]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BenchProperties {
+    // Classification
+    pub proving_system: Cow<'static, str>,
+    pub field_curve: FieldCurve,
+    pub iop: Iop,
+    pub pcs: Option<Pcs>,
+    pub arithm: Arithmetization,
+    pub is_zk: bool,
+
+    // Security
+    pub security_bits: u64,
+    pub is_pq: bool,
+
+    // Setup / trust model
+    pub setup_kind: SetupKind,
+    pub recursion_friendly: bool,
+
+    // Maintenance / audit / zk
+    pub is_maintained: bool,
+    pub is_audited: AuditStatus,
+
+    // zkVM specifics
+    pub isa: Option<Cow<'static, str>>,
+
+    // Version of the prover that produced the results; absent in results
+    // collected before it was recorded.
+    pub proof_system_version: Option<Cow<'static, str>>,
+}
+
+impl BenchProperties {
+    #[allow(clippy::too_many_arguments)]
+    /// Create a new BenchProperties struct.
+    /// # Arguments
+    /// * `proving_system` - The proving system name.
+    /// * `field_curve` - The finite field or curve used by the system.
+    /// * `iop` - The IOP used by the system.
+    /// * `pcs` - The PCS used by the system (if applicable).
+    /// * `arithm` - The arithmetization used by the system.
+    /// * `is_zk` - Whether the proofs are zero-knowledge.
+    /// * `security_bits` - The security (soundness) parameter of the system.
+    /// * `is_pq` - Whether the system is post-quantum-sound.
+    /// * `setup_kind` - Whether the setup is transparent, universal or circuit-specific.
+    /// * `recursion_friendly` - Whether the proof is succinct, or can be recursively
+    ///   compressed into a succinct proof, for on-chain verification.
+    /// * `is_maintained` - Whether the system codebase is maintained.
+    /// * `is_audited` - The audit status of the system.
+    /// * `isa` - The instruction set architecture of the system (for zkVMs).
+    /// * `proof_system_version` - The prover library version (crate/tag/commit) benchmarked.
+    pub const fn new(
+        proving_system: &'static str,
+        field_curve: FieldCurve,
+        iop: Iop,
+        pcs: Option<Pcs>,
+        arithm: Arithmetization,
+        is_zk: bool,
+        security_bits: u64,
+        is_pq: bool,
+        setup_kind: SetupKind,
+        recursion_friendly: bool,
+        is_maintained: bool,
+        is_audited: AuditStatus,
+        isa: Option<&'static str>,
+        proof_system_version: Option<&'static str>,
+    ) -> Self {
+        // Serde deserialization default implementation does not allow static strings, so we need to convert them to Cow::Borrowed.
+        Self {
+            proving_system: Cow::Borrowed(proving_system),
+            field_curve,
+            iop,
+            pcs,
+            arithm,
+            is_zk,
+            security_bits,
+            is_pq,
+            setup_kind,
+            recursion_friendly,
+            is_maintained,
+            is_audited,
+            isa: match isa {
+                Some(isa) => Some(Cow::Borrowed(isa)),
+                None => None,
+            },
+            proof_system_version: match proof_system_version {
+                Some(version) => Some(Cow::Borrowed(version)),
+                None => None,
+            },
+        }
+    }
+
+    /// Whether proofs are succinct (polylogarithmic in the statement size).
+    /// Ligero-based proofs grow with the square root of the circuit size.
+    pub fn has_succinct_proofs(&self) -> bool {
+        self.iop != Iop::Ligero && self.pcs != Some(Pcs::Ligero)
+    }
+
+    /// Checks that the metadata is complete enough to be published.
+    /// `is_zkvm` additionally requires the ISA to be set.
+    pub fn validate(&self, is_zkvm: bool) -> Result<(), String> {
+        let mut missing = Vec::new();
+        if self.proving_system.trim().is_empty() {
+            missing.push("proving_system is empty");
+        }
+        if self.security_bits == 0 {
+            missing.push("security_bits is 0");
+        }
+        if is_zkvm && self.isa.as_deref().is_none_or(|isa| isa.trim().is_empty()) {
+            missing.push("isa is not set for a zkVM");
+        }
+        if self
+            .proof_system_version
+            .as_deref()
+            .is_none_or(|version| version.trim().is_empty())
+        {
+            missing.push("proof_system_version is not set");
+        }
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing.join(", "))
+        }
+    }
+}
+
+impl ProvingSystem {
+    /// The canonical properties published for this system.
+    pub fn properties(&self) -> BenchProperties {
+        match self {
+            ProvingSystem::Binius64 => BenchProperties::new(
+                "Binius64",
+                FieldCurve::Ghash, // https://www.binius.xyz/basics/binius64-vs-v0
+                Iop::Binius64,
+                Some(Pcs::Binius64),
+                Arithmetization::Binius64,
+                false, // https://www.irreducible.com/posts/announcing-binius64
+                96, // https://github.com/IrreducibleOSS/binius64/blob/main/verifier/verifier/src/verify.rs#L40
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                false,
+                true,
+                AuditStatus::NotAudited,
+                None,
+                Some("binius64@d8bfbd6"),
+            ),
+            ProvingSystem::Expander => BenchProperties::new(
+                "Libra",
+                FieldCurve::M31,      // See ./polyhedra-expander/src/bench.rs
+                Iop::Gkr,             // https://eprint.iacr.org/2019/317
+                Some(Pcs::Orion),     // See ./polyhedra-expander/src/bench.rs
+                Arithmetization::Gkr, // https://eprint.iacr.org/2019/317
+                false,
+                128, // https://github.com/PolyhedraZK/Expander/blob/main/poly_commit/src/lib.rs#L6
+                true, // Hash-based PCS (https://eprint.iacr.org/2022/1010.pdf)
+                SetupKind::Transparent,
+                false,
+                true,
+                AuditStatus::NotAudited,
+                None,
+                Some("Expander@af1b747"), // the commit Cargo.lock resolves `branch = "main"` to
+            ),
+            ProvingSystem::Plonky2 => BenchProperties::new(
+                "Plonky2", // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                FieldCurve::Goldilocks, // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                Iop::Fri, // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                Some(Pcs::Fri), // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                Arithmetization::Plonkish, // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                true, // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/plonky2.pdf
+                100,  // https://github.com/0xPolygonZero/plonky2?tab=readme-ov-file#security
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                true,                 // designed for recursion
+                false, // deprecated: https://github.com/0xPolygonZero/plonky2?tab=readme-ov-file#%EF%B8%8F-plonky2-deprecation-notice
+                AuditStatus::Audited, // https://github.com/0xPolygonZero/plonky2/tree/main/audits
+                None,
+                Some("plonky2 1.0.2"),
+            ),
+            ProvingSystem::OpenVM => BenchProperties::new(
+                "STARK",
+                FieldCurve::BabyBear, // https://docs.openvm.dev/whitepaper.pdf
+                Iop::Stark,           // https://docs.openvm.dev/whitepaper.pdf
+                Some(Pcs::Fri),       // https://docs.openvm.dev/whitepaper.pdf
+                Arithmetization::Air, // https://docs.openvm.dev/whitepaper.pdf
+                false,                // STARK proofs are not zero-knowledge
+                100,                  // https://docs.openvm.dev/book/advanced-usage/security
+                true,                 // hash-based PCS
+                SetupKind::Transparent,
+                true, // STARK aggregation + Halo2 wrapper for on-chain verification
+                true, // https://github.com/openvm-org/openvm/releases
+                AuditStatus::Audited, // https://github.com/openvm-org/openvm/tree/main/audits
+                Some("RISC-V RV32IM"), // https://docs.openvm.dev/specs/openvm/isa
+                Some("ere-openvm v0.0.13"),
+            ),
+            ProvingSystem::Provekit => BenchProperties::new(
+                "Spartan+WHIR",        // https://github.com/worldfnd/provekit
+                FieldCurve::Bn254,     // https://github.com/worldfnd/provekit
+                Iop::Spartan,          // https://github.com/worldfnd/provekit
+                Some(Pcs::Whir),       // https://github.com/worldfnd/provekit
+                Arithmetization::R1cs, // https://github.com/worldfnd/provekit
+                true,                  // https://github.com/worldfnd/provekit/pull/138
+                128, // https://github.com/worldfnd/provekit/blob/d7deea66c41d56c1d411dd799d0d6066272323e4/provekit/r1cs-compiler/src/whir_r1cs.rs#L43
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                false,
+                true, // https://github.com/worldfnd/provekit
+                AuditStatus::NotAudited,
+                None,
+                Some("ProveKit@d7deea6"),
+            ),
+            ProvingSystem::Circom => BenchProperties::new(
+                "Groth16",
+                FieldCurve::Bn254,
+                Iop::Groth16,
+                None,
+                Arithmetization::R1cs,
+                true,
+                128, // Bn254 curve
+                false,
+                SetupKind::CircuitSpecific, // per-circuit Groth16 zkey
+                true,                       // constant-size proof
+                true,
+                AuditStatus::PartiallyAudited, // e.g., https://veridise.com/wp-content/uploads/2023/02/VAR-circom-bigint.pdf
+                None,
+                Some("circom-prover 0.1"),
+            ),
+            ProvingSystem::Risc0 => BenchProperties::new(
+                "STARK",
+                FieldCurve::BabyBear, // 15 × 2^27 + 1; https://dev.risczero.com/proof-system-in-detail.pdf
+                Iop::Stark,           // https://dev.risczero.com/proof-system/stark-by-hand
+                Some(Pcs::Fri),       // https://dev.risczero.com/proof-system/stark-by-hand
+                Arithmetization::Air, // https://dev.risczero.com/proof-system/proof-system-sequence-diagram
+                true,                 // https://dev.risczero.com/api/security-model
+                96, // 96-bit base STARK, 99-bit recursion; https://dev.risczero.com/api/security-model
+                true, // STARK is PQ-safe (Groth16 compression is not); https://dev.risczero.com/api/security-model
+                SetupKind::Transparent,
+                true,                  // succinct receipts and Groth16 compression
+                true,                  // https://github.com/risc0/risc0/releases
+                AuditStatus::Audited,  // https://github.com/risc0/rz-security/tree/main/audits
+                Some("RISC-V RV32IM"), // base + multiplication; https://dev.risczero.com/reference-docs/about-risc-v
+                Some("ere-risc0 v0.0.13"),
+            ),
+            ProvingSystem::Sp1 => BenchProperties::new(
+                "STARK",
+                FieldCurve::BabyBear, // https://docs.succinct.xyz/docs/sp1/security/security-model
+                Iop::Stark,
+                Some(Pcs::Fri),
+                Arithmetization::Air,
+                false, // only the Groth16/PLONK wrappers are zero-knowledge
+                100,   // https://docs.succinct.xyz/docs/sp1/security/security-model
+                true,  // hash-based PCS
+                SetupKind::Transparent,
+                true,                  // compressed proofs and Groth16/PLONK wrappers
+                true,                  // https://github.com/succinctlabs/sp1/releases
+                AuditStatus::Audited,  // https://github.com/succinctlabs/sp1/tree/dev/audits
+                Some("RISC-V RV32IM"), // https://docs.succinct.xyz/docs/sp1/introduction
+                Some("ere-sp1 v0.0.13"),
+            ),
+            ProvingSystem::Jolt => BenchProperties::new(
+                "Jolt",
+                FieldCurve::Bn254,     // https://jolt.a16zcrypto.com/
+                Iop::Sumcheck, // lookup-centric sum-check IOP; https://eprint.iacr.org/2023/1217
+                Some(Pcs::Dory), // https://jolt.a16zcrypto.com/
+                Arithmetization::R1cs, // R1CS + lookups; https://jolt.a16zcrypto.com/
+                false,         // zero-knowledge is not implemented yet
+                128,
+                false,                  // pairing-based PCS
+                SetupKind::Transparent, // Dory needs no trusted setup
+                false,
+                true, // https://github.com/a16z/jolt
+                AuditStatus::NotAudited,
+                Some("RISC-V RV32IM"), // https://jolt.a16zcrypto.com/
+                Some("ere-jolt v0.0.13"),
+            ),
+            ProvingSystem::Miden => BenchProperties::new(
+                "STARK",
+                FieldCurve::Goldilocks, // 2^64 - 2^32 + 1; https://0xmiden.github.io/miden-vm/design/main.html#design
+                Iop::Stark,             // https://0xmiden.github.io/miden-vm/intro/main.html
+                Some(Pcs::Fri), // https://0xmiden.github.io/miden-vm/user_docs/assembly/cryptographic_operations.html#fri-folding
+                Arithmetization::Air, // https://0xmiden.github.io/miden-vm/design/chiplets/hasher.html?highlight=AIR#air-constraints
+                true,                 // https://github.com/0xPolygonMiden/miden-vm
+                128, // Target security = 128 bits (bench config); https://github.com/eth-act/ere/blob/ae6baa03c157512b905bf393c0f94b6b9b3b420c/crates/zkvm/miden/src/zkvm.rs#L100
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                true,                    // recursive verifier in Miden assembly
+                true,                    // https://github.com/0xPolygonMiden/miden-vm/releases
+                AuditStatus::NotAudited, // https://github.com/0xPolygonMiden/miden-vm
+                Some("Miden"), // stack-based ISA with MAST; https://hackmd.io/@bobbinth/ry-OIBwPF
+                Some("ere-miden v0.0.13"),
+            ),
+            ProvingSystem::CairoM => BenchProperties::new(
+                "Circle STARK", // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#welcome-to-cairo-m
+                FieldCurve::M31, // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#welcome-to-cairo-m
+                Iop::CircleFri,  // https://eprint.iacr.org/2024/278.pdf
+                Some(Pcs::CircleFri), // https://eprint.iacr.org/2024/278.pdf
+                Arithmetization::Air,
+                false,
+                96, // https://github.com/kkrt-labs/cairo-m/blob/main/crates/prover/src/prover_config.rs#L13-L20
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                false,
+                true,                    // https://github.com/kkrt-labs
+                AuditStatus::NotAudited, // https://github.com/kkrt-labs/cairo-m/?tab=readme-ov-file#about
+                Some("Cairo ISA"), // https://github.com/kkrt-labs/cairo-m/blob/main/docs/design.md
+                Some("cairo-m@79c1b1e"),
+            ),
+            ProvingSystem::Nexus => BenchProperties::new(
+                "Circle STARK",
+                FieldCurve::M31,      // 2^31 - 1; https://specification.nexus.xyz/
+                Iop::CircleFri,       // https://eprint.iacr.org/2024/278.pdf
+                Some(Pcs::CircleFri), // https://eprint.iacr.org/2024/278.pdf
+                Arithmetization::Air, // https://specification.nexus.xyz/
+                true,                 // https://whitepaper.nexus.xyz/
+                0,    // TODO: https://github.com/privacy-ethereum/csp-benchmarks/issues/147
+                true, // hash-based PCS
+                SetupKind::Transparent,
+                false,
+                true,                    // https://github.com/nexus-xyz/nexus-zkvm/releases
+                AuditStatus::NotAudited, // https://github.com/nexus-xyz/nexus-zkvm
+                Some("RISC-V RV32I"),    // base ISA + precompiles; https://specification.nexus.xyz/
+                Some("ere-nexus v0.0.13"),
+            ),
+            ProvingSystem::Barretenberg => BenchProperties::new(
+                "UltraHonk",
+                FieldCurve::Bn254,
+                Iop::UltraHonk,
+                Some(Pcs::Kzg),
+                Arithmetization::Acir,
+                true,
+                128,
+                false,
+                SetupKind::Universal, // KZG SRS
+                true,                 // constant-size proof, native recursion
+                true,
+                AuditStatus::NotAudited,
+                None,
+                Some("bb v0.87.0, nargo 1.0.0-beta.13"),
+            ),
+            ProvingSystem::Ligetron => BenchProperties::new(
+                "Ligero",
+                FieldCurve::Bn254,
+                Iop::Ligero,
+                Some(Pcs::Ligero),
+                Arithmetization::Ligero,
+                true,
+                128,
+                true,
+                SetupKind::Transparent,
+                false,
+                true,
+                AuditStatus::NotAudited,
+                Some("WASM"),
+                Some("ligero-prover (git submodule)"),
+            ),
+        }
+    }
+
+    /// The canonical properties, or an error describing what is missing
+    /// before this system may publish results.
+    pub fn validated_properties(&self) -> Result<BenchProperties, String> {
+        let properties = self.properties();
+        properties
+            .validate(self.is_zkvm())
+            .map_err(|e| format!("incomplete bench properties for {}: {}", self.as_str(), e))?;
+        Ok(properties)
+    }
+}
+
+/// Returns the properties to publish for `proving_system`.
+///
+/// Registered systems always publish `ProvingSystem::properties()`; a
+/// `bench_props.json` given alongside must agree with the registry.
+/// Unregistered systems must provide the file. Either way, the properties
+/// are validated before anything is written.
+pub fn resolve_properties(
+    proving_system: &str,
+    properties_file: Option<&Path>,
+    is_zkvm: bool,
+) -> io::Result<BenchProperties> {
+    let given = properties_file.map(load_properties_json).transpose()?;
+    let source = properties_file.map_or_else(String::new, |f| f.display().to_string());
+    resolve_properties_from(proving_system, &source, given, is_zkvm)
+}
+
+/// `resolve_properties` for properties given another way than a file
+/// (e.g. command line flags), described by `source` in errors.
+pub fn resolve_properties_from(
+    proving_system: &str,
+    source: &str,
+    given: Option<BenchProperties>,
+    is_zkvm: bool,
+) -> io::Result<BenchProperties> {
+    let properties = match ProvingSystem::from_str(proving_system) {
+        Ok(system) => {
+            let registered = system.validated_properties().map_err(io::Error::other)?;
+            if let Some(props) = &given
+                && *props != registered
+            {
+                return Err(io::Error::other(format!(
+                    "properties from {source} do not match the registered properties of {proving_system}"
+                )));
+            }
+            registered
+        }
+        Err(_) => given.ok_or_else(|| {
+            io::Error::other(format!(
+                "{proving_system} is not a registered proving system and no properties were provided"
+            ))
+        })?,
+    };
+    properties.validate(is_zkvm).map_err(|e| {
+        io::Error::other(format!(
+            "incomplete bench properties for {proving_system}: {e}"
+        ))
+    })?;
+    Ok(properties)
+}
+
+/// Whether `proving_system` is a zkVM: the registry's answer for registered
+/// systems, `is_zkvm` (the `--is-zkvm` flag) for the others.
+pub fn resolve_is_zkvm(proving_system: &str, is_zkvm: bool) -> bool {
+    ProvingSystem::from_str(proving_system).map_or(is_zkvm, |system| system.is_zkvm())
+}
+
+fn load_properties_json(path: &Path) -> io::Result<BenchProperties> {
+    let s = fs::read_to_string(path)?;
+    serde_json::from_str::<BenchProperties>(&s).map_err(io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_complete() {
+        for system in ProvingSystem::ALL {
+            // Security level not yet determined, see the TODO in the registry.
+            if matches!(system, ProvingSystem::Nexus) {
+                assert!(system.validated_properties().is_err());
+                continue;
+            }
+            system.validated_properties().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_resolve_is_zkvm() {
+        assert!(resolve_is_zkvm("ligetron", false));
+        assert!(!resolve_is_zkvm("barretenberg", true));
+        assert!(resolve_is_zkvm("my-zkvm", true));
+        assert!(!resolve_is_zkvm("my-circuit", false));
+    }
+
+    #[test]
+    fn test_property_strings_roundtrip_serde() {
+        let props = ProvingSystem::Barretenberg.properties();
+        let json = serde_json::to_value(&props).unwrap();
+        assert_eq!(json["field_curve"], props.field_curve.as_str());
+        assert_eq!(json["pcs"], Pcs::Kzg.as_str());
+        assert_eq!(json["arithm"], props.arithm.as_str());
+        assert_eq!("Circle FRI".parse::<Iop>(), Ok(Iop::CircleFri));
+    }
+}
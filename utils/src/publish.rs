@@ -0,0 +1,374 @@
+//! Export of collected results to the ethproofs.org CSP benchmarks page.
+//!
+//! The site ingests a `collected_benchmarks.json`-style array uploaded as a
+//! multipart form (`file` and `filename` fields, bearer token auth). Rows are
+//! converted to [`UploadEntry`], which keeps the fields the site renders and
+//! drops the ones only used locally (derived metrics, standard deviations,
+//! device tags). [`MockServer`] mimics the endpoint so the request flow can be
+//! exercised offline.
+
+use crate::bench::Metrics;
+use crate::harness::{BenchProperties, BenchTarget};
+use serde::Serialize;
+use serde_with::skip_serializing_none;
+use serde_with::{DurationNanoSeconds, serde_as};
+use std::collections::BTreeSet;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub const DEFAULT_URL: &str = "https://ethproofs.org/api/v0/csp-benchmarks/upload";
+pub const API_KEY_ENV: &str = "ETHPROOFS_API_KEY";
+
+const BOUNDARY: &str = "csp-benchmarks-upload-boundary";
+
+/// One row of the upload payload.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Serialize, Clone)]
+pub struct UploadEntry {
+    pub schema_version: u32,
+    pub name: String,
+    pub feat: Option<String>,
+    pub is_zkvm: bool,
+    pub target: String,
+    pub input_size: usize,
+    #[serde_as(as = "DurationNanoSeconds")]
+    pub proof_duration: Duration,
+    #[serde_as(as = "DurationNanoSeconds")]
+    pub verify_duration: Duration,
+    pub cycles: Option<u64>,
+    pub proof_size: usize,
+    pub preprocessing_size: usize,
+    pub num_constraints: usize,
+    pub peak_memory: usize,
+    #[serde(flatten)]
+    pub bench_properties: BenchProperties,
+}
+
+impl From<&Metrics> for UploadEntry {
+    fn from(m: &Metrics) -> Self {
+        UploadEntry {
+            schema_version: m.schema_version,
+            name: m.name.clone(),
+            feat: m.feat.clone().filter(|f| !f.is_empty()),
+            is_zkvm: m.is_zkvm,
+            target: m.target.clone(),
+            input_size: m.input_size,
+            proof_duration: m.proof_duration,
+            verify_duration: m.verify_duration,
+            cycles: m.cycles,
+            proof_size: m.proof_size,
+            preprocessing_size: m.preprocessing_size,
+            num_constraints: m.num_constraints,
+            peak_memory: m.peak_memory,
+            bench_properties: m.bench_properties.clone(),
+        }
+    }
+}
+
+/// Checks that every entry can be rendered by the site. Returns one message
+/// per problem.
+pub fn validate(entries: &[UploadEntry]) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    if entries.is_empty() {
+        errors.push("payload has no entries".to_string());
+    }
+    let mut seen = BTreeSet::new();
+    for e in entries {
+        let row = format!("{} {} {}", e.name, e.target, e.input_size);
+        if e.name.is_empty() {
+            errors.push(format!("{row}: empty system name"));
+        }
+        if !BenchTarget::ALL.iter().any(|t| t.as_str() == e.target) {
+            errors.push(format!("{row}: unknown target `{}`", e.target));
+        }
+        if e.input_size == 0 {
+            errors.push(format!("{row}: input size is 0"));
+        }
+        if e.proof_duration.is_zero() || e.verify_duration.is_zero() {
+            errors.push(format!("{row}: prove or verify time is 0"));
+        }
+        if e.proof_size == 0 {
+            errors.push(format!("{row}: proof size is 0"));
+        }
+        if !seen.insert((&e.name, &e.feat, &e.target, e.input_size)) {
+            errors.push(format!(
+                "{row}: duplicate entry (results of several devices? select one with --device)"
+            ));
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// A fully rendered upload request, without credentials.
+pub struct UploadRequest {
+    pub url: String,
+    pub filename: String,
+    pub json: String,
+}
+
+impl UploadRequest {
+    /// Converts and validates `results`.
+    pub fn new(url: &str, filename: &str, results: &[Metrics]) -> Result<Self, Vec<String>> {
+        let entries: Vec<UploadEntry> = results.iter().map(UploadEntry::from).collect();
+        validate(&entries)?;
+        Ok(UploadRequest {
+            url: url.to_string(),
+            filename: filename.to_string(),
+            json: serde_json::to_string_pretty(&entries).expect("serialize upload entries"),
+        })
+    }
+
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={BOUNDARY}")
+    }
+
+    /// The multipart body, in the same shape as `curl -F file=@... -F filename=...`.
+    pub fn body(&self) -> Vec<u8> {
+        format!(
+            "--{BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"file\"; filename=\"{name}.json\"\r\n\
+             Content-Type: application/json\r\n\r\n\
+             {json}\r\n\
+             --{BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"filename\"\r\n\r\n\
+             {name}\r\n\
+             --{BOUNDARY}--\r\n",
+            name = self.filename,
+            json = self.json,
+        )
+        .into_bytes()
+    }
+
+    /// The exact request as it would be sent, with the token redacted.
+    pub fn render(&self) -> String {
+        format!(
+            "POST {}\nAuthorization: Bearer ***\nContent-Type: {}\n\n{}",
+            self.url,
+            self.content_type(),
+            String::from_utf8_lossy(&self.body())
+        )
+    }
+
+    /// POSTs the payload and returns the response body. Non-2xx responses
+    /// are errors.
+    pub fn send(&self, api_key: &str) -> Result<String, String> {
+        let response = ureq::post(&self.url)
+            .set("Authorization", &format!("Bearer {api_key}"))
+            .set("Content-Type", &self.content_type())
+            .send_bytes(&self.body());
+        match response {
+            Ok(resp) => resp.into_string().map_err(|e| e.to_string()),
+            Err(ureq::Error::Status(code, resp)) => Err(format!(
+                "HTTP {code}: {}",
+                resp.into_string().unwrap_or_default()
+            )),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// A request received by [`MockServer`].
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Content of the multipart form field `name`.
+    pub fn form_field(&self, name: &str) -> Option<String> {
+        let boundary = self.header("Content-Type")?.split("boundary=").nth(1)?;
+        let body = String::from_utf8_lossy(&self.body);
+        body.split(&format!("--{boundary}")).find_map(|part| {
+            let (headers, content) = part.split_once("\r\n\r\n")?;
+            headers
+                .contains(&format!("name=\"{name}\""))
+                .then(|| content.trim_end_matches("\r\n").to_string())
+        })
+    }
+}
+
+/// Local stand-in for the upload endpoint. Serves a fixed number of requests
+/// on a loopback port and answers like the site: 401 without a bearer token,
+/// 400 unless the `file` field is a JSON array, 200 otherwise.
+pub struct MockServer {
+    addr: SocketAddr,
+    handle: JoinHandle<io::Result<Vec<RecordedRequest>>>,
+}
+
+impl MockServer {
+    pub fn start(requests: usize) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let handle = thread::spawn(move || {
+            let mut recorded = Vec::with_capacity(requests);
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream?;
+                let request = read_request(&mut stream)?;
+                let (status, body) = mock_response(&request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )?;
+                recorded.push(request);
+            }
+            Ok(recorded)
+        });
+        Ok(MockServer { addr, handle })
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}/api/v0/csp-benchmarks/upload", self.addr)
+    }
+
+    /// Waits for all requests and returns them.
+    pub fn finish(self) -> io::Result<Vec<RecordedRequest>> {
+        self.handle.join().expect("mock server thread panicked")
+    }
+}
+
+fn read_request(stream: &mut impl Read) -> io::Result<RecordedRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let Some((name, value)) = line.trim_end().split_once(':') else {
+            break;
+        };
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+    let len = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("Content-Length"))
+        .and_then(|(_, v)| v.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+    Ok(RecordedRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+fn mock_response(request: &RecordedRequest) -> (&'static str, &'static str) {
+    let authorized = request
+        .header("Authorization")
+        .is_some_and(|v| v.starts_with("Bearer ") && v.len() > "Bearer ".len());
+    if request.method != "POST" {
+        return (
+            "405 Method Not Allowed",
+            r#"{"error":"method not allowed"}"#,
+        );
+    }
+    if !authorized {
+        return ("401 Unauthorized", r#"{"error":"missing bearer token"}"#);
+    }
+    let valid = request
+        .form_field("file")
+        .and_then(|f| serde_json::from_str::<serde_json::Value>(&f).ok())
+        .is_some_and(|v| v.is_array())
+        && request.form_field("filename").is_some();
+    if valid {
+        ("200 OK", r#"{"status":"ok"}"#)
+    } else {
+        ("400 Bad Request", r#"{"error":"invalid payload"}"#)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    fn row(system: ProvingSystem, input_size: usize) -> Metrics {
+        let mut m = Metrics::new(
+            system.as_str().to_string(),
+            None,
+            system.is_zkvm(),
+            "sha256".to_string(),
+            input_size,
+            system.properties(),
+        );
+        m.proof_duration = Duration::from_secs(2);
+        m.verify_duration = Duration::from_millis(20);
+        m.proof_size = 1 << 16;
+        m.proof_duration_stddev = Some(Duration::from_millis(30));
+        m.device = Some("c7i.8xlarge".to_string());
+        m
+    }
+
+    #[test]
+    fn test_conversion_and_validation() {
+        let results = [row(ProvingSystem::Sp1, 128), row(ProvingSystem::Sp1, 2048)];
+        let request = UploadRequest::new(DEFAULT_URL, "collected-benchmarks-1", &results).unwrap();
+        let entries: serde_json::Value = serde_json::from_str(&request.json).unwrap();
+        let first = &entries[0];
+        assert_eq!(first["proof_duration"], 2_000_000_000u64);
+        assert_eq!(
+            first["proving_system"],
+            ProvingSystem::Sp1.properties().proving_system.as_ref()
+        );
+        assert!(first.get("device").is_none());
+        assert!(first.get("proof_duration_stddev").is_none());
+
+        // The same row from two devices collides once the device is dropped.
+        let mut other = row(ProvingSystem::Sp1, 128);
+        other.device = Some("mac2.metal".to_string());
+        other.proof_size = 0;
+        let errors = UploadRequest::new(DEFAULT_URL, "x", &[results[0].clone(), other])
+            .err()
+            .unwrap();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].contains("duplicate"));
+    }
+
+    #[test]
+    fn test_upload_to_mock_server() {
+        let server = MockServer::start(2).unwrap();
+        let request = UploadRequest::new(
+            &server.url(),
+            "collected-benchmarks-42",
+            &[row(ProvingSystem::Risc0, 128)],
+        )
+        .unwrap();
+        assert_eq!(request.send("secret").unwrap(), r#"{"status":"ok"}"#);
+        assert!(request.send("").unwrap_err().starts_with("HTTP 401"));
+
+        let recorded = server.finish().unwrap();
+        let first = &recorded[0];
+        assert_eq!(first.path, "/api/v0/csp-benchmarks/upload");
+        assert_eq!(first.header("Authorization"), Some("Bearer secret"));
+        assert_eq!(
+            first.form_field("filename").as_deref(),
+            Some("collected-benchmarks-42")
+        );
+        assert_eq!(first.form_field("file").unwrap(), request.json);
+    }
+}
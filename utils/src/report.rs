@@ -0,0 +1,494 @@
+//! Renders collected benchmark results (`collected_benchmarks.json`) into
+//! human-readable comparison reports: Markdown and CSV tables per target and
+//! input size, and a self-contained static HTML page with SVG charts.
+
+use crate::bench::{Metrics, display_bytes, display_cycles, display_duration, display_string};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use tabled::{Table, Tabled, builder::Builder, settings::Style};
+
+/// One row of a comparison table.
+#[derive(Tabled)]
+struct ComparisonRow {
+    #[tabled(rename = "system")]
+    name: String,
+    #[tabled(rename = "feature", display_with = "display_string")]
+    feat: Option<String>,
+    #[tabled(rename = "device", display_with = "display_string")]
+    device: Option<String>,
+    #[tabled(rename = "prove", display_with = "display_duration")]
+    proof_duration: std::time::Duration,
+    #[tabled(rename = "verify", display_with = "display_duration")]
+    verify_duration: std::time::Duration,
+    #[tabled(rename = "peak memory", display_with = "display_bytes")]
+    peak_memory: usize,
+    #[tabled(rename = "proof size", display_with = "display_bytes")]
+    proof_size: usize,
+    #[tabled(rename = "preprocessing size", display_with = "display_bytes")]
+    preprocessing_size: usize,
+    #[tabled(rename = "constraints")]
+    num_constraints: usize,
+    #[tabled(rename = "cycles", display_with = "display_cycles")]
+    cycles: Option<u64>,
+}
+
+impl From<&Metrics> for ComparisonRow {
+    fn from(m: &Metrics) -> Self {
+        ComparisonRow {
+            name: m.name.clone(),
+            feat: m.feat.clone(),
+            device: m.device.clone(),
+            proof_duration: m.proof_duration,
+            verify_duration: m.verify_duration,
+            peak_memory: m.peak_memory,
+            proof_size: m.proof_size,
+            preprocessing_size: m.preprocessing_size,
+            num_constraints: m.num_constraints,
+            cycles: m.cycles,
+        }
+    }
+}
+
+/// Flat CSV record with raw (unformatted) values.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    target: &'a str,
+    input_size: usize,
+    name: &'a str,
+    feat: &'a str,
+    device: &'a str,
+    is_zkvm: bool,
+    proof_duration_ns: u128,
+    verify_duration_ns: u128,
+    peak_memory: usize,
+    proof_size: usize,
+    preprocessing_size: usize,
+    num_constraints: usize,
+    cycles: Option<u64>,
+}
+
+/// Groups results by (target, input size), each group sorted by prove time.
+pub fn group_by_target_and_size(results: &[Metrics]) -> BTreeMap<(&str, usize), Vec<&Metrics>> {
+    let mut groups: BTreeMap<(&str, usize), Vec<&Metrics>> = BTreeMap::new();
+    for m in results {
+        groups
+            .entry((m.target.as_str(), m.input_size))
+            .or_default()
+            .push(m);
+    }
+    for rows in groups.values_mut() {
+        rows.sort_by_key(|m| m.proof_duration);
+    }
+    groups
+}
+
+/// Label identifying a system (and feature variant and device) in charts.
+pub fn series_label(m: &Metrics) -> String {
+    let mut label = match m.feat.as_deref() {
+        Some(f) if !f.is_empty() => format!("{} ({})", m.name, f),
+        _ => m.name.clone(),
+    };
+    if let Some(device) = &m.device {
+        let _ = write!(label, " @ {device}");
+    }
+    label
+}
+
+/// Distinct device tags of the results (untagged rows count as `-`).
+fn devices(results: &[Metrics]) -> BTreeSet<String> {
+    results
+        .iter()
+        .map(|m| m.device.clone().unwrap_or_else(|| "-".to_string()))
+        .collect()
+}
+
+/// Markdown report with one comparison table per target and input size. When
+/// the results span several devices, a pivot of prove times by device follows.
+pub fn render_markdown(results: &[Metrics]) -> String {
+    let mut out = String::from("# Benchmark report\n");
+    for ((target, size), rows) in group_by_target_and_size(results) {
+        let mut table = Table::new(rows.into_iter().map(ComparisonRow::from));
+        table.with(Style::markdown());
+        let _ = write!(out, "\n## {target}, {size} bytes\n\n{table}\n");
+    }
+    if devices(results).len() > 1 {
+        out.push_str(&render_device_pivot(results));
+    }
+    out
+}
+
+/// Markdown tables of prove time per system (rows) and device (columns), one
+/// per target and input size.
+pub fn render_device_pivot(results: &[Metrics]) -> String {
+    let devices = devices(results);
+    let mut out = String::from("\n# Prove time by device\n");
+    for ((target, size), rows) in group_by_target_and_size(results) {
+        let mut cells: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        for m in rows {
+            let system = match m.feat.as_deref() {
+                Some(f) if !f.is_empty() => format!("{} ({f})", m.name),
+                _ => m.name.clone(),
+            };
+            let device = m.device.clone().unwrap_or_else(|| "-".to_string());
+            cells
+                .entry(system)
+                .or_default()
+                .insert(device, display_duration(&m.proof_duration));
+        }
+        let mut builder = Builder::default();
+        builder.push_record(std::iter::once("system".to_string()).chain(devices.iter().cloned()));
+        for (system, by_device) in cells {
+            builder.push_record(
+                std::iter::once(system).chain(
+                    devices
+                        .iter()
+                        .map(|d| by_device.get(d).cloned().unwrap_or_else(|| "-".to_string())),
+                ),
+            );
+        }
+        let mut table = builder.build();
+        table.with(Style::markdown());
+        let _ = write!(out, "\n## {target}, {size} bytes\n\n{table}\n");
+    }
+    out
+}
+
+/// CSV report with one row per result, ordered by target and input size.
+pub fn render_csv(results: &[Metrics]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    for rows in group_by_target_and_size(results).into_values() {
+        for m in rows {
+            writer.serialize(CsvRow {
+                target: &m.target,
+                input_size: m.input_size,
+                name: &m.name,
+                feat: m.feat.as_deref().unwrap_or(""),
+                device: m.device.as_deref().unwrap_or(""),
+                is_zkvm: m.is_zkvm,
+                proof_duration_ns: m.proof_duration.as_nanos(),
+                verify_duration_ns: m.verify_duration.as_nanos(),
+                peak_memory: m.peak_memory,
+                proof_size: m.proof_size,
+                preprocessing_size: m.preprocessing_size,
+                num_constraints: m.num_constraints,
+                cycles: m.cycles,
+            })?;
+        }
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8(bytes).expect("CSV output is UTF-8"))
+}
+
+/// A metric plotted against input size in the HTML report.
+struct ChartMetric {
+    title: &'static str,
+    value: fn(&Metrics) -> f64,
+    format: fn(f64) -> String,
+}
+
+const CHART_METRICS: [ChartMetric; 3] = [
+    ChartMetric {
+        title: "Prove time",
+        value: |m| m.proof_duration.as_secs_f64(),
+        format: |v| display_duration(&std::time::Duration::from_secs_f64(v)),
+    },
+    ChartMetric {
+        title: "Peak memory",
+        value: |m| m.peak_memory as f64,
+        format: |v| display_bytes(&(v as usize)),
+    },
+    ChartMetric {
+        title: "Proof size",
+        value: |m| m.proof_size as f64,
+        format: |v| display_bytes(&(v as usize)),
+    },
+];
+
+const PALETTE: [&str; 12] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf", "#393b79", "#ad494a",
+];
+
+const CHART_WIDTH: f64 = 640.0;
+const CHART_HEIGHT: f64 = 360.0;
+const MARGIN_LEFT: f64 = 80.0;
+const MARGIN_RIGHT: f64 = 170.0;
+const MARGIN_TOP: f64 = 30.0;
+const MARGIN_BOTTOM: f64 = 45.0;
+
+/// Renders one SVG line chart of `metric` vs input size, one series per
+/// system. Both axes are logarithmic; non-positive values are skipped.
+fn render_chart(metric: &ChartMetric, rows: &[&Metrics]) -> String {
+    let mut series: BTreeMap<String, Vec<(f64, f64)>> = BTreeMap::new();
+    for m in rows {
+        let y = (metric.value)(m);
+        if y > 0.0 && m.input_size > 0 {
+            series
+                .entry(series_label(m))
+                .or_default()
+                .push((m.input_size as f64, y));
+        }
+    }
+    for points in series.values_mut() {
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+
+    let points = series.values().flatten();
+    let (x_min, x_max) = log_bounds(points.clone().map(|p| p.0), 2.0);
+    let (y_min, y_max) = log_bounds(points.map(|p| p.1), 10.0);
+
+    let plot_w = CHART_WIDTH - MARGIN_LEFT - MARGIN_RIGHT;
+    let plot_h = CHART_HEIGHT - MARGIN_TOP - MARGIN_BOTTOM;
+    let sx = |x: f64| MARGIN_LEFT + scale_log(x, x_min, x_max) * plot_w;
+    let sy = |y: f64| MARGIN_TOP + (1.0 - scale_log(y, y_min, y_max)) * plot_h;
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{CHART_WIDTH}" height="{CHART_HEIGHT}" viewBox="0 0 {CHART_WIDTH} {CHART_HEIGHT}" font-family="sans-serif" font-size="11">"#
+    );
+    let _ = write!(
+        svg,
+        r#"<text x="{}" y="18" font-size="13" font-weight="bold">{}</text>"#,
+        MARGIN_LEFT, metric.title
+    );
+    let _ = write!(
+        svg,
+        r##"<rect x="{MARGIN_LEFT}" y="{MARGIN_TOP}" width="{plot_w}" height="{plot_h}" fill="none" stroke="#999"/>"##
+    );
+
+    // Axis ticks at powers of two (x) and powers of ten (y).
+    let mut x = x_min;
+    while x <= x_max * 1.0001 {
+        let px = sx(x);
+        let _ = write!(
+            svg,
+            r##"<line x1="{px:.1}" y1="{}" x2="{px:.1}" y2="{}" stroke="#eee"/><text x="{px:.1}" y="{}" text-anchor="middle">{}</text>"##,
+            MARGIN_TOP,
+            MARGIN_TOP + plot_h,
+            MARGIN_TOP + plot_h + 15.0,
+            x as u64
+        );
+        x *= 2.0;
+    }
+    let mut y = y_min;
+    while y <= y_max * 1.0001 {
+        let py = sy(y);
+        let _ = write!(
+            svg,
+            r##"<line x1="{}" y1="{py:.1}" x2="{}" y2="{py:.1}" stroke="#eee"/><text x="{}" y="{:.1}" text-anchor="end">{}</text>"##,
+            MARGIN_LEFT,
+            MARGIN_LEFT + plot_w,
+            MARGIN_LEFT - 5.0,
+            py + 4.0,
+            escape_html(&(metric.format)(y))
+        );
+        y *= 10.0;
+    }
+    let _ = write!(
+        svg,
+        r#"<text x="{:.1}" y="{}" text-anchor="middle">input size (bytes)</text>"#,
+        MARGIN_LEFT + plot_w / 2.0,
+        CHART_HEIGHT - 8.0
+    );
+
+    for (i, (label, points)) in series.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let path = points
+            .iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", sx(x), sy(y)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let _ = write!(
+            svg,
+            r#"<polyline points="{path}" fill="none" stroke="{color}" stroke-width="2"/>"#
+        );
+        for &(x, y) in points {
+            let _ = write!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="3" fill="{color}"><title>{}: {}</title></circle>"#,
+                sx(x),
+                sy(y),
+                escape_html(label),
+                escape_html(&(metric.format)(y))
+            );
+        }
+        let ly = MARGIN_TOP + 10.0 + i as f64 * 16.0;
+        let lx = CHART_WIDTH - MARGIN_RIGHT + 12.0;
+        let _ = write!(
+            svg,
+            r#"<rect x="{lx}" y="{:.1}" width="10" height="10" fill="{color}"/><text x="{}" y="{:.1}">{}</text>"#,
+            ly - 9.0,
+            lx + 14.0,
+            ly,
+            escape_html(label)
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Smallest power-of-`base` range covering all values (defaults to [1, base]).
+fn log_bounds(values: impl Iterator<Item = f64>, base: f64) -> (f64, f64) {
+    let (min, max) = values.fold((f64::INFINITY, 0.0f64), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if !min.is_finite() || max <= 0.0 {
+        return (1.0, base);
+    }
+    let lo = base.powf(min.log(base).floor());
+    let mut hi = base.powf(max.log(base).ceil());
+    if hi <= lo {
+        hi = lo * base;
+    }
+    (lo, hi)
+}
+
+fn scale_log(v: f64, min: f64, max: f64) -> f64 {
+    (v.ln() - min.ln()) / (max.ln() - min.ln())
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Self-contained HTML report (no external assets) with SVG charts per
+/// target and the comparison tables per target and input size.
+pub fn render_html(results: &[Metrics]) -> String {
+    let mut by_target: BTreeMap<&str, Vec<&Metrics>> = BTreeMap::new();
+    for m in results {
+        by_target.entry(m.target.as_str()).or_default().push(m);
+    }
+
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Benchmark report</title>
+<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2em; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: right; }
+th:first-child, td:first-child, th:nth-child(2), td:nth-child(2) { text-align: left; }
+.charts { display: flex; flex-wrap: wrap; gap: 1em; }
+</style>
+</head>
+<body>
+<h1>Benchmark report</h1>
+"#,
+    );
+
+    let groups = group_by_target_and_size(results);
+    for (target, rows) in &by_target {
+        let _ = writeln!(html, "<h2>{}</h2>", escape_html(target));
+        html.push_str("<div class=\"charts\">\n");
+        for metric in &CHART_METRICS {
+            html.push_str(&render_chart(metric, rows));
+            html.push('\n');
+        }
+        html.push_str("</div>\n");
+
+        for ((_, size), rows) in groups.range((*target, 0)..=(*target, usize::MAX)) {
+            let _ = writeln!(html, "<h3>{} bytes</h3>", size);
+            html.push_str("<table>\n<tr>");
+            for header in ComparisonRow::headers() {
+                let _ = write!(html, "<th>{}</th>", escape_html(&header));
+            }
+            html.push_str("</tr>\n");
+            for row in rows.iter().map(|m| ComparisonRow::from(*m)) {
+                html.push_str("<tr>");
+                for cell in row.fields() {
+                    let _ = write!(html, "<td>{}</td>", escape_html(&cell));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+    use std::time::Duration;
+
+    fn row(name: &str, target: &str, input_size: usize, prove_ms: u64) -> Metrics {
+        let mut m = Metrics::new(
+            name.to_string(),
+            None,
+            true,
+            target.to_string(),
+            input_size,
+            ProvingSystem::Risc0.properties(),
+        );
+        m.proof_duration = Duration::from_millis(prove_ms);
+        m.peak_memory = 1 << 20;
+        m.proof_size = 1 << 10;
+        m
+    }
+
+    fn sample() -> Vec<Metrics> {
+        vec![
+            row("risc0", "sha256", 256, 900),
+            row("sp1", "sha256", 256, 400),
+            row("risc0", "sha256", 128, 500),
+            row("risc0", "ecdsa", 32, 2000),
+        ]
+    }
+
+    #[test]
+    fn test_markdown_groups_by_target_and_size() {
+        let md = render_markdown(&sample());
+        let headings: Vec<&str> = md.lines().filter(|l| l.starts_with("## ")).collect();
+        assert_eq!(
+            headings,
+            [
+                "## ecdsa, 32 bytes",
+                "## sha256, 128 bytes",
+                "## sha256, 256 bytes"
+            ]
+        );
+        // Within a group, the fastest prover comes first.
+        let sp1 = md.find("| sp1").unwrap();
+        let risc0 = md.rfind("| risc0").unwrap();
+        assert!(sp1 < risc0);
+    }
+
+    #[test]
+    fn test_markdown_pivots_by_device() {
+        assert!(!render_markdown(&sample()).contains("by device"));
+
+        let mut results = sample();
+        results[0].device = Some("mac2.metal".to_string());
+        results[1].device = Some("c7i".to_string());
+        let md = render_markdown(&results);
+        let pivot = &md[md.find("# Prove time by device").unwrap()..];
+        assert!(pivot.contains("| system | -"));
+        assert!(pivot.contains("c7i"));
+        assert!(pivot.contains("mac2.metal"));
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_result() {
+        let csv = render_csv(&sample()).unwrap();
+        let mut lines = csv.lines();
+        assert!(lines.next().unwrap().starts_with("target,input_size,name"));
+        assert_eq!(lines.count(), 4);
+    }
+
+    #[test]
+    fn test_html_is_self_contained() {
+        let html = render_html(&sample());
+        assert_eq!(html.matches("<svg").count(), 2 * CHART_METRICS.len());
+        assert!(html.contains("<polyline"));
+        assert!(!html.contains("<script") && !html.contains("src=") && !html.contains("href="));
+    }
+}
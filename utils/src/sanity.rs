@@ -0,0 +1,296 @@
+//! Plausibility checks over collected results, run before publishing.
+//!
+//! Every finding carries a stable reason code, so reports can be filtered
+//! and known issues tracked across runs. Findings are warnings: they point at
+//! rows that are likely mis-measured or mis-described, not at hard errors.
+
+use crate::bench::{Metrics, display_duration};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum Reason {
+    /// A circuit (non-zkVM) system reports no constraints.
+    ZeroConstraints,
+    /// `security_bits` is 0, i.e. the security level is unknown.
+    ZeroSecurityBits,
+    /// Peak memory was not measured.
+    ZeroPeakMemory,
+    /// Proof size was not measured.
+    ZeroProofSize,
+    /// Prove or verify time was not measured.
+    ZeroDuration,
+    /// Verification is slower than proving.
+    VerifySlowerThanProve,
+    /// The proof of a non-succinct system does not grow with the input.
+    ProofSizeNotGrowing,
+    /// zkVM cycles grow much slower than the input, e.g. because the guest
+    /// hashes a constant instead of the input.
+    CyclesNotScaling,
+    /// Circuit constraints grow much slower than the input.
+    ConstraintsNotScaling,
+}
+
+impl Reason {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Reason::ZeroConstraints => "ZERO_CONSTRAINTS",
+            Reason::ZeroSecurityBits => "ZERO_SECURITY_BITS",
+            Reason::ZeroPeakMemory => "ZERO_PEAK_MEMORY",
+            Reason::ZeroProofSize => "ZERO_PROOF_SIZE",
+            Reason::ZeroDuration => "ZERO_DURATION",
+            Reason::VerifySlowerThanProve => "VERIFY_SLOWER_THAN_PROVE",
+            Reason::ProofSizeNotGrowing => "PROOF_SIZE_NOT_GROWING",
+            Reason::CyclesNotScaling => "CYCLES_NOT_SCALING",
+            Reason::ConstraintsNotScaling => "CONSTRAINTS_NOT_SCALING",
+        }
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    pub reason: Reason,
+    pub name: String,
+    pub feat: Option<String>,
+    pub device: Option<String>,
+    pub target: String,
+    /// The offending row, or `None` for findings about how a system scales
+    /// across input sizes.
+    pub input_size: Option<usize>,
+    pub message: String,
+}
+
+/// Proof sizes within this relative spread count as "not growing".
+const PROOF_SIZE_GROWTH_TOLERANCE: f64 = 0.01;
+
+/// Checks of a single row.
+fn check_row(m: &Metrics, findings: &mut Vec<Finding>) {
+    let props = &m.bench_properties;
+    let mut flag = |reason: Reason, message: String| {
+        findings.push(Finding {
+            reason,
+            name: m.name.clone(),
+            feat: m.feat.clone(),
+            device: m.device.clone(),
+            target: m.target.clone(),
+            input_size: Some(m.input_size),
+            message,
+        })
+    };
+
+    if !m.is_zkvm && m.num_constraints == 0 {
+        flag(
+            Reason::ZeroConstraints,
+            "circuit system reports 0 constraints".to_string(),
+        );
+    }
+    if props.security_bits == 0 {
+        flag(
+            Reason::ZeroSecurityBits,
+            "security level is unknown (0 bits)".to_string(),
+        );
+    }
+    if m.peak_memory == 0 {
+        flag(
+            Reason::ZeroPeakMemory,
+            "peak memory was not measured".to_string(),
+        );
+    }
+    if m.proof_size == 0 {
+        flag(
+            Reason::ZeroProofSize,
+            "proof size was not measured".to_string(),
+        );
+    }
+    if m.proof_duration.is_zero() || m.verify_duration.is_zero() {
+        flag(
+            Reason::ZeroDuration,
+            "prove or verify time was not measured".to_string(),
+        );
+    } else if m.verify_duration > m.proof_duration {
+        flag(
+            Reason::VerifySlowerThanProve,
+            format!(
+                "verify time {} exceeds prove time {}",
+                display_duration(&m.verify_duration),
+                display_duration(&m.proof_duration)
+            ),
+        );
+    }
+}
+
+/// Checks of how a system scales over the input sizes of one target. A count
+/// (cycles, constraints) is expected to grow at least with the square root of
+/// the input size; real SHA-256/Keccak work grows linearly.
+fn check_series(rows: &[&Metrics], findings: &mut Vec<Finding>) {
+    let (Some(first), Some(last)) = (rows.first(), rows.last()) else {
+        return;
+    };
+    if first.input_size == 0 || last.input_size <= first.input_size {
+        return;
+    }
+    let size_ratio = last.input_size as f64 / first.input_size as f64;
+    let mut flag = |reason: Reason, message: String| {
+        findings.push(Finding {
+            reason,
+            name: first.name.clone(),
+            feat: first.feat.clone(),
+            device: first.device.clone(),
+            target: first.target.clone(),
+            input_size: None,
+            message,
+        })
+    };
+    let sizes = format!("{}..{} bytes", first.input_size, last.input_size);
+
+    let grows = |count: fn(&Metrics) -> u64| -> Option<f64> {
+        let (a, b) = (count(first), count(last));
+        (a > 0 && b > 0).then(|| b as f64 / a as f64)
+    };
+    if let Some(ratio) = grows(|m| m.cycles.unwrap_or(0))
+        && ratio < size_ratio.sqrt()
+    {
+        flag(
+            Reason::CyclesNotScaling,
+            format!("cycles grow {ratio:.2}x while the input grows {size_ratio:.0}x ({sizes})"),
+        );
+    }
+    if let Some(ratio) = grows(|m| m.num_constraints as u64)
+        && ratio < size_ratio.sqrt()
+    {
+        flag(
+            Reason::ConstraintsNotScaling,
+            format!(
+                "constraints grow {ratio:.2}x while the input grows {size_ratio:.0}x ({sizes})"
+            ),
+        );
+    }
+
+    if !first.bench_properties.has_succinct_proofs()
+        && let Some(ratio) = grows(|m| m.proof_size as u64)
+        && ratio < 1.0 + PROOF_SIZE_GROWTH_TOLERANCE
+    {
+        flag(
+            Reason::ProofSizeNotGrowing,
+            format!("proofs are not succinct, yet the proof size grows {ratio:.3}x ({sizes})"),
+        );
+    }
+}
+
+/// Runs all checks, ordered by system, target and reason. Each device is a
+/// separate series, so merged results of several hosts are not mixed.
+pub fn check_all(results: &[Metrics]) -> Vec<Finding> {
+    type Key<'a> = (&'a str, Option<&'a str>, Option<&'a str>, &'a str);
+    let mut findings = Vec::new();
+    let mut series: BTreeMap<Key, Vec<&Metrics>> = BTreeMap::new();
+    for m in results {
+        check_row(m, &mut findings);
+        series
+            .entry((&m.name, m.feat.as_deref(), m.device.as_deref(), &m.target))
+            .or_default()
+            .push(m);
+    }
+    for rows in series.values_mut() {
+        rows.sort_by_key(|m| m.input_size);
+        check_series(rows, &mut findings);
+    }
+    findings.sort_by(|a, b| {
+        (&a.name, &a.device, &a.target, a.reason, a.input_size).cmp(&(
+            &b.name,
+            &b.device,
+            &b.target,
+            b.reason,
+            b.input_size,
+        ))
+    });
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+    use std::time::Duration;
+
+    fn row(system: ProvingSystem, input_size: usize, cycles: u64) -> Metrics {
+        let mut m = Metrics::new(
+            system.as_str().to_string(),
+            None,
+            system.is_zkvm(),
+            "sha256".to_string(),
+            input_size,
+            system.properties(),
+        );
+        m.proof_duration = Duration::from_secs(10);
+        m.verify_duration = Duration::from_millis(10);
+        m.peak_memory = 1 << 30;
+        m.proof_size = 1 << 18;
+        if m.is_zkvm {
+            m.cycles = Some(cycles);
+        } else {
+            m.num_constraints = cycles as usize;
+        }
+        m
+    }
+
+    fn codes(findings: &[Finding]) -> Vec<&'static str> {
+        findings.iter().map(|f| f.reason.code()).collect()
+    }
+
+    #[test]
+    fn test_scaling_checks() {
+        // Cycles barely move over a 16x larger input.
+        let results = [
+            row(ProvingSystem::Risc0, 128, 4632),
+            row(ProvingSystem::Risc0, 2048, 6875),
+            row(ProvingSystem::Sp1, 128, 20_000),
+            row(ProvingSystem::Sp1, 2048, 300_000),
+        ];
+        assert_eq!(codes(&check_all(&results)), ["CYCLES_NOT_SCALING"]);
+
+        // Ligero proofs are expected to grow with the input.
+        let results = [
+            row(ProvingSystem::Ligetron, 128, 20_000),
+            row(ProvingSystem::Ligetron, 2048, 300_000),
+        ];
+        assert_eq!(codes(&check_all(&results)), ["PROOF_SIZE_NOT_GROWING"]);
+
+        // Each device is its own series: the 128-byte row of another host does
+        // not make the cycles of SP1 look flat.
+        let on = |device: &str, mut m: Metrics| {
+            m.device = Some(device.to_string());
+            m
+        };
+        let results = [
+            on("mac", row(ProvingSystem::Sp1, 128, 20_000)),
+            on("mac", row(ProvingSystem::Sp1, 2048, 300_000)),
+            on("x86", row(ProvingSystem::Sp1, 128, 300_000)),
+        ];
+        assert!(check_all(&results).is_empty());
+    }
+
+    #[test]
+    fn test_row_checks() {
+        let mut m = row(ProvingSystem::Circom, 128, 0);
+        m.peak_memory = 0;
+        m.verify_duration = Duration::from_secs(20);
+        m.bench_properties.security_bits = 0;
+        assert_eq!(
+            codes(&check_all(&[m])),
+            [
+                "ZERO_CONSTRAINTS",
+                "ZERO_SECURITY_BITS",
+                "ZERO_PEAK_MEMORY",
+                "VERIFY_SLOWER_THAN_PROVE"
+            ]
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Versioning of the metrics JSON format.
+//!
+//! Every `Metrics` JSON written by the harness carries a `schema_version`.
+//! Files produced by older harness versions are upgraded on load by running
+//! the migration steps below in order, so readers only ever deal with the
+//! current layout.
+
+use crate::bench::Metrics;
+use crate::harness::{BenchProperties, ProvingSystem};
+use schemars::{Schema, schema_for};
+use serde_json::{Map, Value};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Current version of the `Metrics` JSON format.
+///
+/// Bump this whenever a field is added, removed or changes meaning, and add
+/// the corresponding step to `MIGRATIONS`.
+pub const METRICS_SCHEMA_VERSION: u32 = 2;
+
+type Migration = fn(&mut Map<String, Value>);
+
+/// `MIGRATIONS[n]` upgrades a metrics object from version `n` to `n + 1`.
+const MIGRATIONS: [Migration; METRICS_SCHEMA_VERSION as usize] =
+    [migrate_v0_to_v1, migrate_v1_to_v2];
+
+/// Version 0 is every file written before `schema_version` existed.
+/// Such files may lack `is_zkvm`, and older formatters wrote an empty
+/// string instead of omitting `feat`.
+fn migrate_v0_to_v1(obj: &mut Map<String, Value>) {
+    if !obj.contains_key("is_zkvm") {
+        let is_zkvm = obj.get("cycles").is_some_and(|c| !c.is_null())
+            || obj.get("isa").is_some_and(|isa| !isa.is_null());
+        obj.insert("is_zkvm".to_string(), Value::Bool(is_zkvm));
+    }
+    if obj.get("feat").and_then(Value::as_str) == Some("") {
+        obj.remove("feat");
+    }
+}
+
+/// Version 2 replaced the free-form classification strings with the typed
+/// enums in `utils::properties`: legacy spellings are mapped to the canonical
+/// ones, and fields left empty by `BenchProperties::default()` are filled from
+/// the `ProvingSystem::properties()` registry, as are the new `setup_kind` and
+/// `recursion_friendly`.
+///
+/// The other fields added in version 2 are optional and were not measured by
+/// older runs, so they are left unset: `proof_system_version`, the timing
+/// spreads and `witness_duration`, the zkVM execution timings, and `device`
+/// (`collect_benchmarks --root DEVICE=DIR` tags untagged results). `derived`
+/// is recomputed by `collect_benchmarks`.
+fn migrate_v1_to_v2(obj: &mut Map<String, Value>) {
+    const LEGACY_SPELLINGS: [(&str, &str, &str); 1] = [("field_curve", "Bn254", "BN254")];
+
+    for (field, legacy, canonical) in LEGACY_SPELLINGS {
+        if obj.get(field).and_then(Value::as_str) == Some(legacy) {
+            obj.insert(field.to_string(), Value::from(canonical));
+        }
+    }
+
+    if let Some(props) = registered_properties(obj) {
+        for field in ["proving_system", "field_curve", "iop", "arithm"] {
+            if obj.get(field).and_then(Value::as_str) == Some("") {
+                obj.insert(field.to_string(), props[field].clone());
+            }
+        }
+        for field in ["setup_kind", "recursion_friendly"] {
+            if !obj.contains_key(field) {
+                obj.insert(field.to_string(), props[field].clone());
+            }
+        }
+    }
+}
+
+/// The registry properties (as JSON) of the system named by the `name` field.
+fn registered_properties(obj: &Map<String, Value>) -> Option<Map<String, Value>> {
+    let system = ProvingSystem::from_str(obj.get("name")?.as_str()?).ok()?;
+    match serde_json::to_value(system.properties()) {
+        Ok(Value::Object(props)) => Some(props),
+        _ => None,
+    }
+}
+
+/// Upgrades a single metrics JSON object to `METRICS_SCHEMA_VERSION`.
+pub fn migrate_metrics(value: &mut Value) -> Result<(), String> {
+    let obj = value
+        .as_object_mut()
+        .ok_or_else(|| "metrics entry is not a JSON object".to_string())?;
+
+    let version = match obj.get("schema_version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .ok_or_else(|| format!("invalid schema_version: {v}"))? as u32,
+    };
+    if version > METRICS_SCHEMA_VERSION {
+        return Err(format!(
+            "schema_version {version} is newer than supported version {METRICS_SCHEMA_VERSION}"
+        ));
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(obj);
+    }
+    obj.insert(
+        "schema_version".to_string(),
+        Value::from(METRICS_SCHEMA_VERSION),
+    );
+    Ok(())
+}
+
+/// Parses a metrics JSON object of any known version into `Metrics`.
+pub fn metrics_from_value(mut value: Value) -> io::Result<Metrics> {
+    migrate_metrics(&mut value).map_err(io::Error::other)?;
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Loads a single `*_metrics.json` file, upgrading it if needed.
+pub fn load_metrics_file(path: &Path) -> io::Result<Metrics> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    metrics_from_value(value)
+}
+
+/// Loads a collected benchmarks file (a JSON array of metrics, e.g.
+/// `results/collected_benchmarks_<run_id>.json`), upgrading every entry.
+pub fn load_collected(path: &Path) -> io::Result<Vec<Metrics>> {
+    let value: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    match value {
+        Value::Array(entries) => entries.into_iter().map(metrics_from_value).collect(),
+        _ => Err(io::Error::other(format!(
+            "{}: expected a JSON array of metrics",
+            path.display()
+        ))),
+    }
+}
+
+/// JSON Schema of a single `Metrics` entry.
+pub fn metrics_json_schema() -> Schema {
+    schema_for!(Metrics)
+}
+
+/// JSON Schema of the `bench_props.json` file used by non-Rust systems.
+pub fn bench_properties_json_schema() -> Schema {
+    schema_for!(BenchProperties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::FieldCurve;
+    use serde_json::json;
+
+    fn legacy_row() -> Value {
+        json!({
+            "name": "risc0",
+            "feat": "",
+            "target": "sha256",
+            "input_size": 128,
+            "proof_duration": 1000,
+            "verify_duration": 10,
+            "cycles": 42,
+            "proof_size": 1,
+            "preprocessing_size": 2,
+            "num_constraints": 0,
+            "peak_memory": 3,
+            "proving_system": "STARK",
+            "field_curve": "Bn254",
+            "iop": "STARK",
+            "pcs": "FRI",
+            "arithm": "AIR",
+            "is_zk": true,
+            "security_bits": 96,
+            "is_pq": true,
+            "is_maintained": true,
+            "is_audited": "audited"
+        })
+    }
+
+    #[test]
+    fn test_migrate_unversioned_metrics() {
+        let metrics = metrics_from_value(legacy_row()).unwrap();
+        assert_eq!(metrics.schema_version, METRICS_SCHEMA_VERSION);
+        assert!(metrics.is_zkvm);
+        assert!(metrics.feat.is_none());
+        assert_eq!(metrics.cycles, Some(42));
+        assert_eq!(metrics.bench_properties.field_curve, FieldCurve::Bn254);
+        assert_eq!(
+            metrics.bench_properties.setup_kind,
+            ProvingSystem::Risc0.properties().setup_kind
+        );
+        assert!(metrics.bench_properties.proof_system_version.is_none());
+    }
+
+    #[test]
+    fn test_migrate_fills_empty_properties_from_registry() {
+        let mut row = legacy_row();
+        row["schema_version"] = json!(1);
+        row["is_zkvm"] = json!(true);
+        row["name"] = json!("sp1");
+        for field in ["proving_system", "field_curve", "iop", "arithm"] {
+            row[field] = json!("");
+        }
+        let metrics = metrics_from_value(row).unwrap();
+        let expected = ProvingSystem::Sp1.properties();
+        assert_eq!(
+            metrics.bench_properties.proving_system,
+            expected.proving_system
+        );
+        assert_eq!(metrics.bench_properties.field_curve, expected.field_curve);
+        assert_eq!(metrics.bench_properties.arithm, expected.arithm);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut row = legacy_row();
+        row["schema_version"] = json!(METRICS_SCHEMA_VERSION + 1);
+        assert!(migrate_metrics(&mut row).is_err());
+    }
+
+    #[test]
+    fn test_published_schemas_are_up_to_date() {
+        let metrics: Value =
+            serde_json::from_str(include_str!("../schema/metrics.schema.json")).unwrap();
+        assert_eq!(
+            metrics,
+            serde_json::to_value(metrics_json_schema()).unwrap()
+        );
+
+        let props: Value =
+            serde_json::from_str(include_str!("../schema/bench_properties.schema.json")).unwrap();
+        assert_eq!(
+            props,
+            serde_json::to_value(bench_properties_json_schema()).unwrap()
+        );
+    }
+}
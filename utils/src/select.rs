@@ -0,0 +1,355 @@
+//! System selection for a use case: filters the results of one target and
+//! input size by hard requirements (memory and proof size budgets, post-quantum
+//! security, audit status, ...) and computes the Pareto frontier over prove
+//! time, peak memory and proof size among the qualifying systems measured on
+//! the same device.
+
+use crate::bench::{Metrics, display_bytes, display_duration};
+use crate::harness::{AuditStatus, SetupKind};
+use std::time::Duration;
+
+/// Hard requirements a system must meet to be considered.
+#[derive(Debug, Clone, Default)]
+pub struct Requirements {
+    pub max_prove_time: Option<Duration>,
+    pub max_memory: Option<usize>,
+    pub max_proof_size: Option<usize>,
+    pub min_security_bits: Option<u64>,
+    pub require_pq: bool,
+    pub require_zk: bool,
+    pub require_audited: bool,
+    pub require_maintained: bool,
+    pub setup_kind: Option<SetupKind>,
+}
+
+impl Requirements {
+    /// Checks `m` against every requirement. Returns the reasons it qualified
+    /// and the reasons it was disqualified.
+    pub fn check(&self, m: &Metrics) -> (Vec<String>, Vec<String>) {
+        let props = &m.bench_properties;
+        let mut passed = Vec::new();
+        let mut failed = Vec::new();
+        let mut check = |ok: bool, what: String| {
+            if ok { &mut passed } else { &mut failed }.push(what);
+        };
+
+        if let Some(max) = self.max_prove_time {
+            check(
+                m.proof_duration <= max,
+                format!(
+                    "prove time {} (max {})",
+                    display_duration(&m.proof_duration),
+                    display_duration(&max)
+                ),
+            );
+        }
+        if let Some(max) = self.max_memory {
+            check(
+                m.peak_memory > 0 && m.peak_memory <= max,
+                budget("memory", m.peak_memory, max),
+            );
+        }
+        if let Some(max) = self.max_proof_size {
+            check(
+                m.proof_size > 0 && m.proof_size <= max,
+                budget("proof size", m.proof_size, max),
+            );
+        }
+        if let Some(min) = self.min_security_bits {
+            check(
+                props.security_bits >= min,
+                format!("{} security bits (min {min})", props.security_bits),
+            );
+        }
+        if self.require_pq {
+            check(props.is_pq, yes_no("post-quantum", props.is_pq));
+        }
+        if self.require_zk {
+            check(props.is_zk, yes_no("zero-knowledge", props.is_zk));
+        }
+        if self.require_audited {
+            check(
+                props.is_audited == AuditStatus::Audited,
+                format!("audit: {}", props.is_audited),
+            );
+        }
+        if self.require_maintained {
+            check(
+                props.is_maintained,
+                yes_no("maintained", props.is_maintained),
+            );
+        }
+        if let Some(kind) = self.setup_kind {
+            check(
+                props.setup_kind == kind,
+                format!("{} setup", props.setup_kind),
+            );
+        }
+        (passed, failed)
+    }
+}
+
+fn budget(what: &str, value: usize, max: usize) -> String {
+    if value == 0 {
+        format!("{what} not measured (max {})", display_bytes(&max))
+    } else {
+        format!(
+            "{what} {} (max {})",
+            display_bytes(&value),
+            display_bytes(&max)
+        )
+    }
+}
+
+fn yes_no(what: &str, value: bool) -> String {
+    if value {
+        what.to_string()
+    } else {
+        format!("not {what}")
+    }
+}
+
+/// Relative importance of the objectives when ranking the frontier.
+#[derive(Debug, Clone, Copy)]
+pub struct Weights {
+    pub prove_time: f64,
+    pub memory: f64,
+    pub proof_size: f64,
+}
+
+impl Default for Weights {
+    fn default() -> Self {
+        Weights {
+            prove_time: 1.0,
+            memory: 1.0,
+            proof_size: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    /// Non-dominated. `score` is the weighted sum of log2 ratios to the best
+    /// frontier value of each objective (0 = best in everything).
+    Frontier {
+        score: f64,
+    },
+    /// Another qualifying system is at least as good in every objective and
+    /// better in one.
+    Dominated {
+        by: String,
+    },
+    Disqualified,
+}
+
+#[derive(Clone)]
+pub struct Candidate<'a> {
+    pub metrics: &'a Metrics,
+    pub status: Status,
+    pub passed: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+impl Candidate<'_> {
+    pub fn label(&self) -> String {
+        crate::report::series_label(self.metrics)
+    }
+}
+
+/// Objective values; unmeasured values count as worst.
+fn objectives(m: &Metrics) -> [f64; 3] {
+    let or_worst = |v: usize| if v == 0 { f64::INFINITY } else { v as f64 };
+    [
+        m.proof_duration.as_secs_f64(),
+        or_worst(m.peak_memory),
+        or_worst(m.proof_size),
+    ]
+}
+
+fn dominates(a: &[f64; 3], b: &[f64; 3]) -> bool {
+    a.iter().zip(b).all(|(x, y)| x <= y) && a.iter().zip(b).any(|(x, y)| x < y)
+}
+
+/// Classifies the results of `target` at `input_size`. Frontier systems come
+/// first, best score first, then dominated and disqualified ones.
+pub fn select<'a>(
+    results: &'a [Metrics],
+    target: &str,
+    input_size: usize,
+    requirements: &Requirements,
+    weights: &Weights,
+) -> Vec<Candidate<'a>> {
+    let mut candidates: Vec<Candidate> = results
+        .iter()
+        .filter(|m| m.target == target && m.input_size == input_size)
+        .map(|metrics| {
+            let (passed, failed) = requirements.check(metrics);
+            let status = if failed.is_empty() {
+                Status::Frontier { score: 0.0 }
+            } else {
+                Status::Disqualified
+            };
+            Candidate {
+                metrics,
+                status,
+                passed,
+                failed,
+            }
+        })
+        .collect();
+
+    // Systems are only compared with others measured on the same device.
+    let qualified: Vec<(String, Option<&str>, [f64; 3])> = candidates
+        .iter()
+        .filter(|c| c.status != Status::Disqualified)
+        .map(|c| {
+            (
+                c.label(),
+                c.metrics.device.as_deref(),
+                objectives(c.metrics),
+            )
+        })
+        .collect();
+    for c in candidates.iter_mut() {
+        if c.status == Status::Disqualified {
+            continue;
+        }
+        let own = objectives(c.metrics);
+        if let Some((by, _, _)) = qualified.iter().find(|(_, device, other)| {
+            *device == c.metrics.device.as_deref() && dominates(other, &own)
+        }) {
+            c.status = Status::Dominated { by: by.clone() };
+        }
+    }
+
+    let frontier: Vec<(Option<&str>, [f64; 3])> = candidates
+        .iter()
+        .filter(|c| matches!(c.status, Status::Frontier { .. }))
+        .map(|c| (c.metrics.device.as_deref(), objectives(c.metrics)))
+        .collect();
+    let w = [weights.prove_time, weights.memory, weights.proof_size];
+    for c in candidates.iter_mut() {
+        let device = c.metrics.device.as_deref();
+        let best: Vec<f64> = (0..3)
+            .map(|i| {
+                frontier
+                    .iter()
+                    .filter(|(d, _)| *d == device)
+                    .map(|(_, o)| o[i])
+                    .fold(f64::INFINITY, f64::min)
+            })
+            .collect();
+        if let Status::Frontier { score } = &mut c.status {
+            *score = objectives(c.metrics)
+                .iter()
+                .zip(&best)
+                .zip(w)
+                .filter(|((v, b), w)| v.is_finite() && *w != 0.0 && **b > 0.0)
+                .map(|((v, b), w)| w * (v / b).log2())
+                .sum();
+        }
+    }
+
+    let rank = |s: &Status| match s {
+        Status::Frontier { score } => (0, *score),
+        Status::Dominated { .. } => (1, 0.0),
+        Status::Disqualified => (2, 0.0),
+    };
+    candidates.sort_by(|a, b| {
+        let (ra, rb) = (rank(&a.status), rank(&b.status));
+        ra.0.cmp(&rb.0)
+            .then(ra.1.total_cmp(&rb.1))
+            .then(a.metrics.proof_duration.cmp(&b.metrics.proof_duration))
+    });
+    candidates
+}
+
+/// Parses a byte size such as `2GB`, `100KB`, `512` (1024-based, as reported
+/// by the harness).
+pub fn parse_bytes(s: &str) -> Result<usize, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid byte size: {s}"))?;
+    let factor: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1 << 10,
+        "M" | "MB" | "MIB" => 1 << 20,
+        "G" | "GB" | "GIB" => 1 << 30,
+        _ => return Err(format!("invalid byte size unit: {s}")),
+    };
+    Ok((number * factor as f64) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::ProvingSystem;
+
+    fn row(system: ProvingSystem, prove_ms: u64, memory: usize, proof_size: usize) -> Metrics {
+        let mut m = Metrics::new(
+            system.as_str().to_string(),
+            None,
+            system.is_zkvm(),
+            "sha256".to_string(),
+            1024,
+            system.properties(),
+        );
+        m.proof_duration = Duration::from_millis(prove_ms);
+        m.peak_memory = memory;
+        m.proof_size = proof_size;
+        m
+    }
+
+    #[test]
+    fn test_select_frontier_and_requirements() {
+        let results = [
+            row(ProvingSystem::Barretenberg, 1000, 300 << 20, 15 << 10),
+            row(ProvingSystem::Circom, 2000, 400 << 20, 1 << 10),
+            // Dominated by barretenberg.
+            row(ProvingSystem::Provekit, 5000, 1 << 30, 400 << 10),
+            // Disqualified by the memory budget.
+            row(ProvingSystem::Risc0, 500, 5 << 30, 200 << 10),
+        ];
+        let requirements = Requirements {
+            max_memory: Some(parse_bytes("2GB").unwrap()),
+            ..Default::default()
+        };
+        let candidates = select(&results, "sha256", 1024, &requirements, &Weights::default());
+        let status = |name: &str| candidates.iter().find(|c| c.metrics.name == name).unwrap();
+
+        assert!(matches!(
+            status("barretenberg").status,
+            Status::Frontier { .. }
+        ));
+        assert!(matches!(status("circom").status, Status::Frontier { .. }));
+        assert_eq!(
+            status("provekit").status,
+            Status::Dominated {
+                by: "barretenberg".to_string()
+            }
+        );
+        assert_eq!(status("risc0").status, Status::Disqualified);
+        assert!(status("risc0").failed[0].starts_with("memory"));
+        assert_eq!(candidates.last().unwrap().metrics.name, "risc0");
+
+        let pq = Requirements {
+            require_pq: true,
+            ..Default::default()
+        };
+        let candidates = select(&results, "sha256", 1024, &pq, &Weights::default());
+        assert_eq!(candidates[0].metrics.name, "risc0");
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(parse_bytes("512"), Ok(512));
+        assert_eq!(parse_bytes("100KB"), Ok(100 << 10));
+        assert_eq!(parse_bytes("1.5G"), Ok(3 << 29));
+        assert!(parse_bytes("2 parsecs").is_err());
+    }
+}
@@ -0,0 +1,271 @@
+//! Standard test vectors bundled with the crate (`utils/vectors/*.json`), so an
+//! integration can check its circuit or guest against the reference on edge
+//! cases, not only on the pseudo-random benchmark input.
+//!
+//! - `sha256.json`: the FIPS 180-2 examples (empty, `abc`, the two-block
+//!   message), the RustCrypto test messages and `a`-strings at the padding
+//!   boundaries (55, 56, 63, 64, 65, 119, 120, 128 bytes).
+//! - `keccak256.json`: the Keccak-256 KATs for the empty string and `abc`, the
+//!   RustCrypto test messages and `a`-strings around the 136-byte rate.
+//! - `ecdsa_p256.json`, `ecdsa_k256.json`: the Wycheproof ECDSA/SHA-256 cases
+//!   shipped with the `p256` and `k256` crates, as prehashed inputs with a
+//!   fixed 64-byte `r || s` signature. Cases that only test the DER encoding
+//!   of the signature are dropped, and valid signatures are normalized to low
+//!   `s` as the benchmarked circuits require.
+
+use crate::inputs::GeneratedInput;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(bytes))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VectorSet {
+    Sha256,
+    Keccak256,
+    EcdsaP256,
+    EcdsaK256,
+}
+
+impl VectorSet {
+    pub const ALL: [VectorSet; 4] = [
+        VectorSet::Sha256,
+        VectorSet::Keccak256,
+        VectorSet::EcdsaP256,
+        VectorSet::EcdsaK256,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VectorSet::Sha256 => "sha256",
+            VectorSet::Keccak256 => "keccak256",
+            VectorSet::EcdsaP256 => "ecdsa-p256",
+            VectorSet::EcdsaK256 => "ecdsa-k256",
+        }
+    }
+}
+
+impl fmt::Display for VectorSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VectorSet {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<VectorSet, String> {
+        VectorSet::ALL
+            .into_iter()
+            .find(|set| set.as_str() == s)
+            .ok_or_else(|| format!("Invalid vector set: {}", s))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashVector {
+    pub name: String,
+    pub source: String,
+    #[serde(with = "hex_bytes")]
+    pub message: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub digest: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EcdsaVector {
+    pub name: String,
+    /// Whether a verifier must accept the signature.
+    pub valid: bool,
+    #[serde(with = "hex_bytes")]
+    pub message_hash: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub public_key_x: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub public_key_y: Vec<u8>,
+    /// `r || s`, 32 bytes each.
+    #[serde(with = "hex_bytes")]
+    pub signature: Vec<u8>,
+}
+
+/// A vector of any set, for listing and emitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestVector {
+    Hash(HashVector),
+    Ecdsa(EcdsaVector),
+}
+
+impl TestVector {
+    pub fn name(&self) -> &str {
+        match self {
+            TestVector::Hash(v) => &v.name,
+            TestVector::Ecdsa(v) => &v.name,
+        }
+    }
+
+    /// The fields of the vector under the names of the benchmark inputs
+    /// (see `GeneratedInput`).
+    pub fn input(&self) -> GeneratedInput {
+        match self {
+            TestVector::Hash(v) => GeneratedInput::hash(v.message.clone(), v.digest.clone()),
+            TestVector::Ecdsa(v) => GeneratedInput::ecdsa(
+                v.message_hash.clone(),
+                v.public_key_x.clone(),
+                v.public_key_y.clone(),
+                v.signature.clone(),
+            ),
+        }
+    }
+
+    /// One line summary: size of the message and the expected verdict.
+    pub fn describe(&self) -> String {
+        match self {
+            TestVector::Hash(v) => format!("{} bytes, {}", v.message.len(), v.source),
+            TestVector::Ecdsa(v) if v.valid => "valid".to_string(),
+            TestVector::Ecdsa(_) => "invalid".to_string(),
+        }
+    }
+}
+
+fn parse<T: for<'de> Deserialize<'de>>(json: &str, file: &str) -> Vec<T> {
+    serde_json::from_str(json).unwrap_or_else(|e| panic!("invalid bundled vectors {file}: {e}"))
+}
+
+pub fn sha256_vectors() -> Vec<HashVector> {
+    parse(include_str!("../vectors/sha256.json"), "sha256.json")
+}
+
+pub fn keccak256_vectors() -> Vec<HashVector> {
+    parse(include_str!("../vectors/keccak256.json"), "keccak256.json")
+}
+
+pub fn ecdsa_p256_vectors() -> Vec<EcdsaVector> {
+    parse(
+        include_str!("../vectors/ecdsa_p256.json"),
+        "ecdsa_p256.json",
+    )
+}
+
+pub fn ecdsa_k256_vectors() -> Vec<EcdsaVector> {
+    parse(
+        include_str!("../vectors/ecdsa_k256.json"),
+        "ecdsa_k256.json",
+    )
+}
+
+pub fn vectors(set: VectorSet) -> Vec<TestVector> {
+    match set {
+        VectorSet::Sha256 => sha256_vectors().into_iter().map(TestVector::Hash).collect(),
+        VectorSet::Keccak256 => keccak256_vectors()
+            .into_iter()
+            .map(TestVector::Hash)
+            .collect(),
+        VectorSet::EcdsaP256 => ecdsa_p256_vectors()
+            .into_iter()
+            .map(TestVector::Ecdsa)
+            .collect(),
+        VectorSet::EcdsaK256 => ecdsa_k256_vectors()
+            .into_iter()
+            .map(TestVector::Ecdsa)
+            .collect(),
+    }
+}
+
+/// Test helper: runs `hash` on every vector and lists the ones whose digest
+/// differs from the reference.
+pub fn check_hash_vectors(
+    vectors: &[HashVector],
+    mut hash: impl FnMut(&[u8]) -> Vec<u8>,
+) -> Result<(), String> {
+    let failed: Vec<&str> = vectors
+        .iter()
+        .filter(|v| hash(&v.message) != v.digest)
+        .map(|v| v.name.as_str())
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("digest mismatch for {}", failed.join(", ")))
+    }
+}
+
+/// Test helper: runs `verify` on every vector and lists the ones where it
+/// accepts an invalid signature or rejects a valid one.
+pub fn check_ecdsa_vectors(
+    vectors: &[EcdsaVector],
+    mut verify: impl FnMut(&EcdsaVector) -> bool,
+) -> Result<(), String> {
+    let failed: Vec<String> = vectors
+        .iter()
+        .filter_map(|v| {
+            let accepted = verify(v);
+            (accepted != v.valid).then(|| {
+                let verdict = if accepted { "accepted" } else { "rejected" };
+                format!("{} ({verdict})", v.name)
+            })
+        })
+        .collect();
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("wrong verdict for {}", failed.join(", ")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{Signature as K256Signature, VerifyingKey as K256VerifyingKey};
+    use p256::ecdsa::{Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+    use sha2::{Digest, Sha256};
+    use sha3::Keccak256;
+
+    #[test]
+    fn test_hash_vectors() {
+        let sha256 = sha256_vectors();
+        assert!(sha256.iter().any(|v| v.message.is_empty()));
+        for len in [55, 56, 64] {
+            assert!(sha256.iter().any(|v| v.message.len() == len));
+        }
+        check_hash_vectors(&sha256, |m| Sha256::digest(m).to_vec()).unwrap();
+        check_hash_vectors(&keccak256_vectors(), |m| Keccak256::digest(m).to_vec()).unwrap();
+
+        let err = check_hash_vectors(&sha256, |m| Keccak256::digest(m).to_vec()).unwrap_err();
+        assert!(err.contains("empty"));
+    }
+
+    #[test]
+    fn test_ecdsa_vectors() {
+        let p256 = ecdsa_p256_vectors();
+        assert!(p256.iter().any(|v| v.valid) && p256.iter().any(|v| !v.valid));
+        check_ecdsa_vectors(&p256, |v| {
+            let point = [&[4u8][..], &v.public_key_x, &v.public_key_y].concat();
+            let key = VerifyingKey::from_sec1_bytes(&point).unwrap();
+            Signature::from_slice(&v.signature)
+                .is_ok_and(|sig| key.verify_prehash(&v.message_hash, &sig).is_ok())
+        })
+        .unwrap();
+        check_ecdsa_vectors(&ecdsa_k256_vectors(), |v| {
+            let point = [&[4u8][..], &v.public_key_x, &v.public_key_y].concat();
+            let key = K256VerifyingKey::from_sec1_bytes(&point).unwrap();
+            K256Signature::from_slice(&v.signature)
+                .is_ok_and(|sig| key.verify_prehash(&v.message_hash, &sig).is_ok())
+        })
+        .unwrap();
+
+        let err = check_ecdsa_vectors(&p256, |_| true).unwrap_err();
+        assert!(err.contains("(accepted)"));
+    }
+}
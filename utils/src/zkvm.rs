@@ -1,16 +1,18 @@
 pub mod ecdsa;
 pub mod helpers;
 pub mod instance;
+pub mod program;
 pub mod sha256;
 pub mod traits;
 
-pub use ecdsa::{ECDSA_BENCH, PreparedEcdsa, build_ecdsa_input, encode_public_key};
+pub use ecdsa::{ECDSA_BENCH, EcdsaTarget, PreparedEcdsa, build_ecdsa_input, encode_public_key};
 pub use helpers::{
     execution_cycles, guest_dir, preprocessing_size, proof_size, prove, prove_ecdsa, prove_sha256,
-    verify_ecdsa, verify_sha256,
+    proving_time, verify, verify_ecdsa, verify_sha256,
 };
 pub use instance::{CompiledProgram, ProofArtifacts, compile_guest_program};
-pub use sha256::{PreparedSha256, SHA256_BENCH, build_input};
-pub use traits::{
-    BenchmarkConfig, DataGenerator, InputBuilder, PreparedBenchmark, Program, ZkVMBuilder,
+pub use program::{
+    PreparedProgram, bench_proof_feature, bench_proof_kind, parse_proof_kind, proof_kind_feature,
 };
+pub use sha256::{PreparedSha256, SHA256_BENCH, Sha256Target, build_input, hash_data};
+pub use traits::{PreparedBenchmark, ZkTarget, ZkVMBuilder};
@@ -1,6 +1,6 @@
-use crate::zkvm::instance::ProofArtifacts;
-use crate::zkvm::traits::PreparedBenchmark;
-use ere_zkvm_interface::{Input, Proof, ProofKind, PublicValues, zkVM, zkVMError};
+use crate::zkvm::program::PreparedProgram;
+use crate::zkvm::traits::ZkTarget;
+use ere_zkvm_interface::Input;
 
 /// Benchmark name for ECDSA programs.
 pub const ECDSA_BENCH: &str = "ecdsa";
@@ -30,137 +30,47 @@ pub fn encode_public_key(pub_key_x: &[u8], pub_key_y: &[u8]) -> Result<Vec<u8>,
     Ok(encoded)
 }
 
-/// Common preparation data for zkVM ECDSA benchmarks.
-pub struct PreparedEcdsa<V> {
-    vm: V,
-    input: Input,
-    compiled_size: usize,
-    expected_public_key: Option<(Vec<u8>, Vec<u8>)>,
-    expected_message: Option<Vec<u8>>,
-}
+/// Prepared ECDSA program.
+pub type PreparedEcdsa<V> = PreparedProgram<V, EcdsaTarget>;
 
-impl<V> PreparedEcdsa<V> {
-    pub fn new(vm: V, input: Input, compiled_size: usize) -> Self {
-        Self {
-            vm,
-            input,
-            compiled_size,
-            expected_public_key: None,
-            expected_message: None,
-        }
-    }
-
-    pub fn with_expected_values(
-        vm: V,
-        input: Input,
-        compiled_size: usize,
-        expected_public_key: (Vec<u8>, Vec<u8>),
-        expected_message: Vec<u8>,
-    ) -> Self {
-        Self {
-            vm,
-            input,
-            compiled_size,
-            expected_public_key: Some(expected_public_key),
-            expected_message: Some(expected_message),
-        }
-    }
+/// secp256k1 ECDSA guests: read `(encoded_verifying_key, digest, signature)`
+/// and commit `(encoded_verifying_key, digest)`, bincode encoded.
+pub struct EcdsaTarget;
 
-    pub fn compiled_size(&self) -> usize {
-        self.compiled_size
-    }
+impl ZkTarget for EcdsaTarget {
+    const NAME: &'static str = ECDSA_BENCH;
+    /// (digest, (pub_key_x, pub_key_y), signature)
+    type Data = (Vec<u8>, (Vec<u8>, Vec<u8>), Vec<u8>);
 
-    pub fn expected_public_key(&self) -> Option<&(Vec<u8>, Vec<u8>)> {
-        self.expected_public_key.as_ref()
+    fn generate(_input_size: usize) -> Self::Data {
+        crate::generate_ecdsa_k256_input()
     }
 
-    pub fn expected_message(&self) -> Option<&[u8]> {
-        self.expected_message.as_deref()
+    fn encode_input((digest, (x, y), signature): &Self::Data) -> Input {
+        let encoded_verifying_key =
+            encode_public_key(x, y).expect("generated public key should have valid size");
+        build_ecdsa_input(encoded_verifying_key, digest.clone(), signature.clone())
+            .expect("generated ECDSA input should have valid sizes")
     }
 
-    pub fn vm(&self) -> &V {
-        &self.vm
-    }
+    fn check_public_values(
+        (digest, (x, y), _): &Self::Data,
+        public_values: &[u8],
+    ) -> Result<(), String> {
+        use bincode::Options;
+        let (committed_key, committed_msg): (Vec<u8>, Vec<u8>) = bincode::options()
+            .deserialize(public_values)
+            .map_err(|_| "failed to deserialize public values".to_string())?;
 
-    pub fn input(&self) -> &Input {
-        &self.input
-    }
-}
-
-impl<V> PreparedEcdsa<V>
-where
-    V: zkVM,
-{
-    pub fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
-        let (public_values, proof, report) = self.vm.prove(&self.input, ProofKind::default())?;
-        Ok(ProofArtifacts::new(public_values, proof, report))
-    }
-
-    pub fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
-        self.vm.verify(proof)
-    }
-
-    pub fn verify_with_expected(&self, proof: &ProofArtifacts) -> Result<(), zkVMError> {
-        let public_values = self.vm.verify(&proof.proof)?;
-
-        if public_values != proof.public_values {
-            return Err(zkVMError::other("public values mismatch"));
+        let expected_key = encode_public_key(x, y)?;
+        if committed_key != expected_key {
+            return Err("public key mismatch".to_string());
         }
-
-        // Validate expected values if provided
-        if let (Some(expected_key), Some(expected_msg)) =
-            (&self.expected_public_key, &self.expected_message)
-        {
-            // The guest commits (encoded_verifying_key, message) as a bincode tuple
-            use bincode::Options;
-            let (committed_key, committed_msg): (Vec<u8>, Vec<u8>) = bincode::options()
-                .deserialize(&public_values)
-                .map_err(|_| zkVMError::other("failed to deserialize public values"))?;
-
-            // Reconstruct expected encoded key from x,y coordinates
-            let expected_encoded =
-                encode_public_key(&expected_key.0, &expected_key.1).map_err(zkVMError::other)?;
-
-            if committed_key != expected_encoded {
-                return Err(zkVMError::other("public key mismatch"));
-            }
-
-            if committed_msg != *expected_msg {
-                return Err(zkVMError::other("message mismatch"));
-            }
+        if committed_msg != *digest {
+            return Err("message mismatch".to_string());
         }
-
         Ok(())
     }
-
-    pub fn execution_cycles(&self) -> Result<u64, zkVMError> {
-        let (_, report) = self.vm.execute(&self.input)?;
-        Ok(report.total_num_cycles)
-    }
-}
-
-impl<V: zkVM> PreparedBenchmark for PreparedEcdsa<V> {
-    type VM = V;
-
-    fn compiled_size(&self) -> usize {
-        self.compiled_size
-    }
-
-    fn execution_cycles(&self) -> Result<u64, zkVMError> {
-        PreparedEcdsa::execution_cycles(self)
-    }
-
-    fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
-        PreparedEcdsa::prove(self)
-    }
-
-    fn vm(&self) -> &Self::VM {
-        &self.vm
-    }
-
-    fn input(&self) -> &Input {
-        &self.input
-    }
 }
 
 /// Builds zkVM input for ECDSA verification: (encoded_verifying_key, message, signature).
@@ -240,4 +150,30 @@ mod tests {
         let result = build_ecdsa_input(vec![4u8; 65], vec![5u8; 32], vec![6u8; 63]);
         assert_eq!(result.unwrap_err(), "Signature must be 64 bytes");
     }
+
+    #[test]
+    fn test_ecdsa_public_values_check() {
+        use bincode::Options;
+        let data = EcdsaTarget::generate(32);
+        let (digest, (x, y), _) = &data;
+        let committed = bincode::options()
+            .serialize(&(encode_public_key(x, y).unwrap(), digest.clone()))
+            .unwrap();
+        assert_eq!(EcdsaTarget::check_public_values(&data, &committed), Ok(()));
+
+        let other = bincode::options()
+            .serialize(&(encode_public_key(x, y).unwrap(), vec![0u8; 32]))
+            .unwrap();
+        assert_eq!(
+            EcdsaTarget::check_public_values(&data, &other),
+            Err("message mismatch".to_string())
+        );
+        assert!(
+            crate::zkvm::sha256::Sha256Target::check_public_values(
+                &([1].to_vec(), [2].to_vec()),
+                &[3]
+            )
+            .is_err()
+        );
+    }
 }
@@ -1,11 +1,15 @@
+use crate::cache::{ArtifactCache, ArtifactKey, subcommand_version, tool_version};
 use crate::zkvm::instance::{CompiledProgram, ProofArtifacts, compile_guest_program};
-use crate::zkvm::traits::PreparedBenchmark;
+use crate::zkvm::program::PreparedProgram;
+use crate::zkvm::traits::{PreparedBenchmark, ZkTarget};
 use crate::zkvm::{PreparedEcdsa, PreparedSha256};
 use bincode::Options;
 use ere_zkvm_interface::Compiler;
 use ere_zkvm_interface::zkVM;
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Prove any benchmark using the prepared zkVM instance.
 pub fn prove<P: PreparedBenchmark, SharedState>(prepared: &P, _: &SharedState) -> ProofArtifacts {
@@ -28,13 +32,22 @@ pub fn prove_ecdsa<V: zkVM, SharedState>(
     prove(prepared, shared_state)
 }
 
+/// Verify any benchmark proof, checking the public values against the input.
+pub fn verify<V: zkVM, T: ZkTarget, SharedState>(
+    prepared: &PreparedProgram<V, T>,
+    proof: &ProofArtifacts,
+    _: &SharedState,
+) {
+    prepared.verify_with_expected(proof).expect("verify failed");
+}
+
 /// Verify a SHA-256 proof with digest checking.
 pub fn verify_sha256<V: zkVM, SharedState>(
     prepared: &PreparedSha256<V>,
     proof: &ProofArtifacts,
     _: &SharedState,
 ) {
-    prepared.verify_with_digest(proof).expect("verify failed");
+    verify(prepared, proof, &());
 }
 
 /// Verify an ECDSA proof with expected values checking.
@@ -43,7 +56,7 @@ pub fn verify_ecdsa<V: zkVM, SharedState>(
     proof: &ProofArtifacts,
     _: &SharedState,
 ) {
-    prepared.verify_with_expected(proof).expect("verify failed");
+    verify(prepared, proof, &());
 }
 
 /// Get the execution cycles for any prepared benchmark.
@@ -51,6 +64,11 @@ pub fn execution_cycles<P: PreparedBenchmark>(prepared: &P) -> u64 {
     prepared.execution_cycles().expect("execute failed")
 }
 
+/// Get the proving time the zkVM reported for a proof.
+pub fn proving_time(proof: &ProofArtifacts) -> Duration {
+    proof.report.proving_time
+}
+
 /// Get the preprocessing (compiled program) size for any prepared benchmark.
 pub fn preprocessing_size<P: PreparedBenchmark, SharedState>(
     prepared: &P,
@@ -72,20 +90,68 @@ pub fn guest_dir(benchmark_name: &str) -> PathBuf {
         .join(benchmark_name)
 }
 
-/// Compute the standard compiled program path for a benchmark.
-/// By convention we store at guest/<bench>/target/<bench>.bin
-pub fn compiled_program_path(benchmark_name: &str) -> PathBuf {
-    guest_dir(benchmark_name)
-        .join("target")
-        .join(format!("{}.bin", benchmark_name))
+/// Path of the `guest-lib` crate holding the workloads every guest shares.
+pub fn guest_lib_dir() -> PathBuf {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    PathBuf::from(manifest_dir).join("..").join("guest-lib")
 }
 
-/// Load a compiled program, panicking if it is missing.
-/// Used by RAM measurement binaries which must never trigger compilation.
-pub fn load_compiled_program<C: Compiler>(benchmark_name: &str) -> CompiledProgram<C> {
-    let compiled_path = compiled_program_path(benchmark_name);
-    let program_bin = fs::read(&compiled_path)
-        .expect("missing compiled guest; the harness should have compiled it already");
+/// Cargo profile ere builds the guests with. Any settings of it are in the
+/// guest manifests, which are part of the sources.
+const GUEST_PROFILE: &str = "release";
+
+/// Guest toolchains of the ere zkVM crates, as `(command, args)` for
+/// `subcommand_version`. ere compiles the guests with these rather than the
+/// host `rustc`; Miden assembles in process with the pinned crate.
+fn guest_toolchain(zkvm: &str) -> &'static [(&'static str, &'static [&'static str])] {
+    match zkvm {
+        "ere_sp1" => &[("cargo", &["prove"]), ("rustc", &["+succinct"])],
+        "ere_risc0" => &[
+            ("rzup", &[]),
+            ("cargo", &["risczero"]),
+            ("rustc", &["+risc0"]),
+        ],
+        "ere_openvm" => &[("cargo", &["openvm"])],
+        "ere_jolt" => &[("jolt", &[])],
+        "ere_nexus" => &[("cargo", &["nexus"])],
+        _ => &[],
+    }
+}
+
+/// Cache key of the compiled guest of `benchmark_name` for compiler `C`: the
+/// guest sources, the shared `guest-lib`, the manifest of the zkVM crate
+/// (which pins the ere and zkVM versions), the host Rust toolchain and the
+/// zkVM's guest toolchain, and the build options: the compiler and the guest
+/// profile.
+pub fn guest_cache_key<C: Compiler>(benchmark_name: &str) -> ArtifactKey {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let compiler = std::any::type_name::<C>();
+    let zkvm = compiler.split("::").next().unwrap_or(compiler);
+    let mut toolchain = vec![tool_version("rustc")];
+    toolchain.extend(
+        guest_toolchain(zkvm)
+            .iter()
+            .map(|(command, args)| subcommand_version(command, args)),
+    );
+    ArtifactKey {
+        kind: "zkvm-guest".to_string(),
+        name: format!("{benchmark_name}-{zkvm}"),
+        sources: vec![
+            guest_dir(benchmark_name),
+            guest_lib_dir(),
+            PathBuf::from(manifest_dir).join("Cargo.toml"),
+        ],
+        toolchain,
+        options: vec![
+            format!("compiler={compiler}"),
+            format!("profile={GUEST_PROFILE}"),
+        ],
+        ..Default::default()
+    }
+}
+
+fn read_compiled_program<C: Compiler>(path: &Path) -> CompiledProgram<C> {
+    let program_bin = fs::read(path).expect("failed to read compiled program");
     let program: C::Program = bincode::options()
         .deserialize(&program_bin)
         .expect("failed to deserialize compiled program");
@@ -93,22 +159,32 @@ pub fn load_compiled_program<C: Compiler>(benchmark_name: &str) -> CompiledProgr
     CompiledProgram { program, byte_size }
 }
 
-/// Load a compiled program if present, otherwise compile and persist it.
+/// Load a compiled program from the artifact cache, panicking if it is
+/// missing or stale. Used by RAM measurement binaries which must never
+/// trigger compilation.
+pub fn load_compiled_program<C: Compiler>(benchmark_name: &str) -> CompiledProgram<C> {
+    let dir = ArtifactCache::open_default()
+        .get(&guest_cache_key::<C>(benchmark_name))
+        .expect("failed to hash guest sources")
+        .expect("missing compiled guest; the harness should have compiled it already");
+    read_compiled_program(&dir.join(format!("{benchmark_name}.bin")))
+}
+
+/// Load a compiled program from the artifact cache, compiling it when the
+/// guest sources or toolchain changed since it was cached.
 pub fn load_or_compile_program<C: Compiler>(
     compiler: &C,
     benchmark_name: &str,
 ) -> CompiledProgram<C> {
-    let compiled_path = compiled_program_path(benchmark_name);
-    if compiled_path.exists() {
-        load_compiled_program(benchmark_name)
-    } else {
-        let program = compile_guest_program(compiler, &guest_dir(benchmark_name))
-            .expect("failed to compile guest program");
-        let bytes = bincode::options()
-            .serialize(&program.program)
-            .expect("failed to serialize compiled program");
-        fs::create_dir_all(compiled_path.parent().unwrap()).expect("failed to create directory");
-        fs::write(&compiled_path, bytes).expect("failed to write compiled program file");
-        program
-    }
+    let dir = ArtifactCache::open_default()
+        .get_or_build(&guest_cache_key::<C>(benchmark_name), |out| {
+            let program = compile_guest_program(compiler, &guest_dir(benchmark_name))
+                .map_err(io::Error::other)?;
+            let bytes = bincode::options()
+                .serialize(&program.program)
+                .map_err(io::Error::other)?;
+            fs::write(out.join(format!("{benchmark_name}.bin")), bytes)
+        })
+        .expect("failed to compile guest program");
+    read_compiled_program(&dir.join(format!("{benchmark_name}.bin")))
 }
@@ -0,0 +1,167 @@
+use crate::inputs::GeneratedInput;
+use crate::zkvm::instance::ProofArtifacts;
+use crate::zkvm::traits::{PreparedBenchmark, ZkTarget};
+use ere_zkvm_interface::{Input, Proof, ProofKind, PublicValues, zkVM, zkVMError};
+
+/// Proof kind the zkVM benchmarks prove with, from `BENCH_PROOF_KIND`:
+/// unset (ere's default kind), `compressed` or `groth16`.
+pub fn bench_proof_kind() -> ProofKind {
+    match std::env::var("BENCH_PROOF_KIND").ok().as_deref() {
+        None | Some("") => ProofKind::default(),
+        Some(kind) => parse_proof_kind(kind).unwrap_or_else(|e| panic!("BENCH_PROOF_KIND: {e}")),
+    }
+}
+
+pub fn parse_proof_kind(kind: &str) -> Result<ProofKind, String> {
+    match kind {
+        "compressed" => Ok(ProofKind::Compressed),
+        "groth16" => Ok(ProofKind::Groth16),
+        other => Err(format!(
+            "unknown proof kind '{other}', expected compressed or groth16"
+        )),
+    }
+}
+
+/// Feature tag of the zkVM benchmarks: the tag of `bench_proof_kind()`.
+pub fn bench_proof_feature() -> Option<&'static str> {
+    proof_kind_feature(bench_proof_kind())
+}
+
+/// Feature tag of the results of proofs of `kind`. The default kind is
+/// untagged, so its results keep the names of the plain benchmark.
+pub fn proof_kind_feature(kind: ProofKind) -> Option<&'static str> {
+    match kind {
+        _ if kind == ProofKind::default() => None,
+        ProofKind::Compressed => Some("compressed"),
+        ProofKind::Groth16 => Some("groth16"),
+    }
+}
+
+/// A compiled guest program with its input, ready to execute, prove and
+/// verify on `V`. The target `T` encodes the input and checks the public values.
+pub struct PreparedProgram<V, T: ZkTarget> {
+    vm: V,
+    input: Input,
+    compiled_size: usize,
+    data: T::Data,
+    proof_kind: ProofKind,
+}
+
+impl<V, T: ZkTarget> PreparedProgram<V, T> {
+    /// Prepares the program for `data`, e.g. a test vector or a negative
+    /// instance, to prove with `bench_proof_kind()`.
+    pub fn new(vm: V, compiled_size: usize, data: T::Data) -> Self {
+        Self {
+            vm,
+            input: T::encode_input(&data),
+            compiled_size,
+            data,
+            proof_kind: bench_proof_kind(),
+        }
+    }
+
+    /// Prepares the program for the benchmark input of `input_size`.
+    pub fn prepare(vm: V, compiled_size: usize, input_size: usize) -> Self {
+        Self::new(vm, compiled_size, T::generate(input_size))
+    }
+
+    /// Prepares the program for a `GeneratedInput` (see `ZkTarget::from_generated`).
+    pub fn from_generated(vm: V, compiled_size: usize, input: &GeneratedInput) -> Option<Self> {
+        Some(Self::new(vm, compiled_size, T::from_generated(input)?))
+    }
+
+    /// Proves with `kind` instead of `bench_proof_kind()`.
+    pub fn with_proof_kind(mut self, kind: ProofKind) -> Self {
+        self.proof_kind = kind;
+        self
+    }
+
+    pub fn compiled_size(&self) -> usize {
+        self.compiled_size
+    }
+
+    pub fn proof_kind(&self) -> ProofKind {
+        self.proof_kind
+    }
+
+    pub fn data(&self) -> &T::Data {
+        &self.data
+    }
+
+    pub fn vm(&self) -> &V {
+        &self.vm
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+}
+
+impl<V: zkVM, T: ZkTarget> PreparedProgram<V, T> {
+    pub fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
+        let (public_values, proof, report) = self.vm.prove(&self.input, self.proof_kind)?;
+        Ok(ProofArtifacts::new(public_values, proof, report))
+    }
+
+    pub fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
+        self.vm.verify(proof)
+    }
+
+    /// Verifies the proof and checks the public values against the input.
+    pub fn verify_with_expected(&self, proof: &ProofArtifacts) -> Result<(), zkVMError> {
+        let public_values = self.vm.verify(&proof.proof)?;
+
+        if public_values != proof.public_values {
+            return Err(zkVMError::other("public values mismatch"));
+        }
+        T::check_public_values(&self.data, &public_values).map_err(zkVMError::other)?;
+        Ok(())
+    }
+
+    pub fn execution_cycles(&self) -> Result<u64, zkVMError> {
+        let (_, report) = self.vm.execute(&self.input)?;
+        Ok(report.total_num_cycles)
+    }
+}
+
+impl<V: zkVM, T: ZkTarget> PreparedBenchmark for PreparedProgram<V, T> {
+    type VM = V;
+
+    fn compiled_size(&self) -> usize {
+        self.compiled_size
+    }
+
+    fn execution_cycles(&self) -> Result<u64, zkVMError> {
+        PreparedProgram::execution_cycles(self)
+    }
+
+    fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
+        PreparedProgram::prove(self)
+    }
+
+    fn vm(&self) -> &Self::VM {
+        &self.vm
+    }
+
+    fn input(&self) -> &Input {
+        &self.input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proof_kind_feature() {
+        assert_eq!(proof_kind_feature(ProofKind::default()), None);
+        assert_eq!(proof_kind_feature(ProofKind::Groth16), Some("groth16"));
+    }
+
+    #[test]
+    fn test_parse_proof_kind() {
+        assert_eq!(parse_proof_kind("compressed"), Ok(ProofKind::Compressed));
+        assert_eq!(parse_proof_kind("groth16"), Ok(ProofKind::Groth16));
+        assert!(parse_proof_kind("plonk").is_err());
+    }
+}
@@ -1,119 +1,51 @@
-use crate::zkvm::instance::ProofArtifacts;
-use crate::zkvm::traits::PreparedBenchmark;
-use ere_zkvm_interface::{Input, Proof, ProofKind, PublicValues, zkVM, zkVMError};
+use crate::inputs::GeneratedInput;
+use crate::zkvm::program::PreparedProgram;
+use crate::zkvm::traits::ZkTarget;
+use ere_zkvm_interface::Input;
 
 /// Benchmark name for SHA256 programs.
 pub const SHA256_BENCH: &str = "sha256";
 
-/// Common preparation data for zkVM SHA-256 benchmarks.
-pub struct PreparedSha256<V> {
-    vm: V,
-    input: Input,
-    compiled_size: usize,
-    expected_digest: Option<Vec<u8>>,
-}
-
-impl<V> PreparedSha256<V> {
-    pub fn new(vm: V, input: Input, compiled_size: usize) -> Self {
-        Self {
-            vm,
-            input,
-            compiled_size,
-            expected_digest: None,
-        }
-    }
-
-    pub fn with_expected_digest(
-        vm: V,
-        input: Input,
-        compiled_size: usize,
-        expected_digest: Vec<u8>,
-    ) -> Self {
-        Self {
-            vm,
-            input,
-            compiled_size,
-            expected_digest: Some(expected_digest),
-        }
-    }
-
-    pub fn compiled_size(&self) -> usize {
-        self.compiled_size
-    }
-
-    pub fn expected_digest(&self) -> Option<&[u8]> {
-        self.expected_digest.as_deref()
-    }
+/// Prepared SHA-256 program.
+pub type PreparedSha256<V> = PreparedProgram<V, Sha256Target>;
 
-    pub fn vm(&self) -> &V {
-        &self.vm
-    }
+/// SHA-256 guests: read the raw message, commit the 32-byte digest.
+pub struct Sha256Target;
 
-    pub fn input(&self) -> &Input {
-        &self.input
-    }
-}
+impl ZkTarget for Sha256Target {
+    const NAME: &'static str = SHA256_BENCH;
+    /// (message, digest)
+    type Data = (Vec<u8>, Vec<u8>);
 
-impl<V> PreparedSha256<V>
-where
-    V: zkVM,
-{
-    pub fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
-        let (public_values, proof, report) = self.vm.prove(&self.input, ProofKind::default())?;
-        Ok(ProofArtifacts::new(public_values, proof, report))
+    fn generate(input_size: usize) -> Self::Data {
+        crate::generate_sha256_input(input_size)
     }
 
-    pub fn verify(&self, proof: &Proof) -> Result<PublicValues, zkVMError> {
-        self.vm.verify(proof)
+    fn encode_input((message, _): &Self::Data) -> Input {
+        build_input(message.clone())
     }
 
-    pub fn verify_with_digest(&self, proof: &ProofArtifacts) -> Result<(), zkVMError> {
-        let public_values = self.vm.verify(&proof.proof)?;
-
-        if public_values != proof.public_values {
-            return Err(zkVMError::other("public values mismatch"));
+    fn check_public_values((_, digest): &Self::Data, public_values: &[u8]) -> Result<(), String> {
+        if public_values != digest.as_slice() {
+            return Err("digest mismatch".to_string());
         }
-
-        match &self.expected_digest {
-            None => {}
-            Some(expected) => {
-                if public_values != *expected {
-                    return Err(zkVMError::other("digest mismatch"));
-                }
-            }
-        }
-
         Ok(())
     }
 
-    pub fn execution_cycles(&self) -> Result<u64, zkVMError> {
-        let (_, report) = self.vm.execute(&self.input)?;
-        Ok(report.total_num_cycles)
+    fn from_generated(input: &GeneratedInput) -> Option<Self::Data> {
+        hash_data(input)
     }
 }
 
-impl<V: zkVM> PreparedBenchmark for PreparedSha256<V> {
-    type VM = V;
-
-    fn compiled_size(&self) -> usize {
-        self.compiled_size
-    }
-
-    fn execution_cycles(&self) -> Result<u64, zkVMError> {
-        PreparedSha256::execution_cycles(self)
-    }
-
-    fn prove(&self) -> Result<ProofArtifacts, zkVMError> {
-        PreparedSha256::prove(self)
-    }
-
-    fn vm(&self) -> &Self::VM {
-        &self.vm
-    }
-
-    fn input(&self) -> &Input {
-        &self.input
-    }
+/// `(message, digest)` of the `input`, `input_len` and `digest` fields of a
+/// hash input. The guest hashes the first `input_len` bytes of `input`.
+pub fn hash_data(input: &GeneratedInput) -> Option<(Vec<u8>, Vec<u8>)> {
+    let message = input.bytes("input")?;
+    let len = input.int("input_len")?;
+    Some((
+        message.get(..len)?.to_vec(),
+        input.bytes("digest")?.to_vec(),
+    ))
 }
 
 /// Builds default zkVM input from raw message bytes.
@@ -122,3 +54,21 @@ pub fn build_input(message_bytes: Vec<u8>) -> Input {
     input.write_bytes(message_bytes);
     input
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BenchTarget;
+    use crate::negative::{NegativeCase, negative_input};
+
+    #[test]
+    fn test_hash_data_follows_input_len() {
+        let (message, digest) = hash_data(&GeneratedInput::sha256(128)).unwrap();
+        assert_eq!((message, digest), crate::generate_sha256_input(128));
+
+        let input = negative_input(BenchTarget::Sha256, 128, NegativeCase::LengthMismatch).unwrap();
+        let (message, digest) = hash_data(&input).unwrap();
+        assert_eq!(message.len(), 127);
+        assert!(Sha256Target::check_public_values(&(message, digest), &[0; 32]).is_err());
+    }
+}
@@ -1,9 +1,31 @@
+use crate::inputs::GeneratedInput;
 use crate::zkvm::instance::ProofArtifacts;
 use ere_zkvm_interface::{Compiler, Input, zkVM, zkVMError};
 
-/// Program to be benchmarked.
-pub trait Program {
+/// A benchmark program run by every zkVM: owns how its input is generated
+/// and encoded for the guest, and how the committed public values are checked.
+/// Adding a target takes a guest under `guest/<NAME>` and one impl.
+pub trait ZkTarget {
+    /// Guest directory name under `guest/`.
     const NAME: &'static str;
+    /// Generated input, including what the public values are checked against.
+    type Data;
+
+    /// Benchmark input for `input_size`.
+    fn generate(input_size: usize) -> Self::Data;
+
+    /// zkVM input the guest reads.
+    fn encode_input(data: &Self::Data) -> Input;
+
+    /// Checks the public values the guest committed for `data`.
+    fn check_public_values(data: &Self::Data, public_values: &[u8]) -> Result<(), String>;
+
+    /// Input for the fields of a `GeneratedInput`, e.g. a negative instance
+    /// from `utils::negative`. `None` if the guest cannot express it.
+    fn from_generated(input: &GeneratedInput) -> Option<Self::Data> {
+        let _ = input;
+        None
+    }
 }
 
 /// Common interface for prepared benchmark instances.
@@ -26,23 +48,6 @@ pub trait PreparedBenchmark {
     fn input(&self) -> &Input;
 }
 
-/// Marker trait for benchmark configuration types.
-pub trait BenchmarkConfig {}
-
-/// Generates reusable data for benchmarks.
-pub trait DataGenerator<C: BenchmarkConfig> {
-    type Data;
-
-    fn generate(&self, config: &C) -> (Self::Data, usize);
-}
-
-/// Builds a zkVM specific input from generated data.
-pub trait InputBuilder<P: Program> {
-    type Data;
-
-    fn build_input(data: Self::Data) -> Input;
-}
-
 /// Builds a zkVM instance with a compiled program.
 pub trait ZkVMBuilder<C: Compiler, V: zkVM> {
     type Error: std::error::Error + Send + Sync + 'static;